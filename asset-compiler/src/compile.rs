@@ -0,0 +1,608 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Value};
+use server::{
+    action::{self, Action, ActionStep},
+    appearance::{AppearanceFeature, Locale, LocalizedAppearanceFeature},
+    asset::{
+        baseline::Baseline,
+        encounter::{Encounter, EncounterBlob},
+        level::Level,
+        location_map::{
+            EncounterIdSample, EncounterIdsSampler, LocationMap, LocationMapConnection,
+        },
+        location_map_theme::{EntityBlobSample, EntityBlobsSampler, LocationMapTheme},
+        r#trait::Trait,
+        reference::{AssetKind, AssetNames},
+        stat_block::StatBlock,
+        validation, AssetPack,
+    },
+    entity::EntityBlob,
+    prominence::ProminenceRule,
+};
+use spacetimedb_sats::time_duration::TimeDuration;
+
+use crate::{
+    sats_json,
+    source::{
+        self, BlobSampleSource, BlobSource, LocalizedAppearanceFeatureSource, PackSource,
+        StatBlockSource,
+    },
+};
+
+struct Compiler {
+    errors: Vec<String>,
+    names: AssetNames,
+}
+
+/// Keeps explicit ids and gives every other entry the lowest free id, in order.
+fn assign_ids(entries: impl Iterator<Item = Option<u32>> + Clone) -> Vec<u32> {
+    let taken: HashSet<u32> = entries.clone().flatten().collect();
+    let mut next_id = 0u32;
+    entries
+        .map(|id| {
+            id.unwrap_or_else(|| {
+                while taken.contains(&next_id) {
+                    next_id = next_id.saturating_add(1);
+                }
+                let id = next_id;
+                next_id = next_id.saturating_add(1);
+                id
+            })
+        })
+        .collect()
+}
+
+impl Compiler {
+    fn error(&mut self, message: String) {
+        self.errors.push(message);
+    }
+
+    /// Assigns the ids of one asset kind and names them for `resolve`.
+    fn assign_ids<'a>(
+        &mut self,
+        kind: AssetKind,
+        entries: impl Iterator<Item = (&'a str, Option<u32>)> + Clone,
+    ) -> Vec<u32> {
+        let ids = assign_ids(entries.clone().map(|(_, id)| id));
+        for ((name, _), id) in entries.zip(&ids) {
+            if !self.names.insert(kind, name, *id) {
+                self.error(format!("{} {:?}: duplicate name.", kind, name));
+            }
+        }
+        ids
+    }
+
+    fn resolve(&mut self, kind: AssetKind, context: &str, name: &str) -> u32 {
+        self.names.resolve(kind, name).unwrap_or_else(|| {
+            self.error(format!("{}: unknown {} {:?}.", context, kind, name));
+            0
+        })
+    }
+
+    fn resolve_all(&mut self, kind: AssetKind, context: &str, names: &[String]) -> Vec<u32> {
+        names
+            .iter()
+            .map(|name| self.resolve(kind, context, name))
+            .collect()
+    }
+
+    /// Compiles the `locale` translations of `canonical` features, numbering
+    /// them from `next_id`.
+    fn localized_appearance_features(
+        &mut self,
+        locale: &str,
+        features: &[LocalizedAppearanceFeatureSource],
+        canonical: &[AppearanceFeature],
+        next_id: &mut u64,
+    ) -> Vec<LocalizedAppearanceFeature> {
+        let mut localized = vec![];
+        for f in features {
+            let context = format!("{} appearance feature {:?}", locale, f.text);
+            let index = self.resolve(AssetKind::AppearanceFeature, &context, &f.feature);
+            let Some(canonical) = canonical.iter().find(|c| c.index == index) else {
+                continue;
+            };
+            localized.push(LocalizedAppearanceFeature {
+                id: *next_id,
+                locale: locale.to_string(),
+                index,
+                text: f.text.clone(),
+                appearance_feature_type: f
+                    .appearance_feature_type
+                    .map_or_else(|| canonical.appearance_feature_type.clone(), Into::into),
+                priority: f.priority.unwrap_or(canonical.priority),
+            });
+            *next_id = next_id.saturating_add(1);
+        }
+        localized
+    }
+
+    fn stat_block(&mut self, context: &str, source: &StatBlockSource) -> StatBlock {
+        let mut fields = match sats_json::default_fields::<StatBlock>() {
+            Ok(fields) => fields,
+            Err(err) => {
+                self.error(format!("{}: {}", context, err));
+                return StatBlock::default();
+            }
+        };
+        for (stat, value) in &source.stats {
+            match fields.get_mut(stat) {
+                Some(field @ Value::Number(_)) => *field = json!(value),
+                _ => self.error(format!("{}: unknown stat {:?}.", context, stat)),
+            }
+        }
+        let mut stat_block = sats_json::from_value(Value::Object(fields)).unwrap_or_else(|err| {
+            self.error(format!("{}: {}", context, err));
+            StatBlock::default()
+        });
+        stat_block.action_ids = self.resolve_all(AssetKind::Action, context, &source.actions);
+        stat_block.appearance_feature_ids = self.resolve_all(
+            AssetKind::AppearanceFeature,
+            context,
+            &source.appearance_features,
+        );
+        stat_block
+    }
+
+    fn blob(&mut self, context: &str, source: &BlobSource) -> EntityBlob {
+        let mut fields = match sats_json::default_fields::<EntityBlob>() {
+            Ok(fields) => fields,
+            Err(err) => {
+                self.error(format!("{}: {}", context, err));
+                return EntityBlob::default();
+            }
+        };
+        let mut named = vec![];
+        if let Some(name) = &source.baseline {
+            let baseline_id = self.resolve(AssetKind::Baseline, context, name);
+            named.push(("baseline", json!({ "baseline_id": baseline_id })));
+        }
+        if let Some(names) = &source.traits {
+            let trait_ids = self.resolve_all(AssetKind::Trait, context, names);
+            named.push(("traits", json!({ "trait_ids": trait_ids })));
+        }
+        if let Some(names) = &source.actions {
+            let action_ids = self.resolve_all(AssetKind::Action, context, names);
+            named.push(("actions", json!({ "action_ids": action_ids })));
+        }
+        let components = source
+            .components
+            .iter()
+            .map(|(c, v)| (c.as_str(), Value::from(v.clone())))
+            .chain(named);
+        let mut seen = HashSet::new();
+        for (component, mut value) in components {
+            if !seen.insert(component) {
+                self.error(format!(
+                    "{}: component {:?} is given more than once.",
+                    context, component
+                ));
+                continue;
+            }
+            let Some(field) = fields.get_mut(component) else {
+                self.error(format!("{}: unknown component {:?}.", context, component));
+                continue;
+            };
+            if let Value::Object(component_fields) = &mut value {
+                component_fields.insert("entity_id".to_string(), json!(0));
+            }
+            *field = sats_json::some(value);
+        }
+        sats_json::from_value(Value::Object(fields)).unwrap_or_else(|err| {
+            self.error(format!("{}: {}", context, err));
+            EntityBlob::default()
+        })
+    }
+
+    fn blobs_sampler(&mut self, context: &str, samples: &[BlobSampleSource]) -> EntityBlobsSampler {
+        EntityBlobsSampler {
+            selections: samples
+                .iter()
+                .map(|s| EntityBlobSample {
+                    weight: s.weight,
+                    blob: self.blob(context, &s.blob),
+                })
+                .collect(),
+        }
+    }
+
+    fn action_effect(
+        &mut self,
+        context: &str,
+        effect: &source::ActionEffect,
+    ) -> action::ActionEffect {
+        use action::ActionEffect as E;
+        use source::ActionEffect as S;
+        match effect {
+            S::Buff(source::Buff::Guard(guard)) => E::Buff(action::Buff::Guard(*guard)),
+            S::Attack(damage) => E::Attack(action::Damage {
+                amount: damage.amount,
+                damage_type: damage.damage_type.into(),
+            }),
+            S::Heal(heal) => E::Heal(*heal),
+            S::Rest => E::Rest,
+            S::Move => E::Move,
+            S::Take => E::Take,
+            S::Drop => E::Drop,
+            S::Equip => E::Equip,
+            S::Unequip => E::Unequip,
+            S::AddTrait(name) => E::AddTrait(self.resolve(AssetKind::Trait, context, name)),
+            S::RemoveTrait(name) => E::RemoveTrait(self.resolve(AssetKind::Trait, context, name)),
+        }
+    }
+}
+
+/// Resolves names to ids and validates the result, returning every error found.
+pub fn compile(source: PackSource) -> Result<AssetPack, Vec<String>> {
+    let mut compiler = Compiler {
+        errors: vec![],
+        names: AssetNames::default(),
+    };
+
+    let action_ids = compiler.assign_ids(
+        AssetKind::Action,
+        source.actions.iter().map(|a| (a.name.as_str(), a.id)),
+    );
+    let appearance_feature_indexes = compiler.assign_ids(
+        AssetKind::AppearanceFeature,
+        source
+            .appearance_features
+            .iter()
+            .map(|f| (f.text.as_str(), f.index)),
+    );
+    let baseline_ids = compiler.assign_ids(
+        AssetKind::Baseline,
+        source.baselines.iter().map(|b| (b.name.as_str(), b.id)),
+    );
+    let trait_ids = compiler.assign_ids(
+        AssetKind::Trait,
+        source.traits.iter().map(|t| (t.name.as_str(), t.id)),
+    );
+    let encounter_blob_ids = compiler.assign_ids(
+        AssetKind::EncounterBlob,
+        source
+            .encounter_blobs
+            .iter()
+            .map(|b| (b.name.as_str(), b.id)),
+    );
+    let encounter_ids = compiler.assign_ids(
+        AssetKind::Encounter,
+        source.encounters.iter().map(|e| (e.name.as_str(), e.id)),
+    );
+    let theme_ids = compiler.assign_ids(
+        AssetKind::LocationMapTheme,
+        source.themes.iter().map(|t| (t.name.as_str(), t.id)),
+    );
+    let map_ids = compiler.assign_ids(
+        AssetKind::LocationMap,
+        source.maps.iter().map(|m| (m.name.as_str(), m.id)),
+    );
+
+    let mut next_step_id = 0u64;
+    let mut action_steps = vec![];
+    for (a, id) in source.actions.iter().zip(&action_ids) {
+        let context = format!("action {:?}", a.name);
+        for (sequence_index, step) in (0i32..).zip(&a.steps) {
+            action_steps.push(ActionStep {
+                id: next_step_id,
+                action_id: *id,
+                sequence_index,
+                action_effect: compiler.action_effect(&context, &step.effect),
+                intensity: step.intensity.into(),
+                trait_name: None,
+            });
+            next_step_id = next_step_id.saturating_add(1);
+        }
+    }
+    let pack_actions = source
+        .actions
+        .iter()
+        .zip(&action_ids)
+        .map(|(a, id)| Action {
+            id: *id,
+            name: a.name.clone(),
+            action_type: a.action_type.into(),
+            renderer: a.renderer.as_ref().map(|r| action::AttackRenderer {
+                weight_type: r.weight_type.into(),
+                speed_type: r.speed_type.into(),
+                armament_type: r.armament_type.into(),
+            }),
+        })
+        .collect();
+
+    let pack_appearance_features: Vec<AppearanceFeature> = source
+        .appearance_features
+        .iter()
+        .zip(&appearance_feature_indexes)
+        .map(|(f, index)| AppearanceFeature {
+            index: *index,
+            text: f.text.clone(),
+            appearance_feature_type: f.appearance_feature_type.into(),
+            priority: f.priority,
+        })
+        .collect();
+
+    let locale_ids = assign_ids(source.locales.iter().map(|l| l.id));
+    let mut next_localized_id = 0u64;
+    let mut locales = vec![];
+    let mut localized_appearance_features = vec![];
+    for (l, id) in source.locales.iter().zip(&locale_ids) {
+        locales.push(Locale {
+            id: *id,
+            code: l.code.clone(),
+            fallback_noun: l.fallback_noun.clone(),
+            word_separator: l.word_separator.clone(),
+            article: l.article.clone(),
+            vowel_article: l.vowel_article.clone(),
+        });
+        localized_appearance_features.extend(compiler.localized_appearance_features(
+            &l.code,
+            &l.appearance_features,
+            &pack_appearance_features,
+            &mut next_localized_id,
+        ));
+    }
+
+    let pack_baselines = source
+        .baselines
+        .iter()
+        .zip(&baseline_ids)
+        .map(|(b, id)| Baseline {
+            id: *id,
+            name: b.name.clone(),
+            stat_block: compiler.stat_block(&format!("baseline {:?}", b.name), &b.stat_block),
+            action_names: vec![],
+        })
+        .collect();
+    let pack_traits = source
+        .traits
+        .iter()
+        .zip(&trait_ids)
+        .map(|(t, id)| Trait {
+            id: *id,
+            name: t.name.clone(),
+            stat_block: compiler.stat_block(&format!("trait {:?}", t.name), &t.stat_block),
+            action_names: vec![],
+        })
+        .collect();
+    let levels = source
+        .levels
+        .iter()
+        .map(|l| Level {
+            level: l.level,
+            xp: l.xp,
+            trait_ids: compiler.resolve_all(
+                AssetKind::Trait,
+                &format!("level {}", l.level),
+                &l.traits,
+            ),
+            trait_names: vec![],
+        })
+        .collect();
+
+    let prominence_rules = source
+        .prominence_rules
+        .iter()
+        .map(|r| ProminenceRule {
+            condition: r.condition.into(),
+            weight: r.weight,
+        })
+        .collect();
+
+    let pack_encounter_blobs = source
+        .encounter_blobs
+        .iter()
+        .zip(&encounter_blob_ids)
+        .map(|(b, id)| EncounterBlob {
+            id: *id,
+            name: b.name.clone(),
+            blob: compiler.blob(&format!("encounter blob {:?}", b.name), &b.blob),
+        })
+        .collect();
+    let pack_encounters = source
+        .encounters
+        .iter()
+        .zip(&encounter_ids)
+        .map(|(e, id)| {
+            let context = format!("encounter {:?}", e.name);
+            Encounter {
+                id: *id,
+                categoric_blob_id: compiler.resolve(
+                    AssetKind::EncounterBlob,
+                    &context,
+                    &e.categoric_blob,
+                ),
+                blob_ids: compiler.resolve_all(AssetKind::EncounterBlob, &context, &e.blobs),
+                categoric_blob_name: None,
+                blob_names: vec![],
+                respawn_delay: e.respawn_delay_micros.map(TimeDuration::from_micros),
+            }
+        })
+        .collect();
+
+    let location_map_themes = source
+        .themes
+        .iter()
+        .zip(&theme_ids)
+        .map(|(t, id)| {
+            let context = format!("theme {:?}", t.name);
+            LocationMapTheme {
+                id: *id,
+                name: t.name.clone(),
+                decorations_selector: compiler.blobs_sampler(&context, &t.decorations),
+                min_decoration_count: t.min_decoration_count,
+                max_decoration_count: t.max_decoration_count,
+                paths_selector: compiler.blobs_sampler(&context, &t.paths),
+                rooms_selector: compiler.blobs_sampler(&context, &t.rooms),
+            }
+        })
+        .collect();
+    let location_maps = source
+        .maps
+        .iter()
+        .zip(&map_ids)
+        .map(|(m, id)| {
+            let context = format!("map {:?}", m.name);
+            LocationMap {
+                id: *id,
+                name: m.name.clone(),
+                theme_id: compiler.resolve(AssetKind::LocationMapTheme, &context, &m.theme),
+                theme_name: None,
+                layout: m.layout.into(),
+                rng_seed: m.rng_seed,
+                extra_room_count: m.extra_room_count,
+                main_room_count: m.main_room_count,
+                loop_count: m.loop_count,
+                encounter_ids_sampler: EncounterIdsSampler {
+                    selections: m
+                        .encounters
+                        .iter()
+                        .map(|s| EncounterIdSample {
+                            weight: s.weight,
+                            id: compiler.resolve(AssetKind::Encounter, &context, &s.encounter),
+                        })
+                        .collect(),
+                },
+                min_encounter_count: m.min_encounter_count,
+                max_encounter_count: m.max_encounter_count,
+            }
+        })
+        .collect();
+    let mut connections = HashSet::new();
+    for c in &source.connections {
+        if !connections.insert((&c.exit, &c.destination)) {
+            compiler.error(format!(
+                "connection {:?} -> {:?}: duplicate connection.",
+                c.exit, c.destination
+            ));
+        }
+    }
+    let connection_ids = assign_ids(source.connections.iter().map(|c| c.id));
+    let location_map_connections = source
+        .connections
+        .iter()
+        .zip(&connection_ids)
+        .map(|(c, id)| {
+            let context = format!("connection {:?} -> {:?}", c.exit, c.destination);
+            LocationMapConnection {
+                id: *id,
+                exit_location_map_id: compiler.resolve(AssetKind::LocationMap, &context, &c.exit),
+                destination_location_map_id: compiler.resolve(
+                    AssetKind::LocationMap,
+                    &context,
+                    &c.destination,
+                ),
+            }
+        })
+        .collect();
+
+    let instantiate_entity_blobs = source
+        .instantiate_entity_blobs
+        .iter()
+        .enumerate()
+        .map(|(i, b)| compiler.blob(&format!("instantiate_entity_blobs[{}]", i), b))
+        .collect();
+    let new_player_blob = match &source.new_player_blob {
+        Some(b) => compiler.blob("new_player_blob", b),
+        None => {
+            compiler.error("new_player_blob is missing.".to_string());
+            EntityBlob::default()
+        }
+    };
+    let version = source.version.unwrap_or_else(|| {
+        compiler.error("version is missing.".to_string());
+        0
+    });
+
+    let asset_pack = AssetPack {
+        version,
+        actions: pack_actions,
+        action_steps,
+        appearance_features: pack_appearance_features,
+        locales,
+        localized_appearance_features,
+        baselines: pack_baselines,
+        traits: pack_traits,
+        levels,
+        encounter_blobs: pack_encounter_blobs,
+        encounters: pack_encounters,
+        location_map_themes,
+        location_maps,
+        location_map_connections,
+        prominence_rules,
+        instantiate_entity_blobs,
+        new_player_blob,
+    };
+
+    let mut errors = compiler.errors;
+    if errors.is_empty() {
+        errors.extend(
+            validation::validate(&asset_pack)
+                .iter()
+                .map(|e| e.to_string()),
+        );
+    }
+    if errors.is_empty() {
+        Ok(asset_pack)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::Format;
+
+    fn compile_ron(text: &str) -> Result<AssetPack, Vec<String>> {
+        compile(Format::Ron.parse(text).map_err(|e| vec![e])?)
+    }
+
+    #[test]
+    fn ids_are_assigned_around_explicit_ids() -> Result<(), Vec<String>> {
+        let pack = compile_ron(
+            r#"(
+                version: Some(1),
+                new_player_blob: Some(()),
+                actions: [
+                    (name: "a", action_type: Move, steps: [(effect: Move, intensity: Normal)]),
+                    (id: Some(0), name: "b", action_type: Move, steps: []),
+                    (name: "c", action_type: Move, steps: []),
+                ],
+            )"#,
+        )?;
+
+        let ids: Vec<(u32, &str)> = pack
+            .actions
+            .iter()
+            .map(|a| (a.id, a.name.as_str()))
+            .collect();
+        assert_eq!(ids, vec![(1, "a"), (0, "b"), (2, "c")]);
+        assert_eq!(pack.action_steps.first().map(|s| s.action_id), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_names_are_reported() {
+        let errors = compile_ron(
+            r#"(
+                version: Some(1),
+                baselines: [(name: "human", stat_block: (actions: ["kick"], stats: {"luck": 1}))],
+                new_player_blob: Some((baseline: Some("elf"), components: {"wings": {}})),
+            )"#,
+        )
+        .err()
+        .unwrap_or_default();
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"baseline "human": unknown stat "luck"."#,
+                r#"baseline "human": unknown action "kick"."#,
+                r#"new_player_blob: unknown baseline "elf"."#,
+                r#"new_player_blob: unknown component "wings"."#,
+            ]
+        );
+    }
+}
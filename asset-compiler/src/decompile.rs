@@ -0,0 +1,505 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::Value;
+use server::{
+    action,
+    appearance::{AppearanceFeature, LocalizedAppearanceFeature},
+    asset::{location_map_theme::EntityBlobsSampler, stat_block::StatBlock, AssetPack},
+    entity::EntityBlob,
+};
+
+use crate::{
+    sats_json,
+    source::{
+        self, ActionSource, ActionStepSource, AppearanceFeatureSource, AttackRenderer,
+        BlobSampleSource, BlobSource, ComponentValue, ConnectionSource, EncounterBlobSource,
+        EncounterSampleSource, EncounterSource, LevelSource, LocaleSource,
+        LocalizedAppearanceFeatureSource, MapSource, PackSource, ProminenceRuleSource,
+        StatBlockAssetSource, StatBlockSource, ThemeSource,
+    },
+};
+
+/// Names of one asset kind, keyed by id.
+struct Names {
+    kind: &'static str,
+    by_id: HashMap<u32, String>,
+}
+
+impl Names {
+    fn new(kind: &'static str, entries: impl Iterator<Item = (u32, String)>) -> Self {
+        Self {
+            kind,
+            by_id: entries.collect(),
+        }
+    }
+
+    fn name(&self, errors: &mut Vec<String>, context: &str, id: u32) -> String {
+        match self.by_id.get(&id) {
+            Some(name) => name.clone(),
+            None => {
+                errors.push(format!("{}: unknown {} id {}.", context, self.kind, id));
+                id.to_string()
+            }
+        }
+    }
+
+    fn names(&self, errors: &mut Vec<String>, context: &str, ids: &[u32]) -> Vec<String> {
+        ids.iter()
+            .map(|id| self.name(errors, context, *id))
+            .collect()
+    }
+}
+
+struct Decompiler {
+    errors: Vec<String>,
+    actions: Names,
+    appearance_features: Names,
+    baselines: Names,
+    traits: Names,
+}
+
+impl Decompiler {
+    /// Names the `locale` translations after their canonical features, leaving
+    /// out the type and priority where they match.
+    fn localized_appearance_features(
+        &mut self,
+        locale: &str,
+        features: &[&LocalizedAppearanceFeature],
+        canonical: &[AppearanceFeature],
+    ) -> Vec<LocalizedAppearanceFeatureSource> {
+        features
+            .iter()
+            .map(|f| {
+                let canonical = canonical.iter().find(|c| c.index == f.index);
+                let appearance_feature_type =
+                    source::AppearanceFeatureType::from(&f.appearance_feature_type);
+                LocalizedAppearanceFeatureSource {
+                    feature: self.appearance_features.name(
+                        &mut self.errors,
+                        &format!("{} appearance feature {:?}", locale, f.text),
+                        f.index,
+                    ),
+                    text: f.text.clone(),
+                    appearance_feature_type: Some(appearance_feature_type).filter(|t| {
+                        canonical.map(|c| (&c.appearance_feature_type).into()) != Some(*t)
+                    }),
+                    priority: Some(f.priority)
+                        .filter(|p| canonical.map(|c| c.priority) != Some(*p)),
+                }
+            })
+            .collect()
+    }
+
+    fn stat_block(&mut self, context: &str, stat_block: &StatBlock) -> StatBlockSource {
+        let mut stats = BTreeMap::new();
+        match sats_json::to_value(stat_block) {
+            Ok(Value::Object(fields)) => {
+                for (stat, value) in fields {
+                    if let Some(value) = value.as_i64().and_then(|v| i32::try_from(v).ok()) {
+                        if value != 0 {
+                            stats.insert(stat, value);
+                        }
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(err) => self.errors.push(format!("{}: {}", context, err)),
+        }
+        StatBlockSource {
+            stats,
+            actions: self
+                .actions
+                .names(&mut self.errors, context, &stat_block.action_ids),
+            appearance_features: self.appearance_features.names(
+                &mut self.errors,
+                context,
+                &stat_block.appearance_feature_ids,
+            ),
+        }
+    }
+
+    fn blob(&mut self, context: &str, blob: &EntityBlob) -> BlobSource {
+        let mut source = BlobSource::default();
+        match sats_json::to_value(blob) {
+            Ok(Value::Object(fields)) => {
+                for (component, value) in fields {
+                    let Some(mut value) = sats_json::unwrap_some(value) else {
+                        continue;
+                    };
+                    if let Value::Object(component_fields) = &mut value {
+                        component_fields.remove("entity_id");
+                    }
+                    match ComponentValue::try_from(value) {
+                        Ok(value) => {
+                            source.components.insert(component, value);
+                        }
+                        Err(err) => self
+                            .errors
+                            .push(format!("{}: {}: {}", context, component, err)),
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(err) => self.errors.push(format!("{}: {}", context, err)),
+        }
+        if let Some(b) = &blob.baseline {
+            source.components.remove("baseline");
+            source.baseline = Some(
+                self.baselines
+                    .name(&mut self.errors, context, b.baseline_id),
+            );
+        }
+        if let Some(t) = &blob.traits {
+            source.components.remove("traits");
+            source.traits = Some(self.traits.names(&mut self.errors, context, &t.trait_ids));
+        }
+        if let Some(a) = &blob.actions {
+            source.components.remove("actions");
+            source.actions = Some(self.actions.names(&mut self.errors, context, &a.action_ids));
+        }
+        source
+    }
+
+    fn blob_samples(
+        &mut self,
+        context: &str,
+        sampler: &EntityBlobsSampler,
+    ) -> Vec<BlobSampleSource> {
+        sampler
+            .selections
+            .iter()
+            .map(|s| BlobSampleSource {
+                weight: s.weight,
+                blob: self.blob(context, &s.blob),
+            })
+            .collect()
+    }
+
+    fn action_effect(
+        &mut self,
+        context: &str,
+        effect: &action::ActionEffect,
+    ) -> source::ActionEffect {
+        use action::ActionEffect as E;
+        use source::ActionEffect as S;
+        match effect {
+            E::Buff(action::Buff::Guard(guard)) => S::Buff(source::Buff::Guard(*guard)),
+            E::Attack(damage) => S::Attack(source::Damage {
+                amount: damage.amount,
+                damage_type: (&damage.damage_type).into(),
+            }),
+            E::Heal(heal) => S::Heal(*heal),
+            E::Rest => S::Rest,
+            E::Move => S::Move,
+            E::Take => S::Take,
+            E::Drop => S::Drop,
+            E::Equip => S::Equip,
+            E::Unequip => S::Unequip,
+            E::AddTrait(t) => S::AddTrait(self.traits.name(&mut self.errors, context, *t)),
+            E::RemoveTrait(t) => S::RemoveTrait(self.traits.name(&mut self.errors, context, *t)),
+        }
+    }
+}
+
+fn encounter_name(id: u32) -> String {
+    format!("encounter_{}", id)
+}
+
+/// Converts a pack back into sources, replacing ids by names. Encounters have
+/// no name of their own and are named after their id. Explicit ids are kept so that recompiling yields the same pack.
+pub fn decompile(asset_pack: &AssetPack) -> Result<PackSource, Vec<String>> {
+    let mut d = Decompiler {
+        errors: vec![],
+        actions: Names::new(
+            "action",
+            asset_pack.actions.iter().map(|a| (a.id, a.name.clone())),
+        ),
+        appearance_features: Names::new(
+            "appearance feature",
+            asset_pack
+                .appearance_features
+                .iter()
+                .map(|f| (f.index, f.text.clone())),
+        ),
+        baselines: Names::new(
+            "baseline",
+            asset_pack.baselines.iter().map(|b| (b.id, b.name.clone())),
+        ),
+        traits: Names::new(
+            "trait",
+            asset_pack.traits.iter().map(|t| (t.id, t.name.clone())),
+        ),
+    };
+    let encounter_blobs = Names::new(
+        "encounter blob",
+        asset_pack
+            .encounter_blobs
+            .iter()
+            .map(|b| (b.id, b.name.clone())),
+    );
+    let themes = Names::new(
+        "theme",
+        asset_pack
+            .location_map_themes
+            .iter()
+            .map(|t| (t.id, t.name.clone())),
+    );
+    let maps = Names::new(
+        "map",
+        asset_pack
+            .location_maps
+            .iter()
+            .map(|m| (m.id, m.name.clone())),
+    );
+
+    let mut actions = vec![];
+    for a in &asset_pack.actions {
+        let context = format!("action {:?}", a.name);
+        let mut steps: Vec<_> = asset_pack
+            .action_steps
+            .iter()
+            .filter(|s| s.action_id == a.id)
+            .collect();
+        steps.sort_by_key(|s| s.sequence_index);
+        actions.push(ActionSource {
+            id: Some(a.id),
+            name: a.name.clone(),
+            action_type: (&a.action_type).into(),
+            renderer: a.renderer.as_ref().map(|r| AttackRenderer {
+                weight_type: (&r.weight_type).into(),
+                speed_type: (&r.speed_type).into(),
+                armament_type: (&r.armament_type).into(),
+            }),
+            steps: steps
+                .into_iter()
+                .map(|s| ActionStepSource {
+                    effect: d.action_effect(&context, &s.action_effect),
+                    intensity: (&s.intensity).into(),
+                })
+                .collect(),
+        });
+    }
+
+    let appearance_features = asset_pack
+        .appearance_features
+        .iter()
+        .map(|f| AppearanceFeatureSource {
+            index: Some(f.index),
+            text: f.text.clone(),
+            appearance_feature_type: (&f.appearance_feature_type).into(),
+            priority: f.priority,
+        })
+        .collect();
+
+    for f in &asset_pack.localized_appearance_features {
+        if !asset_pack.locales.iter().any(|l| l.code == f.locale) {
+            d.errors.push(format!(
+                "localized appearance feature {}: unknown locale {:?}.",
+                f.id, f.locale
+            ));
+        }
+    }
+    let locales = asset_pack
+        .locales
+        .iter()
+        .map(|l| {
+            let features: Vec<&LocalizedAppearanceFeature> = asset_pack
+                .localized_appearance_features
+                .iter()
+                .filter(|f| f.locale == l.code)
+                .collect();
+            LocaleSource {
+                id: Some(l.id),
+                code: l.code.clone(),
+                fallback_noun: l.fallback_noun.clone(),
+                word_separator: l.word_separator.clone(),
+                article: l.article.clone(),
+                vowel_article: l.vowel_article.clone(),
+                appearance_features: d.localized_appearance_features(
+                    &l.code,
+                    &features,
+                    &asset_pack.appearance_features,
+                ),
+            }
+        })
+        .collect();
+
+    let baselines = asset_pack
+        .baselines
+        .iter()
+        .map(|b| StatBlockAssetSource {
+            id: Some(b.id),
+            name: b.name.clone(),
+            stat_block: d.stat_block(&format!("baseline {:?}", b.name), &b.stat_block),
+        })
+        .collect();
+    let traits = asset_pack
+        .traits
+        .iter()
+        .map(|t| StatBlockAssetSource {
+            id: Some(t.id),
+            name: t.name.clone(),
+            stat_block: d.stat_block(&format!("trait {:?}", t.name), &t.stat_block),
+        })
+        .collect();
+    let levels = asset_pack
+        .levels
+        .iter()
+        .map(|l| LevelSource {
+            level: l.level,
+            xp: l.xp,
+            traits: d
+                .traits
+                .names(&mut d.errors, &format!("level {}", l.level), &l.trait_ids),
+        })
+        .collect();
+
+    let prominence_rules = asset_pack
+        .prominence_rules
+        .iter()
+        .map(|r| ProminenceRuleSource {
+            condition: (&r.condition).into(),
+            weight: r.weight,
+        })
+        .collect();
+
+    let encounter_blob_sources = asset_pack
+        .encounter_blobs
+        .iter()
+        .map(|b| EncounterBlobSource {
+            id: Some(b.id),
+            name: b.name.clone(),
+            blob: d.blob(&format!("encounter blob {:?}", b.name), &b.blob),
+        })
+        .collect();
+    let encounters = asset_pack
+        .encounters
+        .iter()
+        .map(|e| {
+            let context = format!("encounter {}", e.id);
+            EncounterSource {
+                id: Some(e.id),
+                name: encounter_name(e.id),
+                categoric_blob: encounter_blobs.name(&mut d.errors, &context, e.categoric_blob_id),
+                blobs: encounter_blobs.names(&mut d.errors, &context, &e.blob_ids),
+                respawn_delay_micros: e.respawn_delay.map(|d| d.to_micros()),
+            }
+        })
+        .collect();
+
+    let theme_sources = asset_pack
+        .location_map_themes
+        .iter()
+        .map(|t| {
+            let context = format!("theme {:?}", t.name);
+            ThemeSource {
+                id: Some(t.id),
+                name: t.name.clone(),
+                rooms: d.blob_samples(&context, &t.rooms_selector),
+                paths: d.blob_samples(&context, &t.paths_selector),
+                decorations: d.blob_samples(&context, &t.decorations_selector),
+                min_decoration_count: t.min_decoration_count,
+                max_decoration_count: t.max_decoration_count,
+            }
+        })
+        .collect();
+    let location_maps = asset_pack
+        .location_maps
+        .iter()
+        .map(|m| MapSource {
+            id: Some(m.id),
+            name: m.name.clone(),
+            theme: themes.name(&mut d.errors, &format!("map {:?}", m.name), m.theme_id),
+            layout: (&m.layout).into(),
+            rng_seed: m.rng_seed,
+            main_room_count: m.main_room_count,
+            extra_room_count: m.extra_room_count,
+            loop_count: m.loop_count,
+            encounters: m
+                .encounter_ids_sampler
+                .selections
+                .iter()
+                .map(|s| EncounterSampleSource {
+                    weight: s.weight,
+                    encounter: encounter_name(s.id),
+                })
+                .collect(),
+            min_encounter_count: m.min_encounter_count,
+            max_encounter_count: m.max_encounter_count,
+        })
+        .collect();
+    let connections = asset_pack
+        .location_map_connections
+        .iter()
+        .map(|c| {
+            let context = format!("connection {}", c.id);
+            ConnectionSource {
+                id: Some(c.id),
+                exit: maps.name(&mut d.errors, &context, c.exit_location_map_id),
+                destination: maps.name(&mut d.errors, &context, c.destination_location_map_id),
+            }
+        })
+        .collect();
+
+    let instantiate_entity_blobs = asset_pack
+        .instantiate_entity_blobs
+        .iter()
+        .enumerate()
+        .map(|(i, b)| d.blob(&format!("instantiate_entity_blobs[{}]", i), b))
+        .collect();
+    let new_player_blob = Some(d.blob("new_player_blob", &asset_pack.new_player_blob));
+
+    if !d.errors.is_empty() {
+        return Err(d.errors);
+    }
+    Ok(PackSource {
+        version: Some(asset_pack.version),
+        actions,
+        appearance_features,
+        locales,
+        baselines,
+        traits,
+        levels,
+        encounter_blobs: encounter_blob_sources,
+        encounters,
+        themes: theme_sources,
+        maps: location_maps,
+        connections,
+        prominence_rules,
+        instantiate_entity_blobs,
+        new_player_blob,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{compile::compile, format};
+
+    fn bsatn(pack: &AssetPack) -> Vec<u8> {
+        spacetimedb_sats::bsatn::to_vec(pack).unwrap_or_default()
+    }
+
+    #[test]
+    fn the_example_content_round_trips_through_every_format() -> Result<(), Vec<String>> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("example-content");
+        let source = format::read_dir(&dir)?;
+        let pack = compile(source)?;
+        assert_eq!(pack.version, 1);
+
+        for format in [
+            format::Format::Ron,
+            format::Format::Toml,
+            format::Format::Json,
+        ] {
+            let source = decompile(&pack)?;
+            let text = format.print(&source).map_err(|e| vec![e])?;
+            let source = format.parse(&text).map_err(|e| vec![e])?;
+            let recompiled = compile(source)?;
+            assert_eq!(bsatn(&recompiled), bsatn(&pack), "{:?}", format);
+        }
+        Ok(())
+    }
+}
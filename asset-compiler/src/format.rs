@@ -0,0 +1,169 @@
+use std::{fs, path::Path};
+
+use crate::source::PackSource;
+
+/// A source file format, chosen by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ron,
+    Toml,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ron" => Some(Self::Ron),
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_name)
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ron => "ron",
+            Self::Toml => "toml",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn parse(self, text: &str) -> Result<PackSource, String> {
+        match self {
+            Self::Ron => ron::from_str(text).map_err(|e| e.to_string()),
+            Self::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+            Self::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn print(self, source: &PackSource) -> Result<String, String> {
+        match self {
+            Self::Ron => ron::ser::to_string_pretty(source, ron::ser::PrettyConfig::default())
+                .map_err(|e| e.to_string()),
+            Self::Toml => toml::to_string_pretty(source).map_err(|e| e.to_string()),
+            Self::Json => serde_json::to_string_pretty(source).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Reads and merges every source file in `dir`, in file name order. Files
+/// with other extensions are ignored.
+pub fn read_dir(dir: &Path) -> Result<PackSource, Vec<String>> {
+    let entries = fs::read_dir(dir).map_err(|e| vec![format!("{}: {}", dir.display(), e)])?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && Format::from_path(p).is_some())
+        .collect();
+    paths.sort();
+
+    let mut errors = vec![];
+    let mut pack_source = PackSource::default();
+    for path in paths {
+        let result = Format::from_path(&path)
+            .ok_or_else(|| "Unknown format.".to_string())
+            .and_then(|format| {
+                let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+                format.parse(&text)
+            })
+            .and_then(|source| pack_source.merge(source));
+        if let Err(err) = result {
+            errors.push(format!("{}: {}", path.display(), err));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(pack_source)
+}
+
+/// Writes `source` to `dir` with one file per asset kind, skipping empty kinds.
+pub fn write_dir(dir: &Path, format: Format, source: PackSource) -> Result<(), String> {
+    let files = [
+        (
+            "pack",
+            PackSource {
+                version: source.version,
+                instantiate_entity_blobs: source.instantiate_entity_blobs,
+                new_player_blob: source.new_player_blob,
+                ..Default::default()
+            },
+        ),
+        (
+            "actions",
+            PackSource {
+                actions: source.actions,
+                ..Default::default()
+            },
+        ),
+        (
+            "appearance_features",
+            PackSource {
+                appearance_features: source.appearance_features,
+                locales: source.locales,
+                ..Default::default()
+            },
+        ),
+        (
+            "baselines",
+            PackSource {
+                baselines: source.baselines,
+                ..Default::default()
+            },
+        ),
+        (
+            "traits",
+            PackSource {
+                traits: source.traits,
+                ..Default::default()
+            },
+        ),
+        (
+            "levels",
+            PackSource {
+                levels: source.levels,
+                ..Default::default()
+            },
+        ),
+        (
+            "encounters",
+            PackSource {
+                encounter_blobs: source.encounter_blobs,
+                encounters: source.encounters,
+                ..Default::default()
+            },
+        ),
+        (
+            "maps",
+            PackSource {
+                themes: source.themes,
+                maps: source.maps,
+                connections: source.connections,
+                ..Default::default()
+            },
+        ),
+        (
+            "prominence_rules",
+            PackSource {
+                prominence_rules: source.prominence_rules,
+                ..Default::default()
+            },
+        ),
+    ];
+
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for (name, file) in files {
+        let text = format.print(&file)?;
+        if text.trim() == format.print(&PackSource::default())?.trim() {
+            continue;
+        }
+        let path = dir.join(name).with_extension(format.extension());
+        fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
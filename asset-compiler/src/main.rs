@@ -0,0 +1,189 @@
+// Same no-panic / no-unsafe guard as the server module.
+#![deny(unsafe_code)]
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![deny(clippy::todo)]
+#![deny(clippy::unimplemented)]
+#![deny(clippy::unreachable)]
+
+//! Compiles human-authored RON/TOML/JSON content into an `AssetPack` for
+//! `push_assets`/`update_assets`, and decompiles packs back into sources.
+
+mod compile;
+mod decompile;
+mod format;
+mod sats_json;
+mod source;
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use server::asset::{reference, AssetPack};
+
+use crate::format::Format;
+
+const USAGE: &str = "\
+usage:
+  asset-compiler compile <source_dir> [--bsatn <out>] [--json <out>]
+  asset-compiler decompile <pack.bsatn|pack.json> <out_dir> [--format ron|toml|json]
+
+compile merges every .ron, .toml and .json file in <source_dir> into one pack.
+--json writes the reducer arguments for `spacetime call <db> push_assets`.";
+
+enum Command {
+    Compile {
+        source_dir: PathBuf,
+        bsatn: Option<PathBuf>,
+        json: Option<PathBuf>,
+    },
+    Decompile {
+        pack: PathBuf,
+        out_dir: PathBuf,
+        format: Format,
+    },
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (command, rest) = args.split_first().ok_or("Missing command.")?;
+    let mut positional = vec![];
+    let mut options = vec![];
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        if let Some(option) = arg.strip_prefix("--") {
+            let value = rest
+                .next()
+                .ok_or_else(|| format!("--{} needs a value.", option))?;
+            options.push((option, value.as_str()));
+        } else {
+            positional.push(PathBuf::from(arg));
+        }
+    }
+    let option = |name: &str| {
+        options
+            .iter()
+            .find(|(o, _)| *o == name)
+            .map(|(_, value)| *value)
+    };
+    let known = |names: &[&str]| {
+        options
+            .iter()
+            .find(|(o, _)| !names.contains(o))
+            .map_or(Ok(()), |(o, _)| Err(format!("Unknown option --{}.", o)))
+    };
+
+    match (command.as_str(), positional.as_slice()) {
+        ("compile", [source_dir]) => {
+            known(&["bsatn", "json"])?;
+            Ok(Command::Compile {
+                source_dir: source_dir.clone(),
+                bsatn: option("bsatn").map(PathBuf::from),
+                json: option("json").map(PathBuf::from),
+            })
+        }
+        ("decompile", [pack, out_dir]) => {
+            known(&["format"])?;
+            let format = match option("format") {
+                Some(name) => {
+                    Format::from_name(name).ok_or_else(|| format!("Unknown format {:?}.", name))?
+                }
+                None => Format::Ron,
+            };
+            Ok(Command::Decompile {
+                pack: pack.clone(),
+                out_dir: out_dir.clone(),
+                format,
+            })
+        }
+        _ => Err(format!("Unexpected arguments for {:?}.", command)),
+    }
+}
+
+fn to_bsatn(pack: &AssetPack) -> Result<Vec<u8>, String> {
+    spacetimedb_sats::bsatn::to_vec(pack).map_err(|e| e.to_string())
+}
+
+fn read_pack(path: &PathBuf) -> Result<AssetPack, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if Format::from_path(path) == Some(Format::Json) {
+        let value: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", path.display(), e))?;
+        // Accept both a bare pack and the `[pack]` reducer arguments.
+        let value = match value {
+            serde_json::Value::Array(mut args) if args.len() == 1 => args.remove(0),
+            value => value,
+        };
+        sats_json::from_value(value).map_err(|e| format!("{}: {}", path.display(), e))
+    } else {
+        spacetimedb_sats::bsatn::from_slice(&bytes)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+fn write(path: &PathBuf, bytes: &[u8]) -> Result<(), Vec<String>> {
+    fs::write(path, bytes).map_err(|e| vec![format!("{}: {}", path.display(), e)])
+}
+
+fn run(command: Command) -> Result<(), Vec<String>> {
+    match command {
+        Command::Compile {
+            source_dir,
+            bsatn,
+            json,
+        } => {
+            let pack = compile::compile(format::read_dir(&source_dir)?)?;
+            if let Some(path) = bsatn {
+                write(&path, &to_bsatn(&pack).map_err(|e| vec![e])?)?;
+            }
+            if let Some(path) = json {
+                let args =
+                    serde_json::Value::Array(
+                        vec![sats_json::to_value(&pack).map_err(|e| vec![e])?],
+                    );
+                write(&path, args.to_string().as_bytes())?;
+            }
+            println!(
+                "Compiled asset pack version {} from {}.",
+                pack.version,
+                source_dir.display()
+            );
+            Ok(())
+        }
+        Command::Decompile {
+            pack,
+            out_dir,
+            format,
+        } => {
+            let mut asset_pack = read_pack(&pack).map_err(|e| vec![e])?;
+            let names = reference::AssetNames::of_pack(&asset_pack);
+            let errors = reference::resolve_names(&mut asset_pack, &names);
+            if !errors.is_empty() {
+                return Err(errors.iter().map(|e| e.to_string()).collect());
+            }
+            let source = decompile::decompile(&asset_pack)?;
+            format::write_dir(&out_dir, format, source).map_err(|e| vec![e])?;
+            println!("Decompiled {} into {}.", pack.display(), out_dir.display());
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(errors) => {
+            for err in &errors {
+                eprintln!("{}", err);
+            }
+            eprintln!("Failed with {} error(s).", errors.len());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde_json::{Map, Value};
+use spacetimedb_sats::{de::Deserialize, ser::Serialize, serde::SerdeWrapper};
+
+/// Converts a SpacetimeType into its SATS-JSON form.
+pub fn to_value<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(SerdeWrapper::from_ref(value)).map_err(|e| e.to_string())
+}
+
+/// Converts SATS-JSON back into a SpacetimeType.
+pub fn from_value<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, String> {
+    serde_json::from_value::<SerdeWrapper<T>>(value)
+        .map(|w| w.0)
+        .map_err(|e| e.to_string())
+}
+
+/// The fields of a SpacetimeType's default value, in SATS-JSON form.
+pub fn default_fields<T: Serialize + Default>() -> Result<Map<String, Value>, String> {
+    match to_value(&T::default())? {
+        Value::Object(fields) => Ok(fields),
+        other => Err(format!("Expected an object, found {}.", other)),
+    }
+}
+
+pub fn some(value: Value) -> Value {
+    Value::Object(Map::from_iter([("some".to_string(), value)]))
+}
+
+/// Unwraps a SATS-JSON `Option`, returning `None` for the `none` variant.
+pub fn unwrap_some(value: Value) -> Option<Value> {
+    match value {
+        Value::Object(mut fields) => fields.remove("some"),
+        _ => None,
+    }
+}
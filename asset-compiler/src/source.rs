@@ -0,0 +1,405 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use server::{action, appearance, asset::location_map, prominence};
+
+/// Mirrors a fieldless server enum so it can be authored by variant name.
+macro_rules! mirror_enum {
+    ($name:ident => $server:path { $($variant:ident),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl From<$name> for $server {
+            fn from(value: $name) -> Self {
+                use $server as S;
+                match value {
+                    $($name::$variant => S::$variant,)*
+                }
+            }
+        }
+
+        impl From<&$server> for $name {
+            fn from(value: &$server) -> Self {
+                use $server as S;
+                match value {
+                    $(S::$variant => $name::$variant,)*
+                }
+            }
+        }
+    };
+}
+
+mirror_enum!(ActionType => action::ActionType { Buff, Attack, Move, Inventory, Equip });
+mirror_enum!(Intensity => action::Intensity { Normal, Powerful, Extreme });
+mirror_enum!(WeightType => action::WeightType { Heavy, Neutral, Light });
+mirror_enum!(SpeedType => action::SpeedType { Slow, Neutral, Fast });
+mirror_enum!(ArmamentType => action::ArmamentType {
+    Blade, Sword, Club, Staff, Fist, Claw, Teeth, Stick, Spout,
+});
+mirror_enum!(DamageType => action::DamageType { Physical, Fire, Cold, Lightning, Poison });
+mirror_enum!(Layout => location_map::Layout { Path, Hub });
+mirror_enum!(AppearanceFeatureType => appearance::AppearanceFeatureType { Noun, Adjective });
+mirror_enum!(ProminenceCondition => prominence::ProminenceCondition {
+    Path, PlayerController, EnemyController, Hp, Downed, Item, Equipped,
+});
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttackRenderer {
+    pub weight_type: WeightType,
+    pub speed_type: SpeedType,
+    pub armament_type: ArmamentType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Buff {
+    Guard(i32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Damage {
+    pub amount: i32,
+    pub damage_type: DamageType,
+}
+
+/// An action effect, with traits referenced by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ActionEffect {
+    Buff(Buff),
+    Attack(Damage),
+    Heal(i32),
+    Rest,
+    Move,
+    Take,
+    Drop,
+    Equip,
+    Unequip,
+    AddTrait(String),
+    RemoveTrait(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionStepSource {
+    pub effect: ActionEffect,
+    pub intensity: Intensity,
+}
+
+/// An action and its steps, in sequence order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    pub action_type: ActionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renderer: Option<AttackRenderer>,
+    pub steps: Vec<ActionStepSource>,
+}
+
+/// An appearance feature, referenced by its `text`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppearanceFeatureSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    pub text: String,
+    pub appearance_feature_type: AppearanceFeatureType,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub priority: i32,
+}
+
+/// A translation of the appearance feature whose text is `feature`. The type
+/// and priority default to the canonical feature's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedAppearanceFeatureSource {
+    pub feature: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appearance_feature_type: Option<AppearanceFeatureType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+}
+
+/// A language, identified by its locale `code`, with its grammar and its
+/// translations of the appearance features.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocaleSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub code: String,
+    pub fallback_noun: String,
+    pub word_separator: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub article: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vowel_article: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub appearance_features: Vec<LocalizedAppearanceFeatureSource>,
+}
+
+/// A stat block with numeric stats by name and ids replaced by names.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatBlockSource {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stats: BTreeMap<String, i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub appearance_features: Vec<String>,
+}
+
+/// A baseline or trait.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatBlockAssetSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    #[serde(default)]
+    pub stat_block: StatBlockSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelSource {
+    pub level: u32,
+    pub xp: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProminenceRuleSource {
+    pub condition: ProminenceCondition,
+    pub weight: i32,
+}
+
+/// A component field value. This mirrors JSON, but keeps numbers plain so that
+/// they print the same way in every source format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComponentValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Array(Vec<ComponentValue>),
+    Object(BTreeMap<String, ComponentValue>),
+}
+
+impl From<ComponentValue> for Value {
+    fn from(value: ComponentValue) -> Self {
+        match value {
+            ComponentValue::Bool(b) => Value::Bool(b),
+            ComponentValue::Int(i) => Value::from(i),
+            ComponentValue::UInt(u) => Value::from(u),
+            ComponentValue::Float(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+            ComponentValue::String(s) => Value::String(s),
+            ComponentValue::Array(values) => {
+                Value::Array(values.into_iter().map(Value::from).collect())
+            }
+            ComponentValue::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(k, v)| (k, Value::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TryFrom<Value> for ComponentValue {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Value::Null => return Err("null is not a component value.".to_string()),
+            Value::Bool(b) => Self::Bool(b),
+            Value::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+                (Some(i), _, _) => Self::Int(i),
+                (_, Some(u), _) => Self::UInt(u),
+                (_, _, Some(f)) => Self::Float(f),
+                _ => return Err(format!("{} is not a supported number.", n)),
+            },
+            Value::String(s) => Self::String(s),
+            Value::Array(values) => Self::Array(
+                values
+                    .into_iter()
+                    .map(Self::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(fields) => Self::Object(
+                fields
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, Self::try_from(v)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+        })
+    }
+}
+
+/// An entity blob. `baseline`, `traits` and `actions` are referenced by name;
+/// every other component is given in `components` by component name, without
+/// its `entity_id`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlobSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traits: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, ComponentValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterBlobSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    pub blob: BlobSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    pub categoric_blob: String,
+    pub blobs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respawn_delay_micros: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobSampleSource {
+    pub weight: u8,
+    pub blob: BlobSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    pub rooms: Vec<BlobSampleSource>,
+    pub paths: Vec<BlobSampleSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorations: Vec<BlobSampleSource>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub min_decoration_count: u8,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub max_decoration_count: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterSampleSource {
+    pub weight: u8,
+    pub encounter: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub name: String,
+    pub theme: String,
+    pub layout: Layout,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng_seed: Option<u64>,
+    pub main_room_count: u8,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub extra_room_count: u8,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub loop_count: u8,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encounters: Vec<EncounterSampleSource>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub min_encounter_count: u8,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub max_encounter_count: u8,
+}
+
+/// A connection between two maps, referenced by map name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    pub exit: String,
+    pub destination: String,
+}
+
+/// The contents of one source file. Every file in a source directory is read
+/// as a `PackSource` and the results are merged, so content can be split
+/// across files freely.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<ActionSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub appearance_features: Vec<AppearanceFeatureSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<LocaleSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub baselines: Vec<StatBlockAssetSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traits: Vec<StatBlockAssetSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<LevelSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encounter_blobs: Vec<EncounterBlobSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encounters: Vec<EncounterSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub themes: Vec<ThemeSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maps: Vec<MapSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connections: Vec<ConnectionSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prominence_rules: Vec<ProminenceRuleSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instantiate_entity_blobs: Vec<BlobSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_player_blob: Option<BlobSource>,
+}
+
+impl PackSource {
+    /// Appends `other` to `self`, failing if both set a singular field.
+    pub fn merge(&mut self, other: PackSource) -> Result<(), String> {
+        if other.version.is_some() {
+            if self.version.is_some() {
+                return Err("version is set in more than one file.".to_string());
+            }
+            self.version = other.version;
+        }
+        if other.new_player_blob.is_some() {
+            if self.new_player_blob.is_some() {
+                return Err("new_player_blob is set in more than one file.".to_string());
+            }
+            self.new_player_blob = other.new_player_blob;
+        }
+        self.actions.extend(other.actions);
+        self.appearance_features.extend(other.appearance_features);
+        self.locales.extend(other.locales);
+        self.baselines.extend(other.baselines);
+        self.traits.extend(other.traits);
+        self.levels.extend(other.levels);
+        self.encounter_blobs.extend(other.encounter_blobs);
+        self.encounters.extend(other.encounters);
+        self.themes.extend(other.themes);
+        self.maps.extend(other.maps);
+        self.connections.extend(other.connections);
+        self.prominence_rules.extend(other.prominence_rules);
+        self.instantiate_entity_blobs
+            .extend(other.instantiate_entity_blobs);
+        Ok(())
+    }
+}
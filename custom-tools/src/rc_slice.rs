@@ -33,9 +33,9 @@ impl<T> AsRef<[T]> for RcSlice<T> {
     }
 }
 
-impl<T: Clone> Into<Vec<T>> for RcSlice<T> {
-    fn into(self) -> Vec<T> {
-        self.0.to_vec()
+impl<T: Clone> From<RcSlice<T>> for Vec<T> {
+    fn from(val: RcSlice<T>) -> Self {
+        val.0.to_vec()
     }
 }
 
@@ -49,7 +49,7 @@ impl<T: Clone> IntoIterator for RcSlice<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<Self::Item>;
     fn into_iter(self) -> Self::IntoIter {
-        IntoIterator::into_iter(self.0.to_vec())
+        Vec::from(self).into_iter()
     }
 }
 
@@ -7,7 +7,7 @@ use syn::{
     parse::{Parse, ParseStream},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Attributes(RcSlice<Attribute>);
 
 impl Attributes {
@@ -23,12 +23,6 @@ impl Deref for Attributes {
     }
 }
 
-impl Default for Attributes {
-    fn default() -> Self {
-        Attributes(Default::default())
-    }
-}
-
 impl From<Vec<Attribute>> for Attributes {
     fn from(value: Vec<Attribute>) -> Self {
         Attributes(value.into())
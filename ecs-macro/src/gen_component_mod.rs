@@ -11,7 +11,7 @@ pub struct ComponentMod {
 
 impl ComponentMod {
     fn make_alias(trait_ident: &Ident, component: &Ident) -> Option<Ident> {
-        let prefix = format!("__{}__", component.to_string());
+        let prefix = format!("__{}__", component);
         trait_ident
             .to_string()
             .strip_prefix(&prefix)
@@ -110,6 +110,14 @@ impl ComponentMod {
             }
         }
 
+        for ccht in entity_traits.component_changes_traits.iter() {
+            if ccht.component == *component {
+                let full = ccht.component_changes_trait.to_owned();
+                let alias = Self::make_alias(&full, component)?;
+                aliases.push((full, alias));
+            }
+        }
+
         Some(Self {
             module: format_ident!("{}_component", component),
             aliases,
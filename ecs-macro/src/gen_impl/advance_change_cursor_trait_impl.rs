@@ -0,0 +1,94 @@
+use crate::{gen_struct, gen_trait, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct EcsStruct {
+    pub advance_change_cursor_trait: gen_trait::AdvanceChangeCursorTrait,
+}
+
+impl EcsStruct {
+    pub fn new(acct: Option<&gen_trait::AdvanceChangeCursorTrait>) -> Option<Self> {
+        acct.map(|acct| Self {
+            advance_change_cursor_trait: acct.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for EcsStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gen_trait::AdvanceChangeCursorTrait {
+            advance_change_cursor_trait,
+            change_struct:
+                gen_struct::ChangeStruct {
+                    table,
+                    cursor_table,
+                    change_cursor_struct,
+                    systems,
+                    ..
+                },
+        } = &self.advance_change_cursor_trait;
+        let systems = systems.iter().map(|s| s.to_string());
+        let change_store = gen_trait::TableStoreTrait::ident(table);
+        let cursor_store = gen_trait::TableStoreTrait::ident(cursor_table);
+        tokens.extend(quote! {
+          impl<'a> #advance_change_cursor_trait for ecs::Ecs<'a> {
+              fn advance_change_cursor(&self, system: &str) {
+                let latest = #change_store::iter(self)
+                  .map(|c| c.change_id)
+                  .max()
+                  .unwrap_or_default();
+                let previous = #cursor_store::find(self, &system.to_string()).map_or(0, |c| c.change_id);
+                #cursor_store::delete(self, &system.to_string());
+                #cursor_store::insert(self, #change_cursor_struct {
+                  system: system.to_string(),
+                  change_id: previous.max(latest),
+                });
+
+                // Until every declared system has a cursor, one of them has
+                // not read anything yet, so nothing may be collected.
+                let declared: &[&str] = &[#(#systems),*];
+                if !declared.iter().all(|s| #cursor_store::find(self, &s.to_string()).is_some()) {
+                  return;
+                }
+                let seen = #cursor_store::iter(self)
+                  .map(|c| c.change_id)
+                  .min()
+                  .unwrap_or_default();
+                let stale: ::std::vec::Vec<u64> = #change_store::iter(self)
+                  .map(|c| c.change_id)
+                  .filter(|change_id| *change_id <= seen)
+                  .collect();
+                for change_id in stale {
+                  #change_store::delete(self, &change_id);
+                }
+              }
+          }
+        });
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    ecs_struct: Option<EcsStruct>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let _ = entity_structs;
+        let gen_trait::EntityTraits {
+            advance_change_cursor_trait,
+            ..
+        } = entity_traits;
+
+        let ecs_struct = EcsStruct::new(advance_change_cursor_trait.as_ref());
+
+        Ok(Self { ecs_struct })
+    }
+}
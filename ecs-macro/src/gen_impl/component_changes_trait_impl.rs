@@ -0,0 +1,81 @@
+use crate::{gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct EcsStruct {
+    pub component_changes_trait: gen_trait::ComponentChangesTrait,
+}
+
+impl EcsStruct {
+    pub fn new(component_changes_trait: &gen_trait::ComponentChangesTrait) -> Self {
+        Self {
+            component_changes_trait: component_changes_trait.to_owned(),
+        }
+    }
+
+    pub fn new_vec(cchts: &RcSlice<gen_trait::ComponentChangesTrait>) -> RcSlice<Self> {
+        cchts.iter().map(Self::new).collect()
+    }
+}
+
+impl ToTokens for EcsStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gen_trait::ComponentChangesTrait {
+            component_name,
+            component_changes_trait,
+            iter_changes_fn,
+            change_struct:
+                gen_struct::ChangeStruct {
+                    table,
+                    cursor_table,
+                    ..
+                },
+            entity_handle_struct:
+                gen_struct::EntityHandleStruct {
+                    entity_handle_struct,
+                    id,
+                    ..
+                },
+            ..
+        } = &self.component_changes_trait;
+        let change_store = gen_trait::TableStoreTrait::ident(table);
+        let cursor_store = gen_trait::TableStoreTrait::ident(cursor_table);
+        tokens.extend(quote! {
+          impl<'a> #component_changes_trait<'a> for ecs::Ecs<'a> {
+              fn #iter_changes_fn(&self, system: &str) -> impl Iterator<Item = (#entity_handle_struct<'a>, ecs::ChangeKind)> {
+                let cursor = #cursor_store::find(self, &system.to_string()).map_or(0, |c| c.change_id);
+                let changes = #change_store::filter_changes_after(self, #component_name, cursor)
+                  .map(|c| (c.#id, c.kind));
+                ecs::net_changes(changes)
+                  .map(|(#id, kind)| (#entity_handle_struct { #id, ecs: self.clone() }, kind))
+              }
+          }
+        });
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    ecs_structs: RcSlice<EcsStruct>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let _ = entity_structs;
+        let gen_trait::EntityTraits {
+            component_changes_traits,
+            ..
+        } = entity_traits;
+
+        let ecs_structs = EcsStruct::new_vec(component_changes_traits);
+
+        Ok(Self { ecs_structs })
+    }
+}
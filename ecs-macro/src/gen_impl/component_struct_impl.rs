@@ -20,7 +20,7 @@ impl ComponentStruct {
     }
 
     pub fn new_vec(component_structs: &RcSlice<gen_struct::ComponentStruct>) -> RcSlice<Self> {
-        component_structs.iter().map(|cs| Self::new(cs)).collect()
+        component_structs.iter().map(Self::new).collect()
     }
 }
 
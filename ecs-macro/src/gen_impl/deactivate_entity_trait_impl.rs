@@ -0,0 +1,111 @@
+use crate::{RcSlice, fundamental, gen_struct, gen_trait, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct WithEntityHandleTrait {
+    pub deactivate_entity_trait: gen_trait::DeactivateEntityTrait,
+    pub entity_struct: gen_struct::EntityStruct,
+    pub with_entity_handle_trait: gen_trait::WithEntityHandleTrait,
+    pub option_component_traits: RcSlice<gen_trait::OptionComponentTrait>,
+}
+
+impl WithEntityHandleTrait {
+    pub fn new(
+        deactivate_entity_trait: Option<&gen_trait::DeactivateEntityTrait>,
+        entity_struct: &gen_struct::EntityStruct,
+        with_entity_handle_trait: &gen_trait::WithEntityHandleTrait,
+        option_component_traits: &RcSlice<gen_trait::OptionComponentTrait>,
+    ) -> Option<Self> {
+        deactivate_entity_trait.map(|deactivate_entity_trait| Self {
+            deactivate_entity_trait: deactivate_entity_trait.to_owned(),
+            entity_struct: entity_struct.to_owned(),
+            with_entity_handle_trait: with_entity_handle_trait.to_owned(),
+            option_component_traits: option_component_traits.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for WithEntityHandleTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gen_trait::DeactivateEntityTrait {
+            deactivate_entity_trait,
+            entity_blob_struct:
+                gen_struct::EntityBlobStruct {
+                    table: fundamental::Table(blob_table),
+                    entity_blob_struct,
+                    blob_fields,
+                    ..
+                },
+            deactivation: macro_input::BlobDeactivation { field, .. },
+        } = &self.deactivate_entity_trait;
+        let gen_struct::EntityStruct { table, .. } = &self.entity_struct;
+        let gen_trait::WithEntityHandleTrait {
+            with_entity_handle_trait,
+            id_fn,
+            ..
+        } = &self.with_entity_handle_trait;
+        let deactivate_field_args =
+            fundamental::FieldArgs(self.deactivate_entity_trait.deactivate_fields());
+        let new_blob_args = blob_fields.iter().map(|f| match &f.ident {
+            Some(ident) if ident == field => {
+                quote! { ::core::option::Option::Some(handle.#id_fn()) }
+            }
+            ident => quote! { #ident },
+        });
+        // The entity is going away as a whole, so per-component hooks, which
+        // would write to it again, are skipped.
+        let discard_calls = self.option_component_traits.iter().map(|oct| {
+            let gen_trait::OptionComponentTrait { discard_fn, .. } = &oct;
+            quote! { handle.#discard_fn(); }
+        });
+        let store = gen_trait::TableStoreTrait::ident(table);
+        let blob_store = gen_trait::TableStoreTrait::ident(blob_table);
+        tokens.extend(quote! {
+          impl<'a, T: #with_entity_handle_trait<'a>> #deactivate_entity_trait for T {
+              fn deactivate(&self, #deactivate_field_args) -> #entity_blob_struct {
+                let handle = self.to_handle();
+                let blob = handle.new_blob(#(#new_blob_args,)*);
+                let blob = #blob_store::insert(&handle.ecs, blob);
+                #store::delete(&handle.ecs, &handle.#id_fn());
+                #(#discard_calls)*
+                blob
+              }
+          }
+        });
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    with_entity_handle_trait: Option<WithEntityHandleTrait>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let gen_struct::EntityStructs { entity_struct, .. } = entity_structs;
+        let gen_trait::EntityTraits {
+            deactivate_entity_trait,
+            option_component_traits,
+            with_entity_handle_trait,
+            ..
+        } = entity_traits;
+
+        let with_entity_handle_trait = WithEntityHandleTrait::new(
+            deactivate_entity_trait.as_ref(),
+            entity_struct,
+            with_entity_handle_trait,
+            option_component_traits,
+        );
+
+        Ok(Self {
+            with_entity_handle_trait,
+        })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{RcSlice, gen_struct, gen_trait, macro_input};
 use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
+use quote::{ToTokens, format_ident, quote};
 use structmeta::ToTokens;
 use syn::Result;
 
@@ -9,6 +9,7 @@ pub struct WithEntityHandleTrait {
     pub entity_struct: gen_struct::EntityStruct,
     pub with_entity_handle_trait: gen_trait::WithEntityHandleTrait,
     pub option_component_traits: RcSlice<gen_trait::OptionComponentTrait>,
+    pub entity_handle_struct: gen_struct::EntityHandleStruct,
 }
 
 impl WithEntityHandleTrait {
@@ -17,12 +18,14 @@ impl WithEntityHandleTrait {
         entity_struct: &gen_struct::EntityStruct,
         with_entity_handle_trait: &gen_trait::WithEntityHandleTrait,
         option_component_traits: &RcSlice<gen_trait::OptionComponentTrait>,
+        entity_handle_struct: &gen_struct::EntityHandleStruct,
     ) -> Self {
         Self {
             delete_entity_trait: delete_entity_trait.to_owned(),
             entity_struct: entity_struct.to_owned(),
             with_entity_handle_trait: with_entity_handle_trait.to_owned(),
             option_component_traits: option_component_traits.to_owned(),
+            entity_handle_struct: entity_handle_struct.to_owned(),
         }
     }
 }
@@ -39,16 +42,51 @@ impl ToTokens for WithEntityHandleTrait {
             id_fn,
             ..
         } = &self.with_entity_handle_trait;
-        let delete_calls = self.option_component_traits.iter().map(|oct| {
-            let gen_trait::OptionComponentTrait { delete_fn, .. } = &oct;
-            quote! { handle.#delete_fn(); }
+        // The entity is going away as a whole, so per-component hooks, which
+        // would write to it again, are skipped.
+        let discard_calls = self.option_component_traits.iter().map(|oct| {
+            let gen_trait::OptionComponentTrait { discard_fn, .. } = &oct;
+            quote! { handle.#discard_fn(); }
         });
+        let gen_struct::EntityHandleStruct {
+            entity_handle_struct,
+            id,
+            ..
+        } = &self.entity_handle_struct;
+        // Relations are resolved after the entity's own components are gone,
+        // so a cycle of references stops at the first entity deleted twice.
+        let relation_calls = self.option_component_traits.iter().flat_map(|oct| {
+            let gen_trait::OptionComponentTrait {
+                table,
+                delete_fn,
+                relations,
+                ..
+            } = oct;
+            relations.iter().map(move |macro_input::Relation { field, kind }| {
+                let apply = match kind {
+                    macro_input::RelationKind::Cascade => quote! { delete() },
+                    macro_input::RelationKind::Nullify => quote! { #delete_fn() },
+                };
+                let store = gen_trait::TableStoreTrait::ident(table);
+                let filter_fn = format_ident!("filter_{}", field);
+                quote! {
+                  let referrers: ::std::vec::Vec<_> = #store::#filter_fn(&handle.ecs, &handle.#id)
+                    .map(|c| c.#id)
+                    .collect();
+                  for #id in referrers {
+                    #entity_handle_struct { #id, ecs: handle.ecs.clone() }.#apply;
+                  }
+                }
+            })
+        });
+        let store = gen_trait::TableStoreTrait::ident(table);
         tokens.extend(quote! {
           impl<'a, T: #with_entity_handle_trait<'a>> #delete_entity_trait for T {
               fn delete(&self) {
                 let handle = self.to_handle();
-                ::spacetimedb::UniqueColumn::delete(&handle.ecs.db.#table().id(), handle.#id_fn());
-                #(#delete_calls)*
+                #store::delete(&handle.ecs, &handle.#id_fn());
+                #(#discard_calls)*
+                #(#relation_calls)*
               }
           }
         });
@@ -67,7 +105,11 @@ impl Impl {
         entity_traits: &gen_trait::EntityTraits,
     ) -> Result<Self> {
         let _ = entity_macro_input;
-        let gen_struct::EntityStructs { entity_struct, .. } = entity_structs;
+        let gen_struct::EntityStructs {
+            entity_struct,
+            entity_handle_struct,
+            ..
+        } = entity_structs;
         let gen_trait::EntityTraits {
             delete_entity_trait,
             option_component_traits,
@@ -80,6 +122,7 @@ impl Impl {
             entity_struct,
             with_entity_handle_trait,
             option_component_traits,
+            entity_handle_struct,
         );
 
         Ok(Self {
@@ -0,0 +1,129 @@
+use crate::{fundamental, gen_struct, gen_trait, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct EntityBlobStruct {
+    pub entity_blob_struct: gen_struct::EntityBlobStruct,
+}
+
+impl EntityBlobStruct {
+    pub fn new(ebs: Option<&gen_struct::EntityBlobStruct>) -> Option<Self> {
+        ebs.map(|ebs| Self {
+            entity_blob_struct: ebs.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for EntityBlobStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gen_struct::EntityBlobStruct {
+            table,
+            entity_blob_struct,
+            blob_fields,
+            parent,
+            component_fields,
+            ..
+        } = &self.entity_blob_struct;
+        let fundamental::Table(table) = table;
+        let blob_fields = blob_fields.iter().map(|field| {
+            let ident = &field.ident;
+            quote! { #ident: child.#ident }
+        });
+        let overlay_components = component_fields.iter().map(
+            |gen_struct::EntityBlobComponentField(ctp, _, merges)| {
+                let macro_input::ComponentTablePair { component, .. } = ctp;
+                let merge_fields = merges.iter().filter_map(
+                    |macro_input::FieldMerge { field, rule }| match rule {
+                        macro_input::MergeRule::Replace => None,
+                        macro_input::MergeRule::Add => Some(quote! {
+                          merged.#field = ecs::MergeAdd::merge_add(parent.#field, merged.#field);
+                        }),
+                        macro_input::MergeRule::Union => Some(quote! {
+                          merged.#field = ecs::MergeUnion::merge_union(parent.#field, merged.#field);
+                        }),
+                    },
+                );
+                quote! {
+                  #component: match (self.#component, child.#component) {
+                    (::core::option::Option::Some(parent), ::core::option::Option::Some(child)) => {
+                      #[allow(unused_mut, unused_variables)]
+                      let mut merged = child;
+                      #(#merge_fields)*
+                      ::core::option::Option::Some(merged)
+                    }
+                    (parent, ::core::option::Option::None) => parent,
+                    (::core::option::Option::None, child) => child,
+                  }
+                }
+            },
+        );
+        let flatten = parent.iter().map(
+            |macro_input::BlobParent { field, primary_key }| {
+                let store = gen_trait::TableStoreTrait::ident(table);
+                quote! {
+                  /// Overlays this blob on its ancestors in the blob table, from
+                  /// the root down. The result has no parent. A missing parent or
+                  /// a cycle ends the lineage.
+                  pub fn flatten(self, ecs: &ecs::Ecs) -> Self {
+                    let mut lineage = ::std::vec![self];
+                    while let ::core::option::Option::Some(parent) = lineage
+                      .last()
+                      .and_then(|blob| ::core::clone::Clone::clone(&blob.#field))
+                      .filter(|id| !lineage.iter().any(|blob| blob.#primary_key == *id))
+                      .and_then(|id| #store::find(ecs, &id))
+                    {
+                      lineage.push(parent);
+                    }
+                    let mut flat = lineage
+                      .into_iter()
+                      .rev()
+                      .reduce(Self::overlay)
+                      .unwrap_or_default();
+                    flat.#field = ::core::option::Option::None;
+                    flat
+                  }
+                }
+            },
+        );
+        tokens.extend(quote! {
+          impl #entity_blob_struct {
+            /// Overlays a child blob on this one. Components only one of them
+            /// has are kept, and components both have are merged by the rules
+            /// declared with `#[merge(...)]`, the child's value replacing the
+            /// parent's otherwise. Other blob fields are the child's.
+            pub fn overlay(self, child: Self) -> Self {
+              Self {
+                #(#blob_fields,)*
+                #(#overlay_components,)*
+              }
+            }
+            #(#flatten)*
+          }
+        });
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    entity_blob_struct: Option<EntityBlobStruct>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let gen_struct::EntityStructs {
+            entity_blob_struct, ..
+        } = entity_structs;
+        let _ = entity_traits;
+
+        let entity_blob_struct = EntityBlobStruct::new(entity_blob_struct.as_ref());
+
+        Ok(Self { entity_blob_struct })
+    }
+}
@@ -7,16 +7,19 @@ use syn::Result;
 pub struct EcsStruct {
     pub find_entity_handle_trait: gen_trait::FindEntityHandleTrait,
     pub entity_handle_struct: gen_struct::EntityHandleStruct,
+    pub entity_struct: gen_struct::EntityStruct,
 }
 
 impl EcsStruct {
     pub fn new(
         feht: &gen_trait::FindEntityHandleTrait,
         ehs: &gen_struct::EntityHandleStruct,
+        es: &gen_struct::EntityStruct,
     ) -> Self {
         Self {
             find_entity_handle_trait: feht.to_owned(),
             entity_handle_struct: ehs.to_owned(),
+            entity_struct: es.to_owned(),
         }
     }
 }
@@ -29,6 +32,7 @@ impl ToTokens for EcsStruct {
             ..
         } = &self.find_entity_handle_trait;
         let gen_struct::EntityHandleStruct { id, id_ty, .. } = &self.entity_handle_struct;
+        let store = gen_trait::TableStoreTrait::ident(&self.entity_struct.table);
         tokens.extend(quote! {
           impl<'a> #find_entity_handle_trait<'a> for ecs::Ecs<'a> {
               fn find(self, #id: #id_ty) -> #entity_handle_struct<'a> {
@@ -37,6 +41,13 @@ impl ToTokens for EcsStruct {
                     ecs: self,
                   }
               }
+              fn try_find(self, #id: #id_ty) -> ::core::option::Option<#entity_handle_struct<'a>> {
+                  #store::find(&self, &#id)?;
+                  ::core::option::Option::Some(#entity_handle_struct {
+                    #id,
+                    ecs: self,
+                  })
+              }
           }
         });
     }
@@ -55,6 +66,7 @@ impl Impl {
     ) -> Result<Self> {
         let _ = entity_macro_input;
         let gen_struct::EntityStructs {
+            entity_struct,
             entity_handle_struct,
             ..
         } = entity_structs;
@@ -63,7 +75,11 @@ impl Impl {
             ..
         } = entity_traits;
 
-        let ecs_struct = EcsStruct::new(find_entity_handle_trait, entity_handle_struct);
+        let ecs_struct = EcsStruct::new(
+            find_entity_handle_trait,
+            entity_handle_struct,
+            entity_struct,
+        );
 
         Ok(Self { ecs_struct })
     }
@@ -89,7 +89,7 @@ impl ToTokens for EntityHandleStruct {
             entity_blob_struct,
         } = &self.instantiate_entity_blob_trait;
         let upsert_components = entity_blob_struct.component_fields.iter().flat_map(
-            |gen_struct::EntityBlobComponentField(ctp, _)| {
+            |gen_struct::EntityBlobComponentField(ctp, ..)| {
                 let gen_trait::OptionComponentTrait {
                     getter_fn,
                     insert_fn,
@@ -113,11 +113,19 @@ impl ToTokens for EntityHandleStruct {
             },
         );
         let gen_struct::EntityBlobStruct {
-            entity_blob_struct, ..
+            entity_blob_struct,
+            parent,
+            ..
         } = entity_blob_struct;
+        let flatten = parent.iter().map(|_| {
+            quote! {
+              let blob = blob.flatten(&self.ecs());
+            }
+        });
         tokens.extend(quote! {
           impl<'a> #instantiate_entity_blob_trait for #entity_handle_struct<'a> {
               fn instantiate_blob(self, blob: #entity_blob_struct) -> Self {
+                  #(#flatten)*
                   #(#upsert_components;)*
                   self
               }
@@ -16,7 +16,7 @@ impl EcsStruct {
     }
 
     pub fn new_vec(ichts: &RcSlice<gen_trait::IntoComponentHandleTrait>) -> RcSlice<Self> {
-        ichts.iter().map(|icht| Self::new(icht)).collect()
+        ichts.iter().map(Self::new).collect()
     }
 }
 
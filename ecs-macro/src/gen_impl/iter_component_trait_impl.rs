@@ -1,6 +1,6 @@
 use crate::{gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
 use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
+use quote::{ToTokens, format_ident, quote};
 use structmeta::ToTokens;
 use syn::Result;
 
@@ -16,7 +16,7 @@ impl EcsStruct {
     }
 
     pub fn new_vec(icts: &RcSlice<gen_trait::IterComponentTrait>) -> RcSlice<Self> {
-        icts.iter().map(|ict| Self::new(ict)).collect()
+        icts.iter().map(Self::new).collect()
     }
 }
 
@@ -34,18 +34,61 @@ impl ToTokens for EcsStruct {
                 },
             with_component_struct,
             iter_fn,
+            indexed_fields,
         } = &self.iter_component_trait;
+        let store = gen_trait::TableStoreTrait::ident(table);
+        let with_handle = quote! {
+          |c| {
+            let #id = c.#id;
+            #with_component_struct {
+              #component: c,
+              value: #entity_handle_struct { #id, ecs: self.clone() },
+            }
+          }
+        };
+        let indexed_fns = indexed_fields.iter().map(
+            |gen_trait::IndexedField {
+                 field,
+                 ty,
+                 kind,
+                 by_fn,
+                 range_fn,
+             }| {
+                let filter_fn = format_ident!("filter_{}", field);
+                let store_range_fn = format_ident!("range_{}", field);
+                let find_fn = format_ident!("find_{}", field);
+                match kind {
+                    gen_trait::IndexKind::BTree => quote! {
+                      fn #by_fn(&self, #field: &#ty) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>> {
+                        #store::#filter_fn(self, #field).map(#with_handle)
+                      }
+
+                      fn #range_fn(
+                        &self,
+                        range: &impl ::std::ops::RangeBounds<#ty>,
+                      ) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>> {
+                        #store::#store_range_fn(self, range).map(#with_handle)
+                      }
+                    },
+                    gen_trait::IndexKind::Hash => quote! {
+                      fn #by_fn(&self, #field: &#ty) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>> {
+                        #store::#filter_fn(self, #field).map(#with_handle)
+                      }
+                    },
+                    gen_trait::IndexKind::Unique => quote! {
+                      fn #by_fn(&self, #field: &#ty) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>> {
+                        #store::#find_fn(self, #field).into_iter().map(#with_handle)
+                      }
+                    },
+                }
+            },
+        );
         tokens.extend(quote! {
           impl<'a> #iter_component_trait<'a> for ecs::Ecs<'a> {
               fn #iter_fn(&self) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>> {
-                ::spacetimedb::Table::iter(self.db.#table()).map(|c| {
-                  let #id = c.#id;
-                  #with_component_struct {
-                    #component: c,
-                    value: #entity_handle_struct { #id, ecs: self.clone() },
-                  }
-              })
+                #store::iter(self).map(#with_handle)
             }
+            #(#indexed_fns)*
           }
         });
     }
@@ -5,10 +5,14 @@ use syn::Result;
 secador::secador!(
     impl_name,
     [
+        advance_change_cursor_trait_impl,
+        component_changes_trait_impl,
         component_delete_trait_impl,
         component_struct_impl,
         component_trait_impl,
+        deactivate_entity_trait_impl,
         delete_entity_trait_impl,
+        entity_blob_struct_impl,
         find_entity_handle_trait_impl,
         instantiate_entity_blob_trait_impl,
         into_component_handle_trait_impl,
@@ -19,6 +23,8 @@ secador::secador!(
         option_component_trait_impl,
         option_get_component_trait_impl,
         option_with_component_trait_impl,
+        reactivate_entity_trait_impl,
+        table_store_trait_impl,
         with_entity_handle_trait_impl,
     ],
     {
@@ -1,4 +1,4 @@
-use crate::{gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
+use crate::{fundamental, gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 use structmeta::ToTokens;
@@ -44,13 +44,17 @@ impl ToTokens for WithComponentStruct {
             new_entity_blob_trait,
             entity_blob_struct:
                 gen_struct::EntityBlobStruct {
-                    entity_blob_struct, ..
+                    entity_blob_struct,
+                    blob_fields,
+                    ..
                 },
         } = &self.new_entity_blob_trait;
+        let blob_field_args = fundamental::FieldArgs(blob_fields.to_owned());
+        let blob_field_names = fundamental::FieldNames(blob_fields.to_owned());
         tokens.extend(quote! {
           impl<T: #new_entity_blob_trait> #new_entity_blob_trait for #with_component_struct<T> {
-              fn new_blob(&self) -> #entity_blob_struct {
-                self.value.new_blob()
+              fn new_blob(&self, #blob_field_args) -> #entity_blob_struct {
+                self.value.new_blob(#blob_field_names)
               }
           }
         });
@@ -85,18 +89,23 @@ impl ToTokens for EntityHandleStruct {
             entity_blob_struct,
         } = &self.new_entity_blob_trait;
         let fields = entity_blob_struct.component_fields.iter().map(
-            |gen_struct::EntityBlobComponentField(ctp, _)| {
+            |gen_struct::EntityBlobComponentField(ctp, ..)| {
                 let macro_input::ComponentTablePair { component, .. } = ctp;
                 quote! { #component: self.#component() }
             },
         );
         let gen_struct::EntityBlobStruct {
-            entity_blob_struct, ..
+            entity_blob_struct,
+            blob_fields,
+            ..
         } = entity_blob_struct;
+        let blob_field_args = fundamental::FieldArgs(blob_fields.to_owned());
+        let blob_field_names = fundamental::FieldNames(blob_fields.to_owned());
         tokens.extend(quote! {
           impl<'a> #new_entity_blob_trait for #entity_handle_struct<'a> {
-              fn new_blob(&self) -> #entity_blob_struct {
+              fn new_blob(&self, #blob_field_args) -> #entity_blob_struct {
                   #entity_blob_struct {
+                    #blob_field_names
                     #(#fields,)*
                   }
               }
@@ -37,10 +37,11 @@ impl ToTokens for EcsStruct {
             ..
         } = &self.entity_struct;
         let gen_struct::EntityHandleStruct { id, .. } = &self.entity_handle_struct;
+        let store = gen_trait::TableStoreTrait::ident(table);
         tokens.extend(quote! {
           impl<'a> #new_entity_handle_trait<'a> for ecs::Ecs<'a> {
               fn new(self) -> #entity_handle_struct<'a> {
-                  let entity = ::spacetimedb::Table::insert(self.db.#table(), #entity_struct { id: 0 });
+                  let entity = #store::insert(&self, #entity_struct { id: 0 });
                   #entity_handle_struct {
                     #id: entity.id,
                     ecs: self,
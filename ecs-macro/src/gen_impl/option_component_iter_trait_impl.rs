@@ -22,7 +22,7 @@ impl OptionComponentIterator {
     ) -> RcSlice<Self> {
         option_component_iter_traits
             .iter()
-            .map(|ocit| Self::new(ocit))
+            .map(Self::new)
             .collect()
     }
 }
@@ -49,6 +49,7 @@ impl ToTokens for PassthroughWithComponentStruct {
             insert_fn,
             update_fn,
             delete_fn,
+            discard_fn,
             ..
         } = &self.option_component_trait;
         tokens.extend(quote! {
@@ -62,6 +63,9 @@ impl ToTokens for PassthroughWithComponentStruct {
             fn #delete_fn(&self) {
               self.value.#delete_fn();
             }
+            fn #discard_fn(&self) {
+              self.value.#discard_fn();
+            }
           }
         });
     }
@@ -70,26 +74,35 @@ impl ToTokens for PassthroughWithComponentStruct {
 pub struct EntityHandleStruct {
     pub entity_handle_struct: gen_struct::EntityHandleStruct,
     pub option_component_trait: gen_trait::OptionComponentTrait,
+    pub component_changes_trait: Option<gen_trait::ComponentChangesTrait>,
 }
 
 impl EntityHandleStruct {
     pub fn new(
         ehs: &gen_struct::EntityHandleStruct,
         oct: &gen_trait::OptionComponentTrait,
+        ccht: Option<&gen_trait::ComponentChangesTrait>,
     ) -> Self {
         Self {
             entity_handle_struct: ehs.to_owned(),
             option_component_trait: oct.to_owned(),
+            component_changes_trait: ccht.cloned(),
         }
     }
 
     pub fn new_vec(
         entity_handle_struct: &gen_struct::EntityHandleStruct,
         option_component_traits: &RcSlice<gen_trait::OptionComponentTrait>,
+        component_changes_traits: &RcSlice<gen_trait::ComponentChangesTrait>,
     ) -> RcSlice<Self> {
         option_component_traits
             .iter()
-            .map(|oct| Self::new(entity_handle_struct, oct))
+            .map(|oct| {
+                let ccht = component_changes_traits
+                    .iter()
+                    .find(|ccht| ccht.component == oct.component);
+                Self::new(entity_handle_struct, oct, ccht)
+            })
             .collect()
     }
 }
@@ -108,21 +121,80 @@ impl ToTokens for EntityHandleStruct {
             insert_fn,
             update_fn,
             delete_fn,
+            discard_fn,
             table,
+            hooks,
             ..
         } = &self.option_component_trait;
+        let record_change = |kind: TokenStream| {
+            self.component_changes_trait.as_ref().map(
+                |gen_trait::ComponentChangesTrait {
+                     component_name,
+                     change_struct:
+                         gen_struct::ChangeStruct {
+                             change_struct,
+                             table: change_table,
+                             ..
+                         },
+                     ..
+                 }| {
+                    let change_store = gen_trait::TableStoreTrait::ident(change_table);
+                    quote! {
+                      #change_store::insert(&self.ecs, #change_struct {
+                        change_id: 0,
+                        component: #component_name.to_string(),
+                        #id: self.#id,
+                        kind: ecs::ChangeKind::#kind,
+                      });
+                    }
+                },
+            )
+        };
+        let store = gen_trait::TableStoreTrait::ident(table);
+        let record_added = record_change(quote! { Added });
+        let record_changed = record_change(quote! { Changed });
+        let record_removed = record_change(quote! { Removed });
+        let macro_input::ComponentHooks {
+            on_insert,
+            on_update,
+            on_remove,
+        } = hooks;
+        let delete = if on_remove.is_empty() {
+            quote! {
+              self.#discard_fn();
+            }
+        } else {
+            quote! {
+              let removed = #store::find(&self.ecs, &self.#id);
+              self.#discard_fn();
+              if let ::core::option::Option::Some(#component) = removed {
+                #(#on_remove(self, &#component);)*
+              }
+            }
+        };
         tokens.extend(quote! {
           impl<'a> #option_component_trait for #entity_handle_struct<'a> {
             fn #insert_fn(&self, mut #component: #component_ty) -> #component_ty {
               #component.#id = self.#id;
-              ::spacetimedb::Table::insert(self.ecs.db.#table(), #component)
+              #record_added
+              let #component = #store::insert(&self.ecs, #component);
+              #(#on_insert(self, &#component);)*
+              #component
             }
             fn #update_fn(&self, mut #component: #component_ty) -> #component_ty {
               #component.#id = self.#id;
-              ::spacetimedb::UniqueColumn::update(&self.ecs.db.#table().#id(), #component)
+              #record_changed
+              let #component = #store::update(&self.ecs, #component);
+              #(#on_update(self, &#component);)*
+              #component
             }
             fn #delete_fn(&self) {
-              ::spacetimedb::UniqueColumn::delete(&self.ecs.db.#table().#id(), self.#id);
+              #delete
+            }
+            fn #discard_fn(&self) {
+              if #store::delete(&self.ecs, &self.#id) {
+                #record_removed
+              }
             }
           }
         });
@@ -149,6 +221,7 @@ impl Impl {
         } = entity_structs;
         let gen_trait::EntityTraits {
             option_component_traits,
+            component_changes_traits,
             ..
         } = entity_traits;
 
@@ -157,8 +230,11 @@ impl Impl {
             option_component_traits,
         );
 
-        let entity_handle_structs =
-            EntityHandleStruct::new_vec(entity_handle_struct, option_component_traits);
+        let entity_handle_structs = EntityHandleStruct::new_vec(
+            entity_handle_struct,
+            option_component_traits,
+            component_changes_traits,
+        );
 
         Ok(Self {
             with_component_structs,
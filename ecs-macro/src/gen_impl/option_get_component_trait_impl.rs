@@ -99,10 +99,11 @@ impl ToTokens for EntityHandleStruct {
             getter_fn,
             ..
         } = &self.option_get_component_trait;
+        let store = gen_trait::TableStoreTrait::ident(table);
         tokens.extend(quote! {
           impl<'a> #option_get_component_trait for #entity_handle_struct<'a> {
             fn #getter_fn(&self) -> ::core::option::Option<#component_ty> {
-              ::spacetimedb::UniqueColumn::find(&self.ecs.db.#table().#id(), self.#id)
+              #store::find(&self.ecs, &self.#id)
             }
           }
         });
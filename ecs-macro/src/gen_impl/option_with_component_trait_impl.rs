@@ -99,7 +99,7 @@ impl Option {
     ) -> RcSlice<Self> {
         option_with_component_traits
             .iter()
-            .map(|owct| Self::new(owct))
+            .map(Self::new)
             .collect()
     }
 }
@@ -0,0 +1,102 @@
+use crate::{fundamental, gen_struct, gen_trait, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct EcsStruct {
+    pub reactivate_entity_trait: gen_trait::ReactivateEntityTrait,
+    pub entity_struct: gen_struct::EntityStruct,
+    pub entity_handle_struct: gen_struct::EntityHandleStruct,
+}
+
+impl EcsStruct {
+    pub fn new(
+        reht: Option<&gen_trait::ReactivateEntityTrait>,
+        es: &gen_struct::EntityStruct,
+        ehs: &gen_struct::EntityHandleStruct,
+    ) -> Option<Self> {
+        reht.map(|reht| Self {
+            reactivate_entity_trait: reht.to_owned(),
+            entity_struct: es.to_owned(),
+            entity_handle_struct: ehs.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for EcsStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let gen_trait::ReactivateEntityTrait {
+            reactivate_entity_trait,
+            entity_handle_struct,
+            entity_blob_struct:
+                gen_struct::EntityBlobStruct {
+                    table: fundamental::Table(blob_table),
+                    ..
+                },
+            deactivation:
+                macro_input::BlobDeactivation {
+                    field,
+                    primary_key,
+                    primary_key_ty,
+                },
+        } = &self.reactivate_entity_trait;
+        let gen_struct::EntityStruct {
+            entity_struct,
+            table,
+            ..
+        } = &self.entity_struct;
+        let gen_struct::EntityHandleStruct { id, .. } = &self.entity_handle_struct;
+        let store = gen_trait::TableStoreTrait::ident(table);
+        let blob_store = gen_trait::TableStoreTrait::ident(blob_table);
+        tokens.extend(quote! {
+          impl<'a> #reactivate_entity_trait<'a> for ecs::Ecs<'a> {
+              fn reactivate(self, #primary_key: #primary_key_ty) -> ::core::option::Option<#entity_handle_struct<'a>> {
+                  let blob = #blob_store::find(&self, &#primary_key)?;
+                  let #id = ::core::clone::Clone::clone(&blob.#field)?;
+                  let entity = #store::try_insert(&self, #entity_struct { id: #id })?;
+                  #blob_store::delete(&self, &#primary_key);
+                  ::core::option::Option::Some(
+                    #entity_handle_struct {
+                      #id: entity.id,
+                      ecs: self,
+                    }
+                    .instantiate_blob(blob),
+                  )
+              }
+          }
+        });
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    ecs_struct: Option<EcsStruct>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let gen_struct::EntityStructs {
+            entity_struct,
+            entity_handle_struct,
+            ..
+        } = entity_structs;
+        let gen_trait::EntityTraits {
+            reactivate_entity_trait,
+            ..
+        } = entity_traits;
+
+        let ecs_struct = EcsStruct::new(
+            reactivate_entity_trait.as_ref(),
+            entity_struct,
+            entity_handle_struct,
+        );
+
+        Ok(Self { ecs_struct })
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use structmeta::ToTokens;
+use syn::Result;
+
+pub struct EcsStruct {
+    pub table_store_trait: gen_trait::TableStoreTrait,
+}
+
+impl EcsStruct {
+    pub fn new(tst: &gen_trait::TableStoreTrait) -> Self {
+        Self {
+            table_store_trait: tst.to_owned(),
+        }
+    }
+
+    pub fn new_vec(table_store_traits: &RcSlice<gen_trait::TableStoreTrait>) -> RcSlice<Self> {
+        table_store_traits.iter().map(Self::new).collect()
+    }
+
+    fn spacetimedb_impl(&self) -> TokenStream {
+        let gen_trait::TableStoreTrait {
+            table_store_trait,
+            table,
+            row_ty,
+            primary_key,
+            primary_key_ty,
+            indexed_fields,
+            change_index,
+            ..
+        } = &self.table_store_trait;
+        let indexed_fns = indexed_fields.iter().map(
+            |gen_trait::IndexedField {
+                 field, ty, kind, ..
+             }| {
+                let filter_fn = format_ident!("filter_{}", field);
+                let range_fn = format_ident!("range_{}", field);
+                let find_fn = format_ident!("find_{}", field);
+                match kind {
+                    gen_trait::IndexKind::BTree => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty> {
+                        self.db.#table().#field().filter(#field)
+                      }
+                      fn #range_fn(&self, range: &impl ::std::ops::RangeBounds<#ty>) -> impl Iterator<Item = #row_ty> {
+                        self.db.#table().#field().filter((range.start_bound(), range.end_bound()))
+                      }
+                    },
+                    gen_trait::IndexKind::Hash => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty> {
+                        self.db.#table().#field().filter(#field)
+                      }
+                    },
+                    gen_trait::IndexKind::Unique => quote! {
+                      fn #find_fn(&self, #field: &#ty) -> ::core::option::Option<#row_ty> {
+                        self.db.#table().#field().find(#field)
+                      }
+                    },
+                }
+            },
+        );
+        let change_fn = change_index.then(|| {
+            quote! {
+              fn filter_changes_after(&self, component: &str, change_id: u64) -> impl Iterator<Item = #row_ty> {
+                self.db.#table().component_change_id().filter((
+                  component,
+                  (::core::ops::Bound::Excluded(change_id), ::core::ops::Bound::Unbounded),
+                ))
+              }
+            }
+        });
+        quote! {
+          #[cfg(not(test))]
+          impl<'a> #table_store_trait for ecs::Ecs<'a> {
+            fn iter(&self) -> impl Iterator<Item = #row_ty> {
+              ::spacetimedb::Table::iter(self.db.#table())
+            }
+            fn insert(&self, row: #row_ty) -> #row_ty {
+              ::spacetimedb::Table::insert(self.db.#table(), row)
+            }
+            fn try_insert(&self, row: #row_ty) -> ::core::option::Option<#row_ty> {
+              ::spacetimedb::Table::try_insert(self.db.#table(), row).ok()
+            }
+            fn find(&self, key: &#primary_key_ty) -> ::core::option::Option<#row_ty> {
+              self.db.#table().#primary_key().find(key)
+            }
+            fn update(&self, row: #row_ty) -> #row_ty {
+              self.db.#table().#primary_key().update(row)
+            }
+            fn delete(&self, key: &#primary_key_ty) -> bool {
+              self.db.#table().#primary_key().delete(key)
+            }
+            #(#indexed_fns)*
+            #change_fn
+          }
+        }
+    }
+
+    fn memory_impl(&self) -> TokenStream {
+        let gen_trait::TableStoreTrait {
+            table_store_trait,
+            table,
+            row_ty,
+            primary_key,
+            primary_key_ty,
+            auto_inc,
+            indexed_fields,
+            change_index,
+        } = &self.table_store_trait;
+        let table_name = table.to_string();
+        let indexed_fns = indexed_fields.iter().map(
+            |gen_trait::IndexedField {
+                 field, ty, kind, ..
+             }| {
+                let filter_fn = format_ident!("filter_{}", field);
+                let range_fn = format_ident!("range_{}", field);
+                let find_fn = format_ident!("find_{}", field);
+                match kind {
+                    gen_trait::IndexKind::BTree => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty> {
+                        #table_store_trait::iter(self).filter(move |row| row.#field == *#field)
+                      }
+                      fn #range_fn(&self, range: &impl ::std::ops::RangeBounds<#ty>) -> impl Iterator<Item = #row_ty> {
+                        #table_store_trait::iter(self).filter(move |row| range.contains(&row.#field))
+                      }
+                    },
+                    gen_trait::IndexKind::Hash => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty> {
+                        #table_store_trait::iter(self).filter(move |row| row.#field == *#field)
+                      }
+                    },
+                    gen_trait::IndexKind::Unique => quote! {
+                      fn #find_fn(&self, #field: &#ty) -> ::core::option::Option<#row_ty> {
+                        #table_store_trait::iter(self).find(|row| row.#field == *#field)
+                      }
+                    },
+                }
+            },
+        );
+        let change_fn = change_index.then(|| {
+            quote! {
+              fn filter_changes_after(&self, component: &str, change_id: u64) -> impl Iterator<Item = #row_ty> {
+                #table_store_trait::iter(self).filter(move |row| row.component == component && row.change_id > change_id)
+              }
+            }
+        });
+        let unique_fields = indexed_fields
+            .iter()
+            .filter(|f| matches!(f.kind, gen_trait::IndexKind::Unique))
+            .map(|f| &f.field)
+            .collect::<Vec<_>>();
+        let conflicts = quote! {
+          |r: &#row_ty, row: &#row_ty| r.#primary_key == row.#primary_key #(|| r.#unique_fields == row.#unique_fields)*
+        };
+        let next_id = auto_inc.then(|| {
+            quote! {
+              if row.#primary_key == 0 {
+                row.#primary_key = ::core::convert::TryFrom::try_from(ecs::memory::next_id(#table_name))
+                  .unwrap_or_default();
+              }
+            }
+        });
+        quote! {
+          #[cfg(test)]
+          impl<'a> #table_store_trait for ecs::Ecs<'a> {
+            fn iter(&self) -> impl Iterator<Item = #row_ty> {
+              ecs::memory::rows::<#row_ty>(#table_name).into_iter()
+            }
+            fn insert(&self, mut row: #row_ty) -> #row_ty {
+              #next_id
+              ecs::memory::insert_unique(#table_name, ::core::clone::Clone::clone(&row), #conflicts);
+              row
+            }
+            fn try_insert(&self, mut row: #row_ty) -> ::core::option::Option<#row_ty> {
+              #next_id
+              let conflicts = #conflicts;
+              if #table_store_trait::iter(self).any(|r| conflicts(&r, &row)) {
+                return ::core::option::Option::None;
+              }
+              ecs::memory::insert(#table_name, ::core::clone::Clone::clone(&row));
+              ::core::option::Option::Some(row)
+            }
+            fn find(&self, key: &#primary_key_ty) -> ::core::option::Option<#row_ty> {
+              #table_store_trait::iter(self).find(|row| row.#primary_key == *key)
+            }
+            fn update(&self, row: #row_ty) -> #row_ty {
+              ecs::memory::remove::<#row_ty>(#table_name, |r| r.#primary_key == row.#primary_key);
+              ecs::memory::insert(#table_name, ::core::clone::Clone::clone(&row));
+              row
+            }
+            fn delete(&self, key: &#primary_key_ty) -> bool {
+              ecs::memory::remove::<#row_ty>(#table_name, |row| row.#primary_key == *key).is_some()
+            }
+            #(#indexed_fns)*
+            #change_fn
+          }
+        }
+    }
+}
+
+impl ToTokens for EcsStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.spacetimedb_impl());
+        tokens.extend(self.memory_impl());
+    }
+}
+
+#[derive(ToTokens)]
+pub struct Impl {
+    ecs_structs: RcSlice<EcsStruct>,
+}
+
+impl Impl {
+    pub fn new(
+        entity_macro_input: &macro_input::EntityMacroInput,
+        entity_structs: &gen_struct::EntityStructs,
+        entity_traits: &gen_trait::EntityTraits,
+    ) -> Result<Self> {
+        let _ = entity_macro_input;
+        let _ = entity_structs;
+        let gen_trait::EntityTraits {
+            table_store_traits,
+            ..
+        } = entity_traits;
+
+        let ecs_structs = EcsStruct::new_vec(table_store_traits);
+
+        Ok(Self { ecs_structs })
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{fundamental, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Ident, Type};
+
+/// The log of changes to tracked components, and the cursor of each system
+/// reading it.
+#[derive(Clone)]
+pub struct ChangeStruct {
+    pub attrs: fundamental::Attributes,
+    pub change_struct: Ident,
+    pub table: Ident,
+    pub change_cursor_struct: Ident,
+    pub cursor_table: Ident,
+    pub systems: Vec<Ident>,
+    pub id: Ident,
+    pub id_ty: Type,
+}
+
+impl ChangeStruct {
+    pub fn new(
+        struct_attrs: &fundamental::WithAttrs<macro_input::StructAttrsDeclaration>,
+        changes_declaration: Option<&fundamental::WithAttrs<macro_input::ChangesDeclaration>>,
+        entity_declaration: &fundamental::WithAttrs<macro_input::EntityDeclaration>,
+    ) -> Option<Self> {
+        changes_declaration.map(|changes_declaration| Self {
+            attrs: struct_attrs.attrs.concat(&changes_declaration.attrs),
+            change_struct: changes_declaration.change.to_owned(),
+            table: changes_declaration.table.to_owned(),
+            change_cursor_struct: format_ident!("{}Cursor", changes_declaration.change),
+            cursor_table: changes_declaration.cursor_table.to_owned(),
+            systems: changes_declaration.systems.to_owned(),
+            id: entity_declaration.id.to_owned(),
+            id_ty: entity_declaration.id_ty.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for ChangeStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            attrs,
+            change_struct,
+            table,
+            change_cursor_struct,
+            cursor_table,
+            id,
+            id_ty,
+            ..
+        } = self;
+        tokens.extend(quote! {
+          #attrs
+          #[::spacetimedb::table(
+            accessor = #table,
+            index(accessor = component_change_id, btree(columns = [component, change_id]))
+          )]
+          pub struct #change_struct {
+            #[primary_key]
+            #[auto_inc]
+            pub change_id: u64,
+            pub component: ::std::string::String,
+            pub #id: #id_ty,
+            pub kind: ecs::ChangeKind,
+          }
+
+          #attrs
+          #[::spacetimedb::table(accessor = #cursor_table)]
+          pub struct #change_cursor_struct {
+            #[primary_key]
+            pub system: ::std::string::String,
+            pub change_id: u64,
+          }
+        })
+    }
+}
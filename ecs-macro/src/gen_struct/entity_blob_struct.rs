@@ -1,14 +1,18 @@
 use crate::{RcSlice, fundamental, macro_input};
 use proc_macro2::TokenStream;
-use quote::{ToTokens, format_ident, quote};
+use quote::{ToTokens, quote};
 use syn::Ident;
 
 #[derive(Clone)]
-pub struct EntityBlobComponentField(pub macro_input::ComponentTablePair, pub Ident);
+pub struct EntityBlobComponentField(
+    pub macro_input::ComponentTablePair,
+    pub Ident,
+    pub Vec<macro_input::FieldMerge>,
+);
 
 impl ToTokens for EntityBlobComponentField {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let Self(macro_input::ComponentTablePair { component, .. }, component_ty) = self;
+        let Self(macro_input::ComponentTablePair { component, .. }, component_ty, _) = self;
         tokens.extend(quote! {
             pub #component: ::core::option::Option<#component_ty>
         })
@@ -20,6 +24,9 @@ pub struct EntityBlobStruct {
     pub attrs: fundamental::Attributes,
     pub table: fundamental::Table,
     pub entity_blob_struct: Ident,
+    pub blob_fields: fundamental::Fields,
+    pub parent: Option<macro_input::BlobParent>,
+    pub deactivation: Option<macro_input::BlobDeactivation>,
     pub component_fields: RcSlice<EntityBlobComponentField>,
 }
 
@@ -27,18 +34,24 @@ impl EntityBlobStruct {
     pub fn new(
         struct_attrs: &fundamental::WithAttrs<macro_input::StructAttrsDeclaration>,
         blob_declaration: Option<&fundamental::WithAttrs<macro_input::BlobDeclaration>>,
-        entity_declaration: &fundamental::WithAttrs<macro_input::EntityDeclaration>,
         component_declarations: &RcSlice<fundamental::WithAttrs<macro_input::ComponentDeclaration>>,
     ) -> Option<Self> {
         blob_declaration.map(|blob_declaration| Self {
-            attrs: struct_attrs.attrs.to_owned(),
+            attrs: struct_attrs.attrs.concat(&blob_declaration.attrs),
             table: fundamental::Table(blob_declaration.table.to_owned()),
-            entity_blob_struct: format_ident!("{}Blob", entity_declaration.entity),
+            entity_blob_struct: blob_declaration.blob.to_owned(),
+            blob_fields: blob_declaration.fields.to_owned(),
+            parent: blob_declaration.parent.to_owned(),
+            deactivation: blob_declaration.deactivation.to_owned(),
             component_fields: component_declarations
                 .iter()
                 .flat_map(|cdwa| {
                     cdwa.component_table_pairs.iter().map(|ctp| {
-                        EntityBlobComponentField(ctp.to_owned(), cdwa.component_ty.to_owned())
+                        EntityBlobComponentField(
+                            ctp.to_owned(),
+                            cdwa.component_ty.to_owned(),
+                            cdwa.merges.to_owned(),
+                        )
                     })
                 })
                 .collect(),
@@ -52,13 +65,18 @@ impl ToTokens for EntityBlobStruct {
             attrs,
             table,
             entity_blob_struct,
+            blob_fields,
+            parent: _,
+            deactivation: _,
             component_fields,
         } = self;
         let component_fields = component_fields.iter();
         tokens.extend(quote! {
           #attrs
           #table
+          #[derive(::core::default::Default)]
           pub struct #entity_blob_struct {
+            #blob_fields
             #(#component_fields,)*
           }
         })
@@ -1,4 +1,5 @@
 use crate::{macro_input, rc_slice::RcSlice};
+pub use change_struct::ChangeStruct;
 pub use component_struct::ComponentStruct;
 pub use entity_blob_struct::{EntityBlobComponentField, EntityBlobStruct};
 pub use entity_handle_struct::EntityHandleStruct;
@@ -6,6 +7,7 @@ pub use entity_struct::EntityStruct;
 use structmeta::ToTokens;
 pub use with_component_struct::WithComponentStruct;
 
+mod change_struct;
 mod component_struct;
 mod entity_blob_struct;
 mod entity_handle_struct;
@@ -19,12 +21,14 @@ pub struct EntityStructs {
     pub entity_handle_struct: EntityHandleStruct,
     pub entity_blob_struct: Option<EntityBlobStruct>,
     pub with_component_structs: RcSlice<WithComponentStruct>,
+    pub change_struct: Option<ChangeStruct>,
 }
 
 impl EntityStructs {
     pub fn new(entity_macro_input: &macro_input::EntityMacroInput) -> Self {
         let macro_input::EntityMacroInput {
             blob_declaration,
+            changes_declaration,
             entity_declaration,
             component_declarations,
             struct_attrs,
@@ -36,14 +40,15 @@ impl EntityStructs {
         let component_structs =
             ComponentStruct::new_vec(struct_attrs, component_declarations, entity_declaration);
         let entity_handle_struct = EntityHandleStruct::new(struct_attrs, entity_declaration);
-        let entity_blob_struct = EntityBlobStruct::new(
+        let entity_blob_struct =
+            EntityBlobStruct::new(struct_attrs, blob_declaration, component_declarations);
+        let with_component_structs =
+            WithComponentStruct::new_vec(struct_attrs, component_declarations);
+        let change_struct = ChangeStruct::new(
             struct_attrs,
-            blob_declaration,
+            changes_declaration.as_ref(),
             entity_declaration,
-            component_declarations,
         );
-        let with_component_structs =
-            WithComponentStruct::new_vec(&struct_attrs, &component_declarations);
 
         Self {
             entity_struct,
@@ -51,6 +56,7 @@ impl EntityStructs {
             entity_handle_struct,
             entity_blob_struct,
             with_component_structs,
+            change_struct,
         }
     }
 }
@@ -0,0 +1,35 @@
+use crate::gen_struct;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::Ident;
+
+#[derive(Clone)]
+pub struct AdvanceChangeCursorTrait {
+    pub advance_change_cursor_trait: Ident,
+    pub change_struct: gen_struct::ChangeStruct,
+}
+
+impl AdvanceChangeCursorTrait {
+    pub fn new(cs: Option<&gen_struct::ChangeStruct>) -> Option<Self> {
+        cs.map(|cs| Self {
+            advance_change_cursor_trait: format_ident!("Advance{}", cs.change_cursor_struct),
+            change_struct: cs.to_owned(),
+        })
+    }
+}
+
+impl ToTokens for AdvanceChangeCursorTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            advance_change_cursor_trait,
+            ..
+        } = self;
+        tokens.extend(quote! {
+          pub trait #advance_change_cursor_trait {
+              /// Marks every recorded change as seen by `system`, then drops the
+              /// changes which every system has seen.
+              fn advance_change_cursor(&self, system: &str);
+          }
+        })
+    }
+}
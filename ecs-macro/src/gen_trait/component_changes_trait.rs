@@ -0,0 +1,71 @@
+use crate::{fundamental, gen_struct, macro_input, rc_slice::RcSlice};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Ident, LitStr};
+
+#[derive(Clone)]
+pub struct ComponentChangesTrait {
+    pub component: Ident,
+    pub component_name: LitStr,
+    pub component_changes_trait: Ident,
+    pub iter_changes_fn: Ident,
+    pub change_struct: gen_struct::ChangeStruct,
+    pub entity_handle_struct: gen_struct::EntityHandleStruct,
+}
+
+impl ComponentChangesTrait {
+    pub fn new(
+        ctp: &macro_input::ComponentTablePair,
+        cs: &gen_struct::ChangeStruct,
+        ehs: &gen_struct::EntityHandleStruct,
+    ) -> Self {
+        Self {
+            component: ctp.component.to_owned(),
+            component_name: LitStr::new(&ctp.component.to_string(), ctp.component.span()),
+            component_changes_trait: format_ident!("__{}__Changes", ctp.component),
+            iter_changes_fn: format_ident!("iter_{}_changes", ctp.component),
+            change_struct: cs.to_owned(),
+            entity_handle_struct: ehs.to_owned(),
+        }
+    }
+
+    pub fn new_vec(
+        component_declarations: &RcSlice<fundamental::WithAttrs<macro_input::ComponentDeclaration>>,
+        cs: Option<&gen_struct::ChangeStruct>,
+        ehs: &gen_struct::EntityHandleStruct,
+    ) -> RcSlice<Self> {
+        cs.iter()
+            .flat_map(|cs| {
+                component_declarations.iter().flat_map(move |cdwa| {
+                    cdwa.component_table_pairs
+                        .iter()
+                        .filter(|ctp| cdwa.is_tracked(&ctp.component))
+                        .map(move |ctp| Self::new(ctp, cs, ehs))
+                })
+            })
+            .collect()
+    }
+}
+
+impl ToTokens for ComponentChangesTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            component_changes_trait,
+            iter_changes_fn,
+            entity_handle_struct:
+                gen_struct::EntityHandleStruct {
+                    entity_handle_struct,
+                    ..
+                },
+            ..
+        } = self;
+        tokens.extend(quote! {
+          #[allow(non_camel_case_types)]
+          pub trait #component_changes_trait<'a> {
+            /// Yields the net change to each entity's component since `system`
+            /// last advanced its change cursor.
+            fn #iter_changes_fn(&self, system: &str) -> impl Iterator<Item = (#entity_handle_struct<'a>, ecs::ChangeKind)>;
+          }
+        })
+    }
+}
@@ -24,7 +24,7 @@ impl ComponentDeleteTrait {
     ) -> RcSlice<Self> {
         component_declarations
             .iter()
-            .flat_map(|dwa| dwa.component_table_pairs.iter().map(|ctp| Self::new(ctp)))
+            .flat_map(|dwa| dwa.component_table_pairs.iter().map(Self::new))
             .collect()
     }
 }
@@ -0,0 +1,57 @@
+use crate::{fundamental, gen_struct, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::Ident;
+
+#[derive(Clone)]
+pub struct DeactivateEntityTrait {
+    pub deactivate_entity_trait: Ident,
+    pub entity_blob_struct: gen_struct::EntityBlobStruct,
+    pub deactivation: macro_input::BlobDeactivation,
+}
+
+impl DeactivateEntityTrait {
+    pub fn new(ebs: Option<&gen_struct::EntityBlobStruct>) -> Option<Self> {
+        let ebs = ebs?;
+        Some(Self {
+            deactivate_entity_trait: format_ident!("DeactivateEntity"),
+            entity_blob_struct: ebs.to_owned(),
+            deactivation: ebs.deactivation.to_owned()?,
+        })
+    }
+
+    /// The blob fields to pass to `deactivate`, without the field it fills in
+    /// with the entity ID.
+    pub fn deactivate_fields(&self) -> fundamental::Fields {
+        fundamental::Fields(
+            self.entity_blob_struct
+                .blob_fields
+                .iter()
+                .filter(|f| f.ident.as_ref() != Some(&self.deactivation.field))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl ToTokens for DeactivateEntityTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            deactivate_entity_trait,
+            entity_blob_struct:
+                gen_struct::EntityBlobStruct {
+                    entity_blob_struct, ..
+                },
+            ..
+        } = self;
+        let deactivate_field_args = fundamental::FieldArgs(self.deactivate_fields());
+        tokens.extend(quote! {
+          pub trait #deactivate_entity_trait {
+              /// Snapshots the entity into the blob table and deletes it along
+              /// with its components, without resolving relations, as the entity
+              /// keeps its ID when reactivated.
+              fn deactivate(&self, #deactivate_field_args) -> #entity_blob_struct;
+          }
+        })
+    }
+}
@@ -33,6 +33,8 @@ impl ToTokens for FindEntityHandleTrait {
         tokens.extend(quote! {
           pub trait #find_entity_handle_trait<'a> {
               fn find(self, #id: #id_ty) -> #entity_handle_struct<'a>;
+              /// Like `find`, but `None` unless the entity row exists.
+              fn try_find(self, #id: #id_ty) -> ::core::option::Option<#entity_handle_struct<'a>>;
           }
         })
     }
@@ -1,7 +1,57 @@
 use crate::{fundamental, gen_struct, macro_input, rc_slice::RcSlice};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::{Ident, Result};
+use syn::{Field, Ident, Result, Type};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IndexKind {
+    BTree,
+    /// `#[index(hash)]`, or any other index which only supports lookups by
+    /// an exact value, so gets no range functions.
+    Hash,
+    Unique,
+}
+
+/// A component field declared with `#[index(btree)]`, `#[index(hash)]` or
+/// `#[unique]`, which gets its own query functions alongside the full-table
+/// `iter_<component>`.
+#[derive(Clone)]
+pub struct IndexedField {
+    pub field: Ident,
+    pub ty: Type,
+    pub kind: IndexKind,
+    pub by_fn: Ident,
+    pub range_fn: Ident,
+}
+
+impl IndexedField {
+    pub fn new(component: &Ident, field: &Field) -> Option<Self> {
+        let kind = field.attrs.iter().find_map(|attr| {
+            if attr.path().is_ident("unique") {
+                Some(IndexKind::Unique)
+            } else if attr.path().is_ident("index") {
+                let mut kind = IndexKind::Hash;
+                let _ = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("btree") {
+                        kind = IndexKind::BTree;
+                    }
+                    Ok(())
+                });
+                Some(kind)
+            } else {
+                None
+            }
+        })?;
+        let field_ident = field.ident.to_owned()?;
+        Some(Self {
+            by_fn: format_ident!("iter_{}_by_{}", component, field_ident),
+            range_fn: format_ident!("iter_{}_by_{}_range", component, field_ident),
+            field: field_ident,
+            ty: field.ty.to_owned(),
+            kind,
+        })
+    }
+}
 
 #[derive(Clone)]
 pub struct IterComponentTrait {
@@ -11,10 +61,12 @@ pub struct IterComponentTrait {
     pub iter_fn: Ident,
     pub with_component_struct: Ident,
     pub entity_handle_struct: gen_struct::EntityHandleStruct,
+    pub indexed_fields: RcSlice<IndexedField>,
 }
 
 impl IterComponentTrait {
     pub fn new(
+        cd: &macro_input::ComponentDeclaration,
         ctp: &macro_input::ComponentTablePair,
         wcs: &gen_struct::WithComponentStruct,
         ehs: &gen_struct::EntityHandleStruct,
@@ -26,6 +78,11 @@ impl IterComponentTrait {
             iter_fn: format_ident!("iter_{}", ctp.component),
             with_component_struct: wcs.with_component_struct.to_owned(),
             entity_handle_struct: ehs.to_owned(),
+            indexed_fields: cd
+                .fields
+                .iter()
+                .filter_map(|f| IndexedField::new(&ctp.component, f))
+                .collect(),
         }
     }
 
@@ -47,7 +104,7 @@ impl IterComponentTrait {
                                 "Cannot find the corresponding with-component struct.",
                             )
                         })?;
-                    Ok(Self::new(ctp, wcs, ehs))
+                    Ok(Self::new(cdwa, ctp, wcs, ehs))
                 })
             })
             .collect()
@@ -65,12 +122,38 @@ impl ToTokens for IterComponentTrait {
                     entity_handle_struct,
                     ..
                 },
+            indexed_fields,
             ..
         } = self;
+        let indexed_fns = indexed_fields.iter().map(
+            |IndexedField {
+                 field,
+                 ty,
+                 kind,
+                 by_fn,
+                 range_fn,
+             }| {
+                let range = (*kind == IndexKind::BTree).then(|| {
+                    quote! {
+                      fn #range_fn(
+                        &self,
+                        range: &impl ::std::ops::RangeBounds<#ty>,
+                      ) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>>;
+                    }
+                });
+                quote! {
+                  // The argument mirrors the declared field type, which may be `String`.
+                  #[allow(clippy::ptr_arg)]
+                  fn #by_fn(&self, #field: &#ty) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>>;
+                  #range
+                }
+            },
+        );
         tokens.extend(quote! {
           #[allow(non_camel_case_types)]
           pub trait #iter_component_trait<'a> {
             fn #iter_fn(&self) -> impl Iterator<Item = #with_component_struct<#entity_handle_struct<'a>>>;
+            #(#indexed_fns)*
           }
         })
     }
@@ -1,6 +1,19 @@
 secador::secador!(
     (module, Type, new, field, FieldType),
     [
+        (
+            table_store_trait,
+            TableStoreTrait,
+            new_vec(
+                component_declarations,
+                entity_struct,
+                entity_handle_struct,
+                entity_blob_struct,
+                change_struct,
+            ),
+            table_store_traits,
+            Type![RcSlice<TableStoreTrait>],
+        ),
         (
             delete_entity_trait,
             DeleteEntityTrait,
@@ -93,10 +106,24 @@ secador::secador!(
         (
             option_component_iter_trait,
             OptionComponentIterTrait,
-            new_vec(&option_with_component_traits),
+            new_vec(&option_with_component_traits, &option_get_component_traits)?,
             option_component_iter_traits,
             Type![RcSlice<OptionComponentIterTrait>],
         ),
+        (
+            component_changes_trait,
+            ComponentChangesTrait,
+            new_vec(component_declarations, change_struct, entity_handle_struct),
+            component_changes_traits,
+            Type![RcSlice<ComponentChangesTrait>],
+        ),
+        (
+            advance_change_cursor_trait,
+            AdvanceChangeCursorTrait,
+            new(change_struct),
+            advance_change_cursor_trait,
+            Type![Option<AdvanceChangeCursorTrait>],
+        ),
         (
             new_entity_blob_trait,
             NewEntityBlobTrait,
@@ -111,6 +138,20 @@ secador::secador!(
             instantiate_entity_blob_trait,
             Type![Option<InstantiateEntityBlobTrait>],
         ),
+        (
+            deactivate_entity_trait,
+            DeactivateEntityTrait,
+            new(entity_blob_struct),
+            deactivate_entity_trait,
+            Type![Option<DeactivateEntityTrait>],
+        ),
+        (
+            reactivate_entity_trait,
+            ReactivateEntityTrait,
+            new(entity_blob_struct, entity_handle_struct),
+            reactivate_entity_trait,
+            Type![Option<ReactivateEntityTrait>],
+        ),
     ],
     {
         use crate::{gen_struct, macro_input, rc_slice::RcSlice};
@@ -135,12 +176,15 @@ secador::secador!(
                     ..
                 } = entity_macro_input;
                 let gen_struct::EntityStructs {
+                    entity_struct,
                     with_component_structs,
                     entity_handle_struct,
                     entity_blob_struct,
+                    change_struct,
                     ..
                 } = entity_structs;
                 let entity_blob_struct = entity_blob_struct.as_ref();
+                let change_struct = change_struct.as_ref();
 
                 seca!(1);
                 let __field = __Type::__new;
@@ -153,3 +197,5 @@ secador::secador!(
         }
     }
 );
+
+pub use iter_component_trait::{IndexKind, IndexedField};
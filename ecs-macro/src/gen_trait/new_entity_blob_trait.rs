@@ -1,4 +1,4 @@
-use crate::gen_struct;
+use crate::{fundamental, gen_struct};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
 use syn::Ident;
@@ -24,12 +24,15 @@ impl ToTokens for NewEntityBlobTrait {
             new_entity_blob_trait,
             entity_blob_struct:
                 gen_struct::EntityBlobStruct {
-                    entity_blob_struct, ..
+                    entity_blob_struct,
+                    blob_fields,
+                    ..
                 },
         } = self;
+        let blob_field_args = fundamental::FieldArgs(blob_fields.to_owned());
         tokens.extend(quote! {
           pub trait #new_entity_blob_trait {
-              fn new_blob(&self) -> #entity_blob_struct;
+              fn new_blob(&self, #blob_field_args) -> #entity_blob_struct;
           }
         })
     }
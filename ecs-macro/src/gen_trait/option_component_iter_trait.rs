@@ -1,7 +1,7 @@
 use crate::{gen_trait, rc_slice::RcSlice};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::Ident;
+use syn::{Error, Ident, Result};
 
 #[derive(Clone)]
 pub struct OptionComponentIterTrait {
@@ -9,24 +9,47 @@ pub struct OptionComponentIterTrait {
     pub option_with_component_trait: Ident,
     pub with_component_struct: Ident,
     pub with_fn: Ident,
+    pub option_get_component_trait: Ident,
+    pub component_ty: Ident,
+    pub getter_fn: Ident,
+    pub without_fn: Ident,
+    pub with_optional_fn: Ident,
 }
 
 impl OptionComponentIterTrait {
-    pub fn new(owct: &gen_trait::OptionWithComponentTrait) -> Self {
+    pub fn new(
+        owct: &gen_trait::OptionWithComponentTrait,
+        ogct: &gen_trait::OptionGetComponentTrait,
+    ) -> Self {
         Self {
             option_component_iter_trait: format_ident!("__{}__OptionIter", owct.component),
             option_with_component_trait: owct.option_with_component_trait.to_owned(),
             with_component_struct: owct.with_component_struct.to_owned(),
             with_fn: owct.with_fn.to_owned(),
+            option_get_component_trait: ogct.option_get_component_trait.to_owned(),
+            component_ty: ogct.component_ty.to_owned(),
+            getter_fn: ogct.getter_fn.to_owned(),
+            without_fn: format_ident!("without_{}", owct.component),
+            with_optional_fn: format_ident!("with_optional_{}", owct.component),
         }
     }
 
     pub fn new_vec(
         option_with_component_traits: &RcSlice<gen_trait::OptionWithComponentTrait>,
-    ) -> RcSlice<Self> {
+        option_get_component_traits: &RcSlice<gen_trait::OptionGetComponentTrait>,
+    ) -> Result<RcSlice<Self>> {
         option_with_component_traits
             .iter()
-            .map(|oct| Self::new(oct))
+            .map(|owct| {
+                let ogct = option_get_component_traits
+                    .iter()
+                    .find(|ogct| ogct.component == owct.component)
+                    .ok_or(Error::new(
+                        owct.component.span(),
+                        "Failed to find matching component.",
+                    ))?;
+                Ok(Self::new(owct, ogct))
+            })
             .collect()
     }
 }
@@ -38,6 +61,11 @@ impl ToTokens for OptionComponentIterTrait {
             option_with_component_trait,
             with_component_struct,
             with_fn,
+            option_get_component_trait,
+            component_ty,
+            getter_fn,
+            without_fn,
+            with_optional_fn,
         } = self;
         tokens.extend(quote! {
           #[allow(non_camel_case_types)]
@@ -45,6 +73,23 @@ impl ToTokens for OptionComponentIterTrait {
               fn #with_fn(self) -> impl Iterator<Item = #with_component_struct<T>> {
                   self.flat_map(|e| e.#with_fn())
               }
+
+              fn #without_fn(self) -> impl Iterator<Item = T>
+              where
+                  T: #option_get_component_trait,
+              {
+                  self.filter(|e| e.#getter_fn().is_none())
+              }
+
+              fn #with_optional_fn(self) -> impl Iterator<Item = (T, ::core::option::Option<#component_ty>)>
+              where
+                  T: #option_get_component_trait,
+              {
+                  self.map(|e| {
+                      let c = e.#getter_fn();
+                      (e, c)
+                  })
+              }
           }
         })
     }
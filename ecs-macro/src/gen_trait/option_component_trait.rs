@@ -19,8 +19,12 @@ pub struct OptionComponentTrait {
     pub insert_fn: Ident,
     pub update_fn: Ident,
     pub delete_fn: Ident,
+    /// Deletes without the `on_remove` hooks, for tearing down a whole entity.
+    pub discard_fn: Ident,
     pub insert_new_fn: Ident,
     pub update_new_fn: Ident,
+    pub relations: Vec<macro_input::Relation>,
+    pub hooks: macro_input::ComponentHooks,
 }
 
 impl OptionComponentTrait {
@@ -45,8 +49,11 @@ impl OptionComponentTrait {
             insert_fn: format_ident!("insert_{}", ctp.component),
             update_fn: format_ident!("update_{}", ctp.component),
             delete_fn: format_ident!("delete_{}", ctp.component),
+            discard_fn: format_ident!("discard_{}", ctp.component),
             insert_new_fn: format_ident!("insert_new_{}", ctp.component),
             update_new_fn: format_ident!("update_new_{}", ctp.component),
+            relations: cd.relations.to_owned(),
+            hooks: cd.component_hooks(&ctp.component),
         }
     }
 
@@ -97,8 +104,11 @@ impl ToTokens for OptionComponentTrait {
             insert_fn,
             update_fn,
             delete_fn,
+            discard_fn,
             insert_new_fn,
             update_new_fn,
+            relations: _,
+            hooks: _,
         } = self;
         tokens.extend(quote! {
           #[allow(non_camel_case_types)]
@@ -120,6 +130,7 @@ impl ToTokens for OptionComponentTrait {
             fn #insert_fn(&self, #component: #component_ty) -> #component_ty;
             fn #update_fn(&self, #component: #component_ty) -> #component_ty;
             fn #delete_fn(&self);
+            fn #discard_fn(&self);
             fn #insert_new_fn(&self, #component_field_args) -> #component_ty {
               self.#insert_fn(#component_ty::new(#component_field_names))
             }
@@ -0,0 +1,51 @@
+use crate::{gen_struct, macro_input};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::Ident;
+
+#[derive(Clone)]
+pub struct ReactivateEntityTrait {
+    pub reactivate_entity_trait: Ident,
+    pub entity_handle_struct: Ident,
+    pub entity_blob_struct: gen_struct::EntityBlobStruct,
+    pub deactivation: macro_input::BlobDeactivation,
+}
+
+impl ReactivateEntityTrait {
+    pub fn new(
+        ebs: Option<&gen_struct::EntityBlobStruct>,
+        ehs: &gen_struct::EntityHandleStruct,
+    ) -> Option<Self> {
+        let ebs = ebs?;
+        Some(Self {
+            reactivate_entity_trait: format_ident!("ReactivateEntity"),
+            entity_handle_struct: ehs.entity_handle_struct.to_owned(),
+            entity_blob_struct: ebs.to_owned(),
+            deactivation: ebs.deactivation.to_owned()?,
+        })
+    }
+}
+
+impl ToTokens for ReactivateEntityTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            reactivate_entity_trait,
+            entity_handle_struct,
+            deactivation:
+                macro_input::BlobDeactivation {
+                    primary_key,
+                    primary_key_ty,
+                    ..
+                },
+            ..
+        } = self;
+        tokens.extend(quote! {
+          pub trait #reactivate_entity_trait<'a> {
+              /// Restores an entity deactivated into the blob with this key, with
+              /// its former ID, and removes the blob. `None` if there is no such
+              /// deactivated blob or the ID has been taken.
+              fn reactivate(self, #primary_key: #primary_key_ty) -> ::core::option::Option<#entity_handle_struct<'a>>;
+          }
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{fundamental, gen_struct, gen_trait, macro_input, rc_slice::RcSlice};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Field, Ident, Type, parse_quote};
+
+/// The table operations the generated code needs, implemented on `Ecs` over
+/// SpacetimeDB, or over `ecs::memory` when compiled for `cargo test`.
+#[derive(Clone)]
+pub struct TableStoreTrait {
+    pub table_store_trait: Ident,
+    pub table: Ident,
+    pub row_ty: Ident,
+    pub primary_key: Ident,
+    pub primary_key_ty: Type,
+    pub auto_inc: bool,
+    pub indexed_fields: RcSlice<gen_trait::IndexedField>,
+    /// Whether the table is a change log with the `component_change_id` index.
+    pub change_index: bool,
+}
+
+impl TableStoreTrait {
+    pub fn ident(table: &Ident) -> Ident {
+        format_ident!("__{}__Store", table)
+    }
+
+    fn new(
+        table: &Ident,
+        row_ty: &Ident,
+        (primary_key, primary_key_ty): (&Ident, &Type),
+        auto_inc: bool,
+        fields: &[Field],
+    ) -> Self {
+        Self {
+            table_store_trait: Self::ident(table),
+            table: table.to_owned(),
+            row_ty: row_ty.to_owned(),
+            primary_key: primary_key.to_owned(),
+            primary_key_ty: primary_key_ty.to_owned(),
+            auto_inc,
+            indexed_fields: fields
+                .iter()
+                .filter_map(|f| gen_trait::IndexedField::new(table, f))
+                .collect(),
+            change_index: false,
+        }
+    }
+
+    pub fn new_vec(
+        component_declarations: &RcSlice<fundamental::WithAttrs<macro_input::ComponentDeclaration>>,
+        entity_struct: &gen_struct::EntityStruct,
+        entity_handle_struct: &gen_struct::EntityHandleStruct,
+        entity_blob_struct: Option<&gen_struct::EntityBlobStruct>,
+        change_struct: Option<&gen_struct::ChangeStruct>,
+    ) -> RcSlice<Self> {
+        let gen_struct::EntityHandleStruct { id, id_ty, .. } = entity_handle_struct;
+        let entity = Self::new(
+            &entity_struct.table,
+            &entity_struct.entity_struct,
+            (&format_ident!("id"), &entity_struct.id_ty),
+            true,
+            &[],
+        );
+        let components = component_declarations.iter().flat_map(|cdwa| {
+            cdwa.component_table_pairs.iter().map(|ctp| {
+                Self::new(
+                    &ctp.table,
+                    &cdwa.component_ty,
+                    (id, id_ty),
+                    false,
+                    &cdwa.fields,
+                )
+            })
+        });
+        // Blobs only need a store to be found by their primary key.
+        let blob = entity_blob_struct.and_then(|ebs| {
+            let primary_key = ebs.blob_fields.iter().find(|f| has_attr(f, "primary_key"))?;
+            Some(Self::new(
+                &ebs.table.0,
+                &ebs.entity_blob_struct,
+                (primary_key.ident.as_ref()?, &primary_key.ty),
+                has_attr(primary_key, "auto_inc"),
+                &ebs.blob_fields,
+            ))
+        });
+        let changes = change_struct.into_iter().flat_map(|cs| {
+            let change = Self {
+                change_index: true,
+                ..Self::new(
+                    &cs.table,
+                    &cs.change_struct,
+                    (&format_ident!("change_id"), &parse_quote!(u64)),
+                    true,
+                    &[],
+                )
+            };
+            let cursor = Self::new(
+                &cs.cursor_table,
+                &cs.change_cursor_struct,
+                (&format_ident!("system"), &parse_quote!(::std::string::String)),
+                false,
+                &[],
+            );
+            [change, cursor]
+        });
+        std::iter::once(entity)
+            .chain(components)
+            .chain(blob)
+            .chain(changes)
+            .collect()
+    }
+}
+
+fn has_attr(field: &Field, key: &str) -> bool {
+    field.attrs.iter().any(|attr| attr.path().is_ident(key))
+}
+
+impl ToTokens for TableStoreTrait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            table_store_trait,
+            row_ty,
+            primary_key_ty,
+            indexed_fields,
+            change_index,
+            ..
+        } = self;
+        let indexed_fns = indexed_fields.iter().map(
+            |gen_trait::IndexedField {
+                 field, ty, kind, ..
+             }| {
+                let filter_fn = format_ident!("filter_{}", field);
+                let range_fn = format_ident!("range_{}", field);
+                let find_fn = format_ident!("find_{}", field);
+                match kind {
+                    gen_trait::IndexKind::BTree => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty>;
+                      fn #range_fn(&self, range: &impl ::std::ops::RangeBounds<#ty>) -> impl Iterator<Item = #row_ty>;
+                    },
+                    gen_trait::IndexKind::Hash => quote! {
+                      fn #filter_fn(&self, #field: &#ty) -> impl Iterator<Item = #row_ty>;
+                    },
+                    gen_trait::IndexKind::Unique => quote! {
+                      fn #find_fn(&self, #field: &#ty) -> ::core::option::Option<#row_ty>;
+                    },
+                }
+            },
+        );
+        let change_fn = change_index.then(|| {
+            quote! {
+              fn filter_changes_after(&self, component: &str, change_id: u64) -> impl Iterator<Item = #row_ty>;
+            }
+        });
+        tokens.extend(quote! {
+          #[allow(non_camel_case_types, dead_code, clippy::ptr_arg)]
+          trait #table_store_trait {
+            fn iter(&self) -> impl Iterator<Item = #row_ty>;
+            fn insert(&self, row: #row_ty) -> #row_ty;
+            fn try_insert(&self, row: #row_ty) -> ::core::option::Option<#row_ty>;
+            fn find(&self, key: &#primary_key_ty) -> ::core::option::Option<#row_ty>;
+            fn update(&self, row: #row_ty) -> #row_ty;
+            fn delete(&self, key: &#primary_key_ty) -> bool;
+            #(#indexed_fns)*
+            #change_fn
+          }
+        })
+    }
+}
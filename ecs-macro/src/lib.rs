@@ -39,7 +39,7 @@ impl Parse for EntityMacro {
 
         let mut component_set = HashSet::new();
         for cdwa in component_declarations {
-            if cdwa.component_table_pairs.len() < 1 {
+            if cdwa.component_table_pairs.is_empty() {
                 return Err(Error::new(
                     cdwa.component_ty.span(),
                     "Must provide at least one component name.",
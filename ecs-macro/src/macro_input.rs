@@ -13,6 +13,13 @@ use syn::{
 mod kw {
     use syn::custom_keyword;
     custom_keyword!(table);
+    custom_keyword!(cursors);
+    custom_keyword!(systems);
+    custom_keyword!(cascade);
+    custom_keyword!(nullify);
+    custom_keyword!(replace);
+    custom_keyword!(add);
+    custom_keyword!(union);
 }
 
 fn try_extract_attr(
@@ -73,11 +80,235 @@ impl Parse for ComponentTablePair {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RelationKind {
+    /// Deleting the referenced entity deletes the referencing entity.
+    Cascade,
+    /// Deleting the referenced entity deletes the referencing component.
+    Nullify,
+}
+
+impl Parse for RelationKind {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::cascade) {
+            input.parse::<kw::cascade>()?;
+            Ok(RelationKind::Cascade)
+        } else if lookahead.peek(kw::nullify) {
+            input.parse::<kw::nullify>()?;
+            Ok(RelationKind::Nullify)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// A component field holding the ID of another entity, declared with
+/// `#[relation(cascade)]` or `#[relation(nullify)]`.
+#[derive(Clone)]
+pub struct Relation {
+    pub field: Ident,
+    pub kind: RelationKind,
+}
+
+impl Relation {
+    /// Removes the `relation` attribute from a field, as it is not meaningful
+    /// to the table.
+    fn extract(field: &mut syn::Field) -> Result<Option<Self>> {
+        let Some(position) = field
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("relation"))
+        else {
+            return Ok(None);
+        };
+        let attr = field.attrs.remove(position);
+        let kind = attr.parse_args::<RelationKind>()?;
+        let ident = field.ident.to_owned().ok_or(Error::new(
+            attr.span(),
+            "A relation field must have an identifier.",
+        ))?;
+        let is_indexed = field.attrs.iter().any(|attr| attr.path().is_ident("index"));
+        if !is_indexed {
+            return Err(Error::new(
+                attr.span(),
+                "A relation field must have `#[index(btree)]` to find the entities referencing a deleted entity.",
+            ));
+        }
+        Ok(Some(Relation { field: ident, kind }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MergeRule {
+    /// The child blob's value replaces the parent's.
+    Replace,
+    /// The child blob's value is added to the parent's, with `ecs::MergeAdd`.
+    Add,
+    /// The child blob's values are appended to the parent's, skipping values
+    /// the parent already has, with `ecs::MergeUnion`.
+    Union,
+}
+
+impl Parse for MergeRule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::replace) {
+            input.parse::<kw::replace>()?;
+            Ok(MergeRule::Replace)
+        } else if lookahead.peek(kw::add) {
+            input.parse::<kw::add>()?;
+            Ok(MergeRule::Add)
+        } else if lookahead.peek(kw::union) {
+            input.parse::<kw::union>()?;
+            Ok(MergeRule::Union)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// How a component field is merged when a child blob overlays a parent blob
+/// that has the same component, declared with `#[merge(add)]` or
+/// `#[merge(union)]`. Fields replace by default.
+#[derive(Clone)]
+pub struct FieldMerge {
+    pub field: Ident,
+    pub rule: MergeRule,
+}
+
+impl FieldMerge {
+    /// Removes the `merge` attribute from a field, as it is not meaningful to
+    /// the table.
+    fn extract(field: &mut syn::Field) -> Result<Option<Self>> {
+        let Some(position) = field
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("merge"))
+        else {
+            return Ok(None);
+        };
+        let attr = field.attrs.remove(position);
+        let rule = attr.parse_args::<MergeRule>()?;
+        let ident = field.ident.to_owned().ok_or(Error::new(
+            attr.span(),
+            "A merged field must have an identifier.",
+        ))?;
+        Ok(Some(FieldMerge { field: ident, rule }))
+    }
+}
+
+enum HookItem {
+    Component(Ident),
+    Hook(Ident, syn::Path),
+}
+
+impl Parse for HookItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Ok(HookItem::Hook(ident, input.parse()?))
+        } else {
+            Ok(HookItem::Component(ident))
+        }
+    }
+}
+
+/// Functions called with the entity handle and the component after the
+/// generated methods insert, update or delete it.
+#[derive(Clone, Default)]
+pub struct ComponentHooks {
+    pub on_insert: Vec<syn::Path>,
+    pub on_update: Vec<syn::Path>,
+    pub on_remove: Vec<syn::Path>,
+}
+
+/// A `#[hooks(on_insert = f, on_update = g, on_remove = h)]` attribute. Hooks
+/// apply to every component of the struct unless components are listed first,
+/// as in `#[hooks(a, b, on_remove = h)]`.
+struct HooksDeclaration {
+    components: Vec<Ident>,
+    hooks: ComponentHooks,
+}
+
+impl HooksDeclaration {
+    fn parse(attr: &Attribute, component_table_pairs: &[ComponentTablePair]) -> Result<Self> {
+        let items = attr.parse_args_with(|input: ParseStream| {
+            Ok(input
+                .parse_terminated(HookItem::parse, Token![,])?
+                .into_iter()
+                .collect::<Vec<_>>())
+        })?;
+        let mut components = vec![];
+        let mut hooks = ComponentHooks::default();
+        for item in items {
+            match item {
+                HookItem::Component(component) => {
+                    if !component_table_pairs
+                        .iter()
+                        .any(|ctp| ctp.component == component)
+                    {
+                        return Err(Error::new(
+                            component.span(),
+                            "Can only add hooks to a component declared by this struct.",
+                        ));
+                    }
+                    components.push(component);
+                }
+                HookItem::Hook(name, path) => match name.to_string().as_str() {
+                    "on_insert" => hooks.on_insert.push(path),
+                    "on_update" => hooks.on_update.push(path),
+                    "on_remove" => hooks.on_remove.push(path),
+                    _ => {
+                        return Err(Error::new(
+                            name.span(),
+                            "Expected `on_insert`, `on_update` or `on_remove`.",
+                        ));
+                    }
+                },
+            }
+        }
+        if components.is_empty() {
+            components = component_table_pairs
+                .iter()
+                .map(|ctp| ctp.component.to_owned())
+                .collect();
+        }
+        Ok(Self { components, hooks })
+    }
+}
+
 #[derive(Clone)]
 pub struct ComponentDeclaration {
     pub component_ty: Ident,
     pub component_table_pairs: Vec<ComponentTablePair>,
     pub fields: fundamental::Fields,
+    /// Components opted into change tracking with `#[track_changes]`, which
+    /// tracks every component of the struct, or `#[track_changes(a, b)]`.
+    pub tracked_components: Vec<Ident>,
+    pub relations: Vec<Relation>,
+    /// Hooks declared with `#[hooks(...)]`, by component.
+    pub hooks: Vec<(Ident, ComponentHooks)>,
+    pub merges: Vec<FieldMerge>,
+}
+
+impl ComponentDeclaration {
+    pub fn is_tracked(&self, component: &Ident) -> bool {
+        self.tracked_components.contains(component)
+    }
+
+    pub fn component_hooks(&self, component: &Ident) -> ComponentHooks {
+        self.hooks.iter().filter(|(c, _)| c == component).fold(
+            ComponentHooks::default(),
+            |mut all, (_, hooks)| {
+                all.on_insert.extend(hooks.on_insert.iter().cloned());
+                all.on_update.extend(hooks.on_update.iter().cloned());
+                all.on_remove.extend(hooks.on_remove.iter().cloned());
+                all
+            },
+        )
+    }
 }
 
 impl fundamental::AddAttrs for ComponentDeclaration {}
@@ -87,18 +318,73 @@ impl TryFrom<ItemStruct> for fundamental::WithAttrs<ComponentDeclaration> {
     fn try_from(value: ItemStruct) -> syn::Result<Self> {
         let (component_attr, attrs) = try_extract_attr("component", value.attrs.clone(), &value)?;
 
-        let component_table_pairs = component_attr.parse_args_with(|input: ParseStream| {
-            Ok(input
-                .parse_terminated(ComponentTablePair::parse, Token![,])?
-                .into_iter()
-                .collect())
-        })?;
+        let component_table_pairs: Vec<ComponentTablePair> =
+            component_attr.parse_args_with(|input: ParseStream| {
+                Ok(input
+                    .parse_terminated(ComponentTablePair::parse, Token![,])?
+                    .into_iter()
+                    .collect())
+            })?;
+        let (tracked_components, attrs) = if value.has_attr("track_changes") {
+            let (track_attr, attrs) = try_extract_attr("track_changes", attrs.to_vec(), &value)?;
+            let tracked_components: Vec<Ident> = match track_attr.meta {
+                syn::Meta::Path(_) => component_table_pairs
+                    .iter()
+                    .map(|ctp| ctp.component.to_owned())
+                    .collect(),
+                _ => track_attr.parse_args_with(|input: ParseStream| {
+                    Ok(input
+                        .parse_terminated(Ident::parse, Token![,])?
+                        .into_iter()
+                        .collect())
+                })?,
+            };
+            if let Some(unknown) = tracked_components
+                .iter()
+                .find(|c| !component_table_pairs.iter().any(|ctp| ctp.component == **c))
+            {
+                return Err(Error::new(
+                    unknown.span(),
+                    "Can only track changes to a component declared by this struct.",
+                ));
+            }
+            (tracked_components, attrs)
+        } else {
+            (vec![], attrs)
+        };
+        let (hooks_attrs, attrs): (Vec<Attribute>, Vec<Attribute>) = attrs
+            .iter()
+            .cloned()
+            .partition(|attr| attr.path().is_ident("hooks"));
+        let mut hooks = vec![];
+        for attr in &hooks_attrs {
+            let declaration = HooksDeclaration::parse(attr, &component_table_pairs)?;
+            for component in declaration.components {
+                hooks.push((component, declaration.hooks.to_owned()));
+            }
+        }
+        let attrs: fundamental::Attributes = attrs.into();
         let component_ty = value.ident.clone();
-        let fields = fundamental::Fields(value.fields.clone().into_iter().collect());
+        let mut fields: Vec<syn::Field> = value.fields.clone().into_iter().collect();
+        let relations = fields
+            .iter_mut()
+            .map(Relation::extract)
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>>>()?;
+        let merges = fields
+            .iter_mut()
+            .map(FieldMerge::extract)
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>>>()?;
+        let fields = fundamental::Fields(fields);
         Ok(ComponentDeclaration {
             component_ty,
             fields,
             component_table_pairs,
+            tracked_components,
+            relations,
+            hooks,
+            merges,
         }
         .add_attrs(attrs))
     }
@@ -166,7 +452,90 @@ impl ToTokens for EntityDeclaration {
 }
 
 pub struct BlobDeclaration {
+    pub blob: Ident,
     pub table: Ident,
+    /// Fields stored on the blob alongside its components, such as a name or
+    /// indexed ids for searching a blob library.
+    pub fields: fundamental::Fields,
+    pub parent: Option<BlobParent>,
+    pub deactivation: Option<BlobDeactivation>,
+}
+
+/// A blob field marked `#[parent]`, holding the primary key of the blob in the
+/// blob table that this blob inherits components from.
+#[derive(Clone)]
+pub struct BlobParent {
+    pub field: Ident,
+    pub primary_key: Ident,
+}
+
+/// A blob field marked `#[deactivated_entity]`, holding the ID of the entity
+/// deactivated into the blob, so it can be reactivated with the same ID.
+#[derive(Clone)]
+pub struct BlobDeactivation {
+    pub field: Ident,
+    pub primary_key: Ident,
+    pub primary_key_ty: Type,
+}
+
+/// Removes a marker attribute from the blob field that has it, as it is not
+/// meaningful to the table, returning the attribute and the field name.
+fn extract_blob_marker(fields: &mut [syn::Field], key: &str) -> Result<Option<(Attribute, Ident)>> {
+    let Some((field, position)) = fields.iter_mut().find_map(|field| {
+        let position = field
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident(key))?;
+        Some((field, position))
+    }) else {
+        return Ok(None);
+    };
+    let attr = field.attrs.remove(position);
+    let ident = field.ident.to_owned().ok_or(Error::new(
+        attr.span(),
+        format!("A `{}` field must have an identifier.", key),
+    ))?;
+    Ok(Some((attr, ident)))
+}
+
+/// The `#[primary_key]` blob field that a marked field relies on to find blobs.
+fn blob_primary_key(fields: &[syn::Field], attr: &Attribute) -> Result<(Ident, Type)> {
+    fields
+        .iter()
+        .find(|f| {
+            f.attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("primary_key"))
+        })
+        .and_then(|f| Some((f.ident.to_owned()?, f.ty.to_owned())))
+        .ok_or(Error::new(
+            attr.span(),
+            "This blob field needs a `#[primary_key]` field to find blobs by.",
+        ))
+}
+
+impl BlobParent {
+    fn extract(fields: &mut [syn::Field]) -> Result<Option<Self>> {
+        let Some((attr, field)) = extract_blob_marker(fields, "parent")? else {
+            return Ok(None);
+        };
+        let (primary_key, _) = blob_primary_key(fields, &attr)?;
+        Ok(Some(BlobParent { field, primary_key }))
+    }
+}
+
+impl BlobDeactivation {
+    fn extract(fields: &mut [syn::Field]) -> Result<Option<Self>> {
+        let Some((attr, field)) = extract_blob_marker(fields, "deactivated_entity")? else {
+            return Ok(None);
+        };
+        let (primary_key, primary_key_ty) = blob_primary_key(fields, &attr)?;
+        Ok(Some(BlobDeactivation {
+            field,
+            primary_key,
+            primary_key_ty,
+        }))
+    }
 }
 
 impl fundamental::AddAttrs for BlobDeclaration {}
@@ -181,21 +550,91 @@ impl TryFrom<ItemStruct> for fundamental::WithAttrs<BlobDeclaration> {
             input.parse::<Token![=]>()?;
             input.parse::<Ident>()
         })?;
-        // WIP Use this struct name instead of computing the blob name.
-        // let blob = value.ident.clone();
-        // WIP Capture fields and apply them to the blob.
-        // `new_blob` function must take the added fields as parameters.
-        // `deactivate` function must take them too.
-        // Additional fields would allow DB index fields to be added.
-        Ok(BlobDeclaration { table }.add_attrs(attrs))
+        if let Some(field) = value.fields.iter().find(|f| f.ident.is_none()) {
+            return Err(Error::new(field.span(), "Blob fields must be named."));
+        }
+        let blob = value.ident.clone();
+        let mut fields: Vec<syn::Field> = value.fields.clone().into_iter().collect();
+        let parent = BlobParent::extract(&mut fields)?;
+        let deactivation = BlobDeactivation::extract(&mut fields)?;
+        let fields = fundamental::Fields(fields);
+        Ok(BlobDeclaration {
+            blob,
+            table,
+            fields,
+            parent,
+            deactivation,
+        }
+        .add_attrs(attrs))
     }
 }
 
 /// Implement Spanned for BlobDeclaration
 impl ToTokens for BlobDeclaration {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let Self { table } = self;
-        table.to_tokens(tokens);
+        let Self { blob, .. } = self;
+        blob.to_tokens(tokens);
+    }
+}
+
+pub struct ChangesDeclaration {
+    pub change: Ident,
+    pub table: Ident,
+    pub cursor_table: Ident,
+    /// Every system reading changes. A change is only collected once each of
+    /// them has a cursor past it, so a system that has not run yet loses none.
+    pub systems: Vec<Ident>,
+}
+
+impl fundamental::AddAttrs for ChangesDeclaration {}
+
+impl TryFrom<ItemStruct> for fundamental::WithAttrs<ChangesDeclaration> {
+    type Error = syn::Error;
+    fn try_from(value: ItemStruct) -> syn::Result<Self> {
+        let (changes_attr, attrs) = try_extract_attr("changes", value.attrs.clone(), &value)?;
+
+        let (table, cursor_table, systems) =
+            changes_attr.parse_args_with(|input: ParseStream| {
+                input.parse::<kw::table>()?;
+                input.parse::<Token![=]>()?;
+                let table = input.parse::<Ident>()?;
+                input.parse::<Token![,]>()?;
+                input.parse::<kw::cursors>()?;
+                input.parse::<Token![=]>()?;
+                let cursor_table = input.parse::<Ident>()?;
+                input.parse::<Token![,]>()?;
+                input.parse::<kw::systems>()?;
+                input.parse::<Token![=]>()?;
+                let content;
+                syn::bracketed!(content in input);
+                let systems = content
+                    .parse_terminated(Ident::parse, Token![,])?
+                    .into_iter()
+                    .collect();
+                input.parse::<Option<Token![,]>>()?;
+                Ok((table, cursor_table, systems))
+            })?;
+        if !value.fields.is_empty() {
+            return Err(Error::new(
+                value.fields.span(),
+                "The change struct fields are generated and cannot be declared.",
+            ));
+        }
+        Ok(ChangesDeclaration {
+            change: value.ident.clone(),
+            table,
+            cursor_table,
+            systems,
+        }
+        .add_attrs(attrs))
+    }
+}
+
+/// Implement Spanned for ChangesDeclaration
+impl ToTokens for ChangesDeclaration {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self { change, .. } = self;
+        change.to_tokens(tokens);
     }
 }
 
@@ -207,6 +646,7 @@ pub struct EntityMacroInput {
     pub component_declarations: RcSlice<fundamental::WithAttrs<ComponentDeclaration>>,
     pub struct_attrs: fundamental::WithAttrs<StructAttrsDeclaration>,
     pub blob_declaration: Option<fundamental::WithAttrs<BlobDeclaration>>,
+    pub changes_declaration: Option<fundamental::WithAttrs<ChangesDeclaration>>,
 }
 
 trait HasAttr {
@@ -224,8 +664,7 @@ fn validate_unary_slice<T: ToTokens>(name: &str, items: &[T]) -> Result<()> {
         Ok(())
     } else {
         Err(Error::new(
-            Spanned::span(items.into_iter()
-                .nth(1)
+            Spanned::span(items.get(1)
                 .ok_or(
                   Error::new(Span::call_site(),
                   format!("Impossible! Couldn't get second elemnt in vec with more than one element (name = {}).", name)))?
@@ -239,9 +678,10 @@ impl Parse for EntityMacroInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut items = vec![];
         let mut entity_declarations = vec![];
-        let mut component_declarations = vec![];
+        let mut component_declarations: Vec<fundamental::WithAttrs<ComponentDeclaration>> = vec![];
         let mut struct_attrses = vec![];
         let mut blob_declarations = vec![];
+        let mut changes_declarations = vec![];
 
         while !input.is_empty() {
             let item: Item = input.parse()?;
@@ -255,6 +695,8 @@ impl Parse for EntityMacroInput {
                         struct_attrses.push(item_struct.try_into()?);
                     } else if item_struct.has_attr("blob") {
                         blob_declarations.push(item_struct.try_into()?);
+                    } else if item_struct.has_attr("changes") {
+                        changes_declarations.push(item_struct.try_into()?);
                     } else {
                         items.push(Item::Struct(item_struct));
                     }
@@ -268,6 +710,17 @@ impl Parse for EntityMacroInput {
         validate_unary_slice("entity_declaration", &entity_declarations)?;
         validate_unary_slice("struct_attrs", &struct_attrses)?;
         validate_unary_slice("blob_declaration", &blob_declarations)?;
+        validate_unary_slice("changes_declaration", &changes_declarations)?;
+        if changes_declarations.is_empty()
+            && let Some(cd) = component_declarations
+                .iter()
+                .find(|cd| !cd.tracked_components.is_empty())
+        {
+            return Err(Error::new(
+                cd.component_ty.span(),
+                "Tracking changes requires a `#[changes(...)]` declaration.",
+            ));
+        }
 
         Ok(EntityMacroInput {
             items: items.into_iter().collect(),
@@ -278,6 +731,7 @@ impl Parse for EntityMacroInput {
             component_declarations: component_declarations.into(),
             struct_attrs: struct_attrses.into_iter().next().unwrap_or_default(),
             blob_declaration: blob_declarations.into_iter().next(),
+            changes_declaration: changes_declarations.into_iter().next(),
         })
     }
 }
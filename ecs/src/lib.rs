@@ -1,8 +1,9 @@
-use std::ops::Deref;
+use std::{collections::BTreeMap, ops::Deref};
 
 pub use ecs_macro::entity;
-use spacetimedb::ReducerContext;
+use spacetimedb::{ReducerContext, SpacetimeType};
 
+pub mod memory;
 mod tests;
 
 #[derive(Clone, Copy)]
@@ -39,3 +40,99 @@ impl WithEcs for ReducerContext {
         Ecs { ctx: self }
     }
 }
+
+/// Combines a parent blob's value with a child blob's for a component field
+/// declared with `#[merge(add)]`. Integers saturate rather than overflow.
+pub trait MergeAdd {
+    fn merge_add(self, child: Self) -> Self;
+}
+
+macro_rules! merge_add_saturating {
+    ($($ty:ty),*) => {
+        $(impl MergeAdd for $ty {
+            fn merge_add(self, child: Self) -> Self {
+                self.saturating_add(child)
+            }
+        })*
+    };
+}
+
+merge_add_saturating!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl MergeAdd for f32 {
+    fn merge_add(self, child: Self) -> Self {
+        self + child
+    }
+}
+
+impl MergeAdd for f64 {
+    fn merge_add(self, child: Self) -> Self {
+        self + child
+    }
+}
+
+impl<T: MergeAdd> MergeAdd for Option<T> {
+    fn merge_add(self, child: Self) -> Self {
+        match (self, child) {
+            (Some(parent), Some(child)) => Some(parent.merge_add(child)),
+            (parent, None) => parent,
+            (None, child) => child,
+        }
+    }
+}
+
+/// Combines a parent blob's values with a child blob's for a component field
+/// declared with `#[merge(union)]`.
+pub trait MergeUnion {
+    fn merge_union(self, child: Self) -> Self;
+}
+
+impl<T: PartialEq> MergeUnion for Vec<T> {
+    /// The parent's values, followed by the child's values the parent lacks.
+    fn merge_union(mut self, child: Self) -> Self {
+        for value in child {
+            if !self.contains(&value) {
+                self.push(value);
+            }
+        }
+        self
+    }
+}
+
+/// How a tracked component changed since a system last advanced its change
+/// cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SpacetimeType)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+impl ChangeKind {
+    /// Folds a later change into the net change so far, or `None` if they
+    /// cancel out, such as a component added then removed between runs.
+    pub fn merge(earlier: Option<Self>, later: Self) -> Option<Self> {
+        match (earlier, later) {
+            (Some(ChangeKind::Added), ChangeKind::Changed) => Some(ChangeKind::Added),
+            (Some(ChangeKind::Added), ChangeKind::Removed) => None,
+            (Some(ChangeKind::Removed), ChangeKind::Added) => Some(ChangeKind::Changed),
+            (_, later) => Some(later),
+        }
+    }
+}
+
+/// Reduces changes, in the order they were recorded, to the net change of each
+/// entity, ordered by entity ID.
+pub fn net_changes<Id: Ord>(
+    changes: impl IntoIterator<Item = (Id, ChangeKind)>,
+) -> impl Iterator<Item = (Id, ChangeKind)> {
+    let mut net: BTreeMap<Id, Option<ChangeKind>> = BTreeMap::new();
+    for (id, kind) in changes {
+        let entry = net.entry(id).or_default();
+        *entry = ChangeKind::merge(*entry, kind);
+    }
+    net.into_iter()
+        .filter_map(|(id, kind)| kind.map(|kind| (id, kind)))
+}
@@ -0,0 +1,94 @@
+//! In-memory tables standing in for SpacetimeDB when the `entity!` macro is
+//! expanded for `cargo test`, so systems run against an `Ecs` without a live
+//! database. Build the `Ecs` from `ReducerContext::__dummy()` and call
+//! [`reset`] at the start of each test. Only the tables the macro declares are
+//! backed here; anything reached through `ecs.db` directly still needs the
+//! host.
+//!
+//! Tables are kept per thread, and rows are cloned in and out, so the rows of
+//! an `entity!` declaration must derive `Clone` through `#[struct_attrs]`.
+//!
+//! The backend is picked with `cfg(test)` of the crate expanding `entity!`, so
+//! its `cargo test` only ever runs this backend. The SpacetimeDB table code,
+//! including its index and `#[auto_inc]` behavior, is compiled but never
+//! executed by those tests.
+
+use std::{any::Any, cell::RefCell, collections::HashMap};
+
+thread_local! {
+    static TABLES: RefCell<HashMap<&'static str, Box<dyn Any>>> = RefCell::new(HashMap::new());
+    static SEQUENCES: RefCell<HashMap<&'static str, u64>> = RefCell::new(HashMap::new());
+}
+
+/// Empties every table and restarts every sequence on this thread.
+pub fn reset() {
+    TABLES.with_borrow_mut(|tables| tables.clear());
+    SEQUENCES.with_borrow_mut(|sequences| sequences.clear());
+}
+
+fn with_rows<Row: 'static, R>(table: &'static str, f: impl FnOnce(&mut Vec<Row>) -> R) -> R {
+    TABLES.with_borrow_mut(|tables| {
+        let rows = tables
+            .entry(table)
+            .or_insert_with(|| Box::new(Vec::<Row>::new()));
+        match rows.downcast_mut::<Vec<Row>>() {
+            Some(rows) => f(rows),
+            // Table names are unique within a module, so a mismatch is two
+            // declarations sharing a name, which would lose rows silently.
+            None => panic!(
+                "Table {} holds rows of another type than {}.",
+                table,
+                std::any::type_name::<Row>()
+            ),
+        }
+    })
+}
+
+/// A snapshot of the rows of a table, in insertion order.
+pub fn rows<Row: Clone + 'static>(table: &'static str) -> Vec<Row> {
+    with_rows(table, |rows: &mut Vec<Row>| rows.clone())
+}
+
+pub fn insert<Row: 'static>(table: &'static str, row: Row) {
+    with_rows(table, |rows| rows.push(row));
+}
+
+/// Inserts a row, panicking like the host's `Table::insert` when `conflicts`
+/// matches a row already in the table on its primary key or a unique column.
+pub fn insert_unique<Row: 'static>(
+    table: &'static str,
+    row: Row,
+    conflicts: impl Fn(&Row, &Row) -> bool,
+) {
+    with_rows(table, |rows: &mut Vec<Row>| {
+        if rows.iter().any(|r| conflicts(r, &row)) {
+            panic!(
+                "Duplicate unique column value inserted into table {}.",
+                table
+            );
+        }
+        rows.push(row);
+    });
+}
+
+/// Removes every row of a table, returning them in insertion order.
+pub fn take<Row: 'static>(table: &'static str) -> Vec<Row> {
+    with_rows(table, std::mem::take)
+}
+
+/// Removes the first row matching `predicate`, returning it.
+pub fn remove<Row: 'static>(table: &'static str, predicate: impl Fn(&Row) -> bool) -> Option<Row> {
+    with_rows(table, |rows: &mut Vec<Row>| {
+        let position = rows.iter().position(predicate)?;
+        Some(rows.remove(position))
+    })
+}
+
+/// The next value of an `#[auto_inc]` column, starting at 1.
+pub fn next_id(table: &'static str) -> u64 {
+    SEQUENCES.with_borrow_mut(|sequences| {
+        let id = sequences.entry(table).or_default();
+        *id += 1;
+        *id
+    })
+}
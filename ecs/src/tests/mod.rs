@@ -2,7 +2,7 @@
 
 use ecs_macro::entity;
 
-use crate::WithEcs;
+use crate::{ChangeKind, WithEcs, net_changes};
 
 mod ecs {
     pub use crate::*;
@@ -21,25 +21,61 @@ entity!(
     }
 
     #[blob(table = entity_blobs)]
-    pub struct EntityBlob;
+    pub struct Prototype {
+        #[primary_key]
+        pub id: u32,
+        #[parent]
+        pub parent_id: Option<u32>,
+        #[deactivated_entity]
+        pub deactivated_entity_id: Option<EntityId>,
+        #[index(btree)]
+        pub tag: u32,
+        pub name: String,
+    }
+
+    #[changes(table = component_changes, cursors = change_cursors, systems = [first, second])]
+    pub struct ComponentChange;
 
+    #[track_changes(location)]
     #[component(
       location in location_components,
       secondary_location in secondary_location_components,
     )]
     pub struct LocationComponent {
+        #[index(btree)]
+        #[relation(cascade)]
         pub location_entity_id: EntityId,
     }
 
+    #[hooks(excess_path, on_insert = note_path, on_remove = note_path)]
     #[component(
       path in path_components,
       excess_path in excess_path_components
     )]
     pub struct PathComponent {
+        #[index(btree)]
+        #[relation(nullify)]
         pub destination_entity_id: EntityId,
     }
+
+    #[component(stats in stats_components)]
+    pub struct StatsComponent {
+        #[merge(add)]
+        pub attack: i32,
+        #[merge(union)]
+        pub trait_ids: Vec<u32>,
+        #[index(hash)]
+        pub title: String,
+    }
 );
 
+/// Remembers where an excess path leads in a component declared before it, so
+/// a hook run while the entity is deleted would leave a row behind.
+fn note_path(e: &EntityHandle, path: &PathComponent) {
+    e.clone()
+        .upsert_new_secondary_location(path.destination_entity_id);
+}
+
 impl<'a> EntityHandle<'a> {
     pub fn peek() {}
 }
@@ -58,7 +94,17 @@ fn sandbox(ctx: &spacetimedb::ReducerContext) -> Option<()> {
     );
     EntityHandle::peek();
     ctx.ecs().new().delete();
-    ctx.ecs().new().new_blob();
+    let prototype = ctx
+        .ecs()
+        .new()
+        .new_blob(1, Some(0), None, 1, "prototype".to_string());
+    println!("{:?}", prototype.clone().flatten(&ctx.ecs()).stats);
+    ctx.ecs().new().instantiate_blob(prototype);
+    let parked = ctx.ecs().new().deactivate(0, None, 2, "parked".to_string());
+    ctx.ecs().reactivate(parked.id);
+    for p in ctx.db.entity_blobs().tag().filter(1u32) {
+        println!("{}", p.name);
+    }
     e.location();
     e.path();
     e.secondary_location();
@@ -79,5 +125,342 @@ fn sandbox(ctx: &spacetimedb::ReducerContext) -> Option<()> {
         println!("{:?}", pl.secondary_location());
     }
     LocationComponent::clone;
+    for (e, kind) in ctx.ecs().iter_location_changes("sandbox") {
+        println!("{:?} {:?}", e.location(), kind);
+    }
+    ctx.ecs().advance_change_cursor("sandbox");
     Some(())
 }
+
+#[test]
+fn overlay_merges_components_both_blobs_have() {
+    let parent = Prototype {
+        id: 1,
+        name: "parent".to_string(),
+        location: Some(LocationComponent::new(1)),
+        stats: Some(StatsComponent::new(2, vec![1, 2], "parent".to_string())),
+        ..Default::default()
+    };
+    let child = Prototype {
+        id: 2,
+        parent_id: Some(1),
+        name: "child".to_string(),
+        path: Some(PathComponent::new(3)),
+        stats: Some(StatsComponent::new(3, vec![2, 3], "child".to_string())),
+        ..Default::default()
+    };
+
+    let blob = parent.overlay(child);
+
+    assert_eq!(
+        (blob.id, blob.parent_id, blob.name.as_str()),
+        (2, Some(1), "child")
+    );
+    assert_eq!(blob.location.map(|l| l.location_entity_id), Some(1));
+    assert_eq!(blob.path.map(|p| p.destination_entity_id), Some(3));
+    assert_eq!(
+        blob.stats.map(|s| (s.attack, s.trait_ids, s.title)),
+        Some((5, vec![1, 2, 3], "child".to_string()))
+    );
+}
+
+#[test]
+fn blobs_keep_their_declared_fields_through_a_round_trip() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone()
+        .upsert_new_location(1)
+        .upsert_new_stats(4, vec![1], "knight".to_string());
+
+    let blob: Prototype = e.new_blob(5, Some(2), None, 3, "knight".to_string());
+    assert_eq!(
+        (
+            blob.id,
+            blob.parent_id,
+            blob.deactivated_entity_id,
+            blob.tag,
+            blob.name.as_str()
+        ),
+        (5, Some(2), None, 3, "knight")
+    );
+
+    let copy = ctx.ecs().new().instantiate_blob(blob);
+    let copied = copy.new_blob(6, None, None, 4, "copy".to_string());
+    assert_eq!(
+        (copied.id, copied.tag, copied.name.as_str()),
+        (6, 4, "copy")
+    );
+    assert_eq!(copied.location.map(|l| l.location_entity_id), Some(1));
+    assert_eq!(
+        copied.stats.map(|s| (s.attack, s.trait_ids, s.title)),
+        Some((4, vec![1], "knight".to_string()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "holds rows of another type")]
+fn memory_tables_reject_a_second_row_type() {
+    ecs::memory::reset();
+    ecs::memory::insert("numbers", 1u32);
+    ecs::memory::insert("numbers", 1u64);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate unique column value")]
+fn memory_tables_reject_a_duplicate_primary_key() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().insert_new_location(1);
+    e.insert_new_location(2);
+}
+
+#[test]
+fn merge_add_saturates() {
+    assert_eq!(ecs::MergeAdd::merge_add(i32::MAX, 1), i32::MAX);
+    assert_eq!(ecs::MergeAdd::merge_add(Some(1u8), None), Some(1));
+}
+
+#[test]
+fn net_changes_fold_each_entity_in_order() {
+    let changes = vec![
+        (2, ChangeKind::Added),
+        (1, ChangeKind::Changed),
+        (2, ChangeKind::Changed),
+        (3, ChangeKind::Added),
+        (1, ChangeKind::Removed),
+        (3, ChangeKind::Removed),
+        (4, ChangeKind::Removed),
+        (4, ChangeKind::Added),
+    ];
+
+    assert_eq!(
+        net_changes(changes).collect::<Vec<_>>(),
+        vec![
+            (1, ChangeKind::Removed),
+            (2, ChangeKind::Added),
+            (4, ChangeKind::Changed),
+        ]
+    );
+}
+
+#[test]
+fn net_changes_restart_after_cancelling_out() {
+    let changes = vec![
+        (1, ChangeKind::Added),
+        (1, ChangeKind::Removed),
+        (1, ChangeKind::Added),
+    ];
+
+    assert_eq!(
+        net_changes(changes).collect::<Vec<_>>(),
+        vec![(1, ChangeKind::Added)]
+    );
+}
+
+#[test]
+fn memory_tables_round_trip_components() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let room = ctx.ecs().new();
+    let e = ctx.ecs().new();
+
+    e.clone().upsert_new_location(room.entity_id());
+    assert_eq!(
+        (room.entity_id(), e.location().map(|l| l.location_entity_id)),
+        (1, Some(1))
+    );
+    assert_eq!(
+        ctx.ecs()
+            .iter_location_by_location_entity_id(&room.entity_id())
+            .map(|l| l.entity_id())
+            .collect::<Vec<_>>(),
+        vec![e.entity_id()]
+    );
+
+    e.clone().upsert_new_location(3);
+    assert_eq!(
+        ctx.ecs()
+            .iter_location_by_location_entity_id_range(&(1..3))
+            .count(),
+        0
+    );
+
+    e.delete_location();
+    assert_eq!(e.location().map(|l| l.location_entity_id), None);
+}
+
+#[test]
+fn memory_tables_look_up_hash_indexes_by_value() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().upsert_new_stats(1, vec![], "knight".to_string());
+    ctx.ecs()
+        .new()
+        .upsert_new_stats(2, vec![], "squire".to_string());
+
+    assert_eq!(
+        ctx.ecs()
+            .iter_stats_by_title(&"knight".to_string())
+            .map(|s| s.entity_id())
+            .collect::<Vec<_>>(),
+        vec![e.entity_id()]
+    );
+}
+
+#[test]
+fn iterators_filter_on_missing_and_optional_components() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let resident = ctx.ecs().new();
+    resident.clone().upsert_new_location(10);
+    let traveller = ctx.ecs().new();
+    traveller.clone().upsert_new_location(10);
+    traveller.clone().upsert_new_path(20);
+    ctx.ecs().new().upsert_new_path(30);
+
+    assert_eq!(
+        ctx.ecs()
+            .iter_location()
+            .without_path()
+            .map(|l| l.entity_id())
+            .collect::<Vec<_>>(),
+        vec![resident.entity_id()]
+    );
+    assert_eq!(
+        ctx.ecs()
+            .iter_location()
+            .with_optional_path()
+            .map(|(l, p)| (l.entity_id(), p.map(|p| p.destination_entity_id)))
+            .collect::<Vec<_>>(),
+        vec![
+            (resident.entity_id(), None),
+            (traveller.entity_id(), Some(20))
+        ]
+    );
+}
+
+#[test]
+fn deleting_an_entity_skips_hooks_and_leaves_no_rows() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().upsert_new_excess_path(2);
+    assert_eq!(
+        e.secondary_location().map(|l| l.location_entity_id),
+        Some(2)
+    );
+    e.delete_secondary_location();
+
+    e.delete();
+
+    assert_eq!(
+        (
+            ctx.ecs().iter_excess_path().count(),
+            ctx.ecs().iter_secondary_location().count(),
+        ),
+        (0, 0)
+    );
+}
+
+#[test]
+fn memory_tables_resolve_relations_on_delete() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let room = ctx.ecs().new();
+    let occupant = ctx.ecs().new();
+    let visitor = ctx.ecs().new();
+    occupant.clone().upsert_new_location(room.entity_id());
+    occupant
+        .clone()
+        .upsert_new_stats(1, vec![], "occupant".to_string());
+    visitor.clone().upsert_new_path(room.entity_id());
+    visitor
+        .clone()
+        .upsert_new_stats(2, vec![], "visitor".to_string());
+
+    room.delete();
+
+    // Cascading deletes the whole occupant, while nullifying only removes the
+    // visitor's path.
+    assert_eq!(
+        (occupant.location().is_some(), occupant.stats().is_some()),
+        (false, false)
+    );
+    assert_eq!(
+        (visitor.path().is_some(), visitor.stats().is_some()),
+        (false, true)
+    );
+    assert_eq!(ctx.ecs().iter_location().count(), 0);
+}
+
+#[test]
+fn memory_tables_record_changes_per_cursor() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().upsert_new_location(1);
+    e.clone().upsert_new_location(2);
+
+    let changes = |system| {
+        ctx.ecs()
+            .iter_location_changes(system)
+            .map(|(e, kind)| (e.entity_id(), kind))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(changes("first"), vec![(e.entity_id(), ChangeKind::Added)]);
+
+    ctx.ecs().advance_change_cursor("first");
+    e.delete_location();
+    assert_eq!(changes("first"), vec![(e.entity_id(), ChangeKind::Removed)]);
+}
+
+#[test]
+fn changes_are_kept_until_every_declared_system_has_read_them() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().upsert_new_location(1);
+
+    let changes = |system| {
+        ctx.ecs()
+            .iter_location_changes(system)
+            .map(|(e, kind)| (e.entity_id(), kind))
+            .collect::<Vec<_>>()
+    };
+    ctx.ecs().advance_change_cursor("first");
+    assert_eq!(changes("second"), vec![(e.entity_id(), ChangeKind::Added)]);
+
+    ctx.ecs().advance_change_cursor("second");
+    assert_eq!(
+        ecs::memory::rows::<ComponentChange>("component_changes").len(),
+        0
+    );
+}
+
+#[test]
+fn memory_tables_park_and_restore_entities() {
+    ecs::memory::reset();
+    let ctx = spacetimedb::ReducerContext::__dummy();
+    let e = ctx.ecs().new();
+    e.clone().upsert_new_stats(4, vec![1], "stats".to_string());
+
+    let parked = e.deactivate(7, None, 2, "parked".to_string());
+    assert_eq!(
+        (parked.deactivated_entity_id, e.stats().is_some()),
+        (Some(e.entity_id()), false)
+    );
+
+    let restored = ctx.ecs().reactivate(parked.id);
+    assert_eq!(restored.map(|r| r.entity_id()), Some(e.entity_id()));
+    assert_eq!(
+        ctx.ecs()
+            .iter_stats()
+            .map(|s| (s.stats.attack, s.stats.title))
+            .collect::<Vec<_>>(),
+        vec![(4, "stats".to_string())]
+    );
+    assert_eq!(ctx.ecs().reactivate(parked.id).map(|r| r.entity_id()), None);
+}
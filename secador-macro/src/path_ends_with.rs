@@ -9,11 +9,11 @@ where
     T: TryToMacro + 'a,
 {
     fn path_ends_with(&'a self, ending: &str) -> bool {
-        if let Some(mac) = TryToMacro::try_to_macro(self) {
-            if let Some(s) = mac.path.segments.last() {
-                return s.ident.to_string() == ending;
-            }
+        if let Some(mac) = TryToMacro::try_to_macro(self)
+            && let Some(s) = mac.path.segments.last()
+        {
+            return s.ident == ending;
         }
-        return false;
+        false
     }
 }
@@ -40,9 +40,9 @@ pub enum Substitution {
 
 impl Parse for Substitution {
     fn parse(input: ParseStream) -> Result<Self> {
-        if let Ok(_) = input.fork().parse::<ExplicitTypeMacro>() {
+        if input.fork().parse::<ExplicitTypeMacro>().is_ok() {
             Ok(Substitution::Type(input.parse::<ExplicitType>()?.0))
-        } else if let Ok(_) = input.fork().parse::<Expr>() {
+        } else if input.fork().parse::<Expr>().is_ok() {
             Ok(Substitution::Expr(input.parse()?))
         } else {
             Ok(Substitution::Type(input.parse()?))
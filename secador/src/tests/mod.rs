@@ -70,6 +70,7 @@ mod test_multi_line {
             }
 
             impl Test {
+                #[allow(clippy::too_many_arguments)]
                 pub fn new_test(__seca: __2, __field: __Type, __other_field: __OtherType) -> Test {
                     Test {
                         __seca: __2,
@@ -1,5 +1,7 @@
 use spacetimedb::{table, ReducerContext, SpacetimeType};
 
+use crate::store::StoredRow;
+
 pub type ActionId = u32;
 
 #[derive(Debug, Clone, SpacetimeType)]
@@ -11,6 +13,48 @@ pub enum ActionType {
     Equip,
 }
 
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum Intensity {
+    Normal,
+    Powerful,
+    Extreme,
+}
+
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum WeightType {
+    Heavy,
+    Neutral,
+    Light,
+}
+
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum SpeedType {
+    Slow,
+    Neutral,
+    Fast,
+}
+
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum ArmamentType {
+    Blade,
+    Sword,
+    Club,
+    Staff,
+    Fist,
+    Claw,
+    Teeth,
+    Stick,
+    Spout,
+}
+
+/// Presentation of an attack action, used by clients to pick animations and verbs.
+#[derive(Debug, Clone, SpacetimeType, PartialEq, Eq)]
+pub struct AttackRenderer {
+    pub weight_type: WeightType,
+    pub speed_type: SpeedType,
+    pub armament_type: ArmamentType,
+}
+
 #[table(accessor = actions, public)]
 #[derive(Debug, Clone)]
 pub struct Action {
@@ -19,6 +63,8 @@ pub struct Action {
     #[unique]
     pub name: String,
     pub action_type: ActionType,
+    /// `None` for actions which are not attacks.
+    pub renderer: Option<AttackRenderer>,
 }
 
 #[derive(Debug, Clone, SpacetimeType)]
@@ -26,10 +72,25 @@ pub enum Buff {
     Guard(i32),
 }
 
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Cold,
+    Lightning,
+    Poison,
+}
+
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct Damage {
+    pub amount: i32,
+    pub damage_type: DamageType,
+}
+
 #[derive(Debug, Clone, SpacetimeType)]
 pub enum ActionEffect {
     Buff(Buff),
-    Attack(i32),
+    Attack(Damage),
     Heal(i32),
     Rest,
     Move,
@@ -37,6 +98,8 @@ pub enum ActionEffect {
     Drop,
     Equip,
     Unequip,
+    AddTrait(u32),
+    RemoveTrait(u32),
 }
 
 #[table(
@@ -47,10 +110,14 @@ pub enum ActionEffect {
 #[derive(Debug, Clone)]
 pub struct ActionStep {
     #[primary_key]
-    id: u64,
-    action_id: ActionId,
-    sequence_index: i32,
-    action_effect: ActionEffect,
+    pub id: u64,
+    pub action_id: ActionId,
+    pub sequence_index: i32,
+    pub action_effect: ActionEffect,
+    pub intensity: Intensity,
+    /// Names the trait of an `AddTrait` or `RemoveTrait` effect in a pack,
+    /// replacing the effect's id when the pack is loaded.
+    pub trait_name: Option<String>,
 }
 
 pub struct ActionHandle<'a> {
@@ -63,13 +130,26 @@ impl<'a> ActionHandle<'a> {
         Self { ctx, action_id }
     }
 
-    pub fn effect(&self, sequence_index: i32) -> Option<ActionEffect> {
-        self.ctx
+    pub fn step(&self, sequence_index: i32) -> Option<ActionStep> {
+        // The in-memory tables have no composite index to filter by.
+        #[cfg(not(test))]
+        let mut steps = self
+            .ctx
             .db
             .action_steps()
             .action_sequence()
-            .filter((self.action_id, sequence_index))
-            .next()
-            .map(|a| a.action_effect)
+            .filter((self.action_id, sequence_index));
+        #[cfg(test)]
+        let mut steps = ActionStep::iter(self.ctx)
+            .filter(|s| s.action_id == self.action_id && s.sequence_index == sequence_index);
+        steps.next()
+    }
+
+    pub fn effect(&self, sequence_index: i32) -> Option<ActionEffect> {
+        self.step(sequence_index).map(|a| a.action_effect)
+    }
+
+    pub fn renderer(&self) -> Option<AttackRenderer> {
+        Action::find(self.ctx, &self.action_id).and_then(|a| a.renderer)
     }
 }
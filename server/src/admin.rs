@@ -0,0 +1,62 @@
+use spacetimedb::{table, Identity, ReducerContext, Table, Timestamp};
+
+/// Identities allowed to call privileged reducers.
+#[table(accessor = admins)]
+pub struct Admin {
+    #[primary_key]
+    pub identity: Identity,
+}
+
+/// Record of every privileged command that succeeded.
+#[table(accessor = admin_audit_logs)]
+pub struct AdminAuditLog {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub time: Timestamp,
+    pub identity: Identity,
+    pub command: String,
+}
+
+pub trait AdminGuard {
+    fn is_admin(&self, identity: Identity) -> bool;
+    /// Fails unless the caller is an admin.
+    fn require_admin(&self) -> Result<(), String>;
+    /// Fails unless the caller is the module itself, as for scheduled reducers.
+    fn require_scheduler(&self) -> Result<(), String>;
+    /// Records a privileged command issued by the caller.
+    fn audit(&self, command: String);
+}
+
+impl AdminGuard for ReducerContext {
+    fn is_admin(&self, identity: Identity) -> bool {
+        self.db.admins().identity().find(identity).is_some()
+    }
+
+    fn require_admin(&self) -> Result<(), String> {
+        if self.is_admin(self.sender()) {
+            Ok(())
+        } else {
+            log::warn!("Rejected privileged call from {}.", self.sender());
+            Err("Only admins may call this reducer.".to_string())
+        }
+    }
+
+    fn require_scheduler(&self) -> Result<(), String> {
+        if self.sender() == self.identity() {
+            Ok(())
+        } else {
+            Err("This reducer may only be called by the scheduler.".to_string())
+        }
+    }
+
+    fn audit(&self, command: String) {
+        log::info!("Admin {}: {}", self.sender(), command);
+        self.db.admin_audit_logs().insert(AdminAuditLog {
+            id: 0,
+            time: self.timestamp,
+            identity: self.sender(),
+            command,
+        });
+    }
+}
@@ -1,4 +1,8 @@
-use spacetimedb::{table, SpacetimeType};
+use std::cmp::Reverse;
+
+use spacetimedb::{table, Identity, ReducerContext, SpacetimeType, ViewContext};
+
+use crate::entity::*;
 
 #[derive(Debug, Clone, SpacetimeType)]
 pub enum AppearanceFeatureType {
@@ -6,9 +10,51 @@ pub enum AppearanceFeatureType {
     Adjective,
 }
 
+/// The grammar of a language for player-facing text, looked up by its locale
+/// code, such as "ja". Players without a loaded locale get the default, the
+/// English grammar of the canonical `appearance_features`.
+#[table(accessor = locales)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    #[primary_key]
+    pub id: u32,
+    #[unique]
+    pub code: String,
+    /// Noun of a description without a noun feature.
+    pub fallback_noun: String,
+    pub word_separator: String,
+    /// Article prefixed to descriptions, if the language has one.
+    pub article: Option<String>,
+    /// Replaces `article` before a word starting with a vowel.
+    pub vowel_article: Option<String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale {
+            id: 0,
+            code: "en".to_string(),
+            fallback_noun: "thing".to_string(),
+            word_separator: " ".to_string(),
+            article: Some("a".to_string()),
+            vowel_article: Some("an".to_string()),
+        }
+    }
+}
+
+impl Locale {
+    fn article(&self, first_word: &str) -> Option<&str> {
+        match first_word.chars().next().map(|c| c.to_ascii_lowercase()) {
+            Some('a' | 'e' | 'i' | 'o' | 'u') => {
+                self.vowel_article.as_deref().or(self.article.as_deref())
+            }
+            _ => self.article.as_deref(),
+        }
+    }
+}
+
 // appearance_features represents the canonical features used to assign IDs to StatBlocks.
 #[table(accessor = appearance_features)]
-#[table(accessor = en_appearance_features)]
 #[derive(Debug, Clone)]
 pub struct AppearanceFeature {
     #[primary_key]
@@ -17,3 +63,185 @@ pub struct AppearanceFeature {
     pub appearance_feature_type: AppearanceFeatureType,
     pub priority: i32,
 }
+
+/// The translation of the canonical feature `index` into the locale with the
+/// code `locale`. Every locale shares this table.
+#[table(
+  accessor = localized_appearance_features,
+  index(accessor = locale_feature, btree(columns = [locale, index]))
+)]
+#[derive(Debug, Clone)]
+pub struct LocalizedAppearanceFeature {
+    #[primary_key]
+    pub id: u64,
+    pub locale: String,
+    pub index: u32,
+    pub text: String,
+    pub appearance_feature_type: AppearanceFeatureType,
+    pub priority: i32,
+}
+
+impl From<LocalizedAppearanceFeature> for AppearanceFeature {
+    fn from(f: LocalizedAppearanceFeature) -> Self {
+        AppearanceFeature {
+            index: f.index,
+            text: f.text,
+            appearance_feature_type: f.appearance_feature_type,
+            priority: f.priority,
+        }
+    }
+}
+
+/// Composes a description such as "a small green goblin": adjectives by
+/// descending priority, then the noun with the highest priority, joined and
+/// prefixed with an article as the locale requires.
+pub fn describe(locale: &Locale, features: &[AppearanceFeature]) -> String {
+    let mut features: Vec<&AppearanceFeature> = features.iter().collect();
+    features.sort_by_key(|f| (Reverse(f.priority), f.index));
+
+    let mut words: Vec<&str> = features
+        .iter()
+        .filter(|f| matches!(f.appearance_feature_type, AppearanceFeatureType::Adjective))
+        .map(|f| f.text.as_str())
+        .collect();
+    words.push(
+        features
+            .iter()
+            .find(|f| matches!(f.appearance_feature_type, AppearanceFeatureType::Noun))
+            .map_or(locale.fallback_noun.as_str(), |f| f.text.as_str()),
+    );
+
+    let article = locale.article(words.first().copied().unwrap_or_default());
+    article
+        .into_iter()
+        .chain(words)
+        .collect::<Vec<_>>()
+        .join(&locale.word_separator)
+}
+
+pub trait AppearanceExtension {
+    fn player_locale(&self, identity: Identity) -> Locale;
+    fn localized_appearance_feature(&self, locale: &str, index: u32) -> Option<AppearanceFeature>;
+    fn describe_entity(&self, entity_id: u64, locale: &Locale) -> Option<String>;
+}
+
+/// Implements `AppearanceExtension` for contexts whose `db` exposes the tables,
+/// so reducers and read-only views share the lookups.
+macro_rules! impl_appearance_extension {
+    ($($context:ty),*) => {$(
+        impl AppearanceExtension for $context {
+            fn player_locale(&self, identity: Identity) -> Locale {
+                self.db
+                    .player_controller_components()
+                    .identity()
+                    .find(identity)
+                    .and_then(|p| self.db.locale_components().entity_id().find(p.entity_id))
+                    .and_then(|l| self.db.locales().code().find(&l.locale))
+                    .unwrap_or_default()
+            }
+
+            fn localized_appearance_feature(
+                &self,
+                locale: &str,
+                index: u32,
+            ) -> Option<AppearanceFeature> {
+                self.db
+                    .localized_appearance_features()
+                    .locale_feature()
+                    .filter((locale, index))
+                    .next()
+                    .map(AppearanceFeature::from)
+                    .or_else(|| self.db.appearance_features().index().find(index))
+            }
+
+            /// Describes an entity from its appearance features, or `None` if it has none.
+            fn describe_entity(&self, entity_id: u64, locale: &Locale) -> Option<String> {
+                let indexes = self
+                    .db
+                    .appearance_features_components()
+                    .entity_id()
+                    .find(entity_id)?
+                    .appearance_feature_indexes;
+                let features: Vec<AppearanceFeature> = indexes
+                    .into_iter()
+                    .filter_map(|i| self.localized_appearance_feature(&locale.code, i))
+                    .collect();
+                Some(describe(locale, &features))
+            }
+        }
+    )*};
+}
+
+impl_appearance_extension!(ReducerContext, ViewContext);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(
+        index: u32,
+        text: &str,
+        appearance_feature_type: AppearanceFeatureType,
+        priority: i32,
+    ) -> AppearanceFeature {
+        AppearanceFeature {
+            index,
+            text: text.to_string(),
+            appearance_feature_type,
+            priority,
+        }
+    }
+
+    #[test]
+    fn describe_orders_adjectives_by_priority_before_the_noun() {
+        let features = vec![
+            feature(0, "goblin", AppearanceFeatureType::Noun, 0),
+            feature(1, "green", AppearanceFeatureType::Adjective, 1),
+            feature(2, "small", AppearanceFeatureType::Adjective, 2),
+        ];
+
+        assert_eq!(
+            describe(&Locale::default(), &features),
+            "a small green goblin"
+        );
+    }
+
+    #[test]
+    fn describe_uses_the_highest_priority_noun_and_an_article_for_vowels() {
+        let features = vec![
+            feature(0, "creature", AppearanceFeatureType::Noun, 0),
+            feature(1, "ogre", AppearanceFeatureType::Noun, 5),
+        ];
+
+        assert_eq!(describe(&Locale::default(), &features), "an ogre");
+    }
+
+    #[test]
+    fn describe_falls_back_to_a_generic_noun() {
+        let features = vec![feature(0, "old", AppearanceFeatureType::Adjective, 0)];
+
+        assert_eq!(describe(&Locale::default(), &features), "an old thing");
+        assert_eq!(describe(&Locale::default(), &[]), "a thing");
+    }
+
+    #[test]
+    fn describe_follows_the_locale_grammar() {
+        let features = vec![
+            feature(0, "ゴブリン", AppearanceFeatureType::Noun, 0),
+            feature(1, "緑の", AppearanceFeatureType::Adjective, 1),
+            feature(2, "小さな", AppearanceFeatureType::Adjective, 2),
+        ];
+
+        let ja = Locale {
+            id: 1,
+            code: "ja".to_string(),
+            fallback_noun: "もの".to_string(),
+            word_separator: String::new(),
+            article: None,
+            vowel_article: None,
+        };
+
+        assert_eq!(describe(&ja, &features), "小さな緑のゴブリン");
+        assert_eq!(describe(&ja, &[]), "もの");
+    }
+}
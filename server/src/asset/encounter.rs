@@ -1,49 +1,56 @@
 use ecs::Ecs;
-use spacetimedb::table;
+use spacetimedb::{table, TimeDuration};
 
 use crate::{
-    entity::{
-        EntityBlob, EntityHandle, InstantiateEntityBlob, NewEntityHandle, WithEntityHandle,
-        __location__Option,
-    },
+    entity::{__location__Option, EntityBlob, EntityHandle, NewEntityHandle, WithEntityHandle},
     entity_handle_extension::InstantiateEntityBlobExtension,
+    store::StoredRow,
 };
 
 #[table(accessor = encounter_blobs)]
+#[derive(Debug, Clone)]
 pub struct EncounterBlob {
     #[primary_key]
     pub id: u32,
+    #[unique]
+    pub name: String,
     pub blob: EntityBlob,
 }
 
 #[table(accessor = encounters)]
+#[derive(Debug, Clone)]
 pub struct Encounter {
     #[primary_key]
     pub id: u32,
     pub categoric_blob_id: u32,
     pub blob_ids: Vec<u32>,
+    /// Names the categoric blob in a pack, replacing `categoric_blob_id` when
+    /// the pack is loaded.
+    pub categoric_blob_name: Option<String>,
+    /// Names of blobs added to `blob_ids` when the pack is loaded.
+    pub blob_names: Vec<String>,
+    /// Delay before a cleared room is populated again, or `None` for an
+    /// encounter that stays cleared.
+    pub respawn_delay: Option<TimeDuration>,
 }
 
 impl Encounter {
     pub fn populate(&self, room: &EntityHandle) {
         let ecs: Ecs = room.ecs();
-        // TODO Make it easier to grab a default empty EntityBlob.
-        let categoric_blob =
-            if let Some(c) = ecs.db.encounter_blobs().id().find(self.categoric_blob_id) {
-                c.blob
-            } else {
-                return;
-            };
+        let categoric_blob = if let Some(c) = EncounterBlob::find(&ecs, &self.categoric_blob_id) {
+            c.blob
+        } else {
+            return;
+        };
         log::debug!(
             "Grabbed categoric_blob {} {:?}",
             self.categoric_blob_id,
             categoric_blob
         );
         for id in &self.blob_ids {
-            if let Some(e) = ecs.db.encounter_blobs().id().find(id) {
+            if let Some(e) = EncounterBlob::find(&ecs, id) {
                 ecs.new()
-                    .instantiate_blob(categoric_blob.clone())
-                    .instantiate_blob_dirty(e.blob)
+                    .instantiate_blob_dirty(categoric_blob.clone().overlay(e.blob))
                     .upsert_new_location(room.entity_id());
             }
         }
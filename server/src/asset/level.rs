@@ -0,0 +1,68 @@
+use spacetimedb::table;
+
+#[table(accessor = levels)]
+#[derive(Debug, Clone)]
+pub struct Level {
+    #[primary_key]
+    pub level: u32,
+    /// Total experience required to reach this level.
+    pub xp: u32,
+    /// Traits granted when this level is reached.
+    pub trait_ids: Vec<u32>,
+    /// Names of traits added to `trait_ids` when the pack is loaded.
+    pub trait_names: Vec<String>,
+}
+
+/// Returns the levels reached with `xp` above `current_level`, in ascending order.
+pub fn levels_gained<'a>(
+    levels: impl IntoIterator<Item = &'a Level>,
+    current_level: u32,
+    xp: u32,
+) -> Vec<&'a Level> {
+    let mut gained: Vec<&Level> = levels
+        .into_iter()
+        .filter(|l| l.level > current_level && l.xp <= xp)
+        .collect();
+    gained.sort_by_key(|l| l.level);
+    gained
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{levels_gained, Level};
+
+    fn level(level: u32, xp: u32) -> Level {
+        Level {
+            level,
+            xp,
+            trait_ids: vec![level],
+            trait_names: vec![],
+        }
+    }
+
+    #[test]
+    fn levels_gained_returns_reached_levels_in_order() {
+        let levels = vec![level(3, 300), level(1, 0), level(2, 100), level(4, 1000)];
+
+        let gained: Vec<u32> = levels_gained(&levels, 1, 350)
+            .iter()
+            .map(|l| l.level)
+            .collect();
+
+        assert_eq!(gained, vec![2, 3]);
+    }
+
+    #[test]
+    fn levels_gained_is_empty_below_next_threshold() {
+        let levels = vec![level(1, 0), level(2, 100)];
+
+        assert!(levels_gained(&levels, 1, 99).is_empty());
+    }
+
+    #[test]
+    fn levels_gained_skips_levels_already_reached() {
+        let levels = vec![level(1, 0), level(2, 100)];
+
+        assert!(levels_gained(&levels, 2, 5000).is_empty());
+    }
+}
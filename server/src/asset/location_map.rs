@@ -1,12 +1,12 @@
 use crate::{
     asset::{
-        encounter::encounters,
-        location_map_theme::location_map_themes,
+        location_map_theme::LocationMapTheme,
         rng_range::RngRange,
         weighted_sampler::{WeightedSample, WeightedSampler},
     },
     ecs_extension::EcsExtension,
     entity::*,
+    store::StoredRow,
 };
 use ecs::Ecs;
 use spacetimedb::{
@@ -22,8 +22,8 @@ pub enum Layout {
 
 #[derive(Debug, Clone, SpacetimeType)]
 pub struct EncounterIdSample {
-    weight: u8,
-    id: u32,
+    pub weight: u8,
+    pub id: u32,
 }
 
 impl WeightedSample for EncounterIdSample {
@@ -38,7 +38,7 @@ impl WeightedSample for EncounterIdSample {
 
 #[derive(Debug, Clone, SpacetimeType)]
 pub struct EncounterIdsSampler {
-    selections: Vec<EncounterIdSample>,
+    pub selections: Vec<EncounterIdSample>,
 }
 
 impl WeightedSampler for EncounterIdsSampler {
@@ -50,11 +50,14 @@ impl WeightedSampler for EncounterIdsSampler {
 }
 
 #[table(accessor = location_maps)]
+#[derive(Debug, Clone)]
 pub struct LocationMap {
     #[primary_key]
     pub id: u32,
     pub name: String,
     pub theme_id: u32,
+    /// Names the theme in a pack, replacing `theme_id` when the pack is loaded.
+    pub theme_name: Option<String>,
     pub layout: Layout,
     pub rng_seed: Option<u64>,
     pub extra_room_count: u8,
@@ -66,6 +69,7 @@ pub struct LocationMap {
 }
 
 #[table(accessor = location_map_connections)]
+#[derive(Debug, Clone)]
 pub struct LocationMapConnection {
     #[primary_key]
     pub id: u32,
@@ -74,8 +78,9 @@ pub struct LocationMapConnection {
 }
 
 pub struct MapGenerationResult {
+    /// `None` when the map could not be generated at all.
+    pub location_map_entity_id: Option<u64>,
     pub main_room_ids: Vec<u64>,
-    pub extra_room_ids: Vec<u64>,
 }
 impl LocationMap {
     pub fn generate_entities(&self, ecs: Ecs) -> MapGenerationResult {
@@ -89,16 +94,16 @@ impl LocationMap {
         StdRng::seed_from_u64(self.rng_seed.unwrap_or_default())
     }
     fn generate_path_layout(&self, ecs: Ecs) -> MapGenerationResult {
-        let theme = if let Some(theme) = ecs.db.location_map_themes().id().find(self.theme_id) {
+        let theme = if let Some(theme) = LocationMapTheme::find(&ecs, &self.theme_id) {
             theme
         } else {
             return MapGenerationResult {
+                location_map_entity_id: None,
                 main_room_ids: vec![],
-                extra_room_ids: vec![],
             };
         };
 
-        let location_map_entity = ecs.new();
+        let location_map_entity = ecs.new().upsert_new_location_map_source(self.id);
         let location_map_entity_id = location_map_entity.entity_id();
 
         let mut rng = self.rng();
@@ -149,8 +154,7 @@ impl LocationMap {
         // (This is intra-map only; it is unrelated to cross-map connections.)
         if main_room_count >= 3 {
             for _ in 0..self.loop_count {
-                let a_index: usize =
-                    rng.get_range::<u32, usize>(0, (main_room_count - 2) as u32);
+                let a_index: usize = rng.get_range::<u32, usize>(0, (main_room_count - 2) as u32);
                 if let Some(p) = theme.paths_selector.sample(&mut rng) {
                     let a = room_handles[a_index].entity_id();
                     let b = room_handles[a_index + 2].entity_id();
@@ -160,17 +164,16 @@ impl LocationMap {
             }
         }
 
-        // TODO Move encounter spawning to a system responding to player movement.
+        // Encounters are only chosen here. Rooms are shuffled before taking
+        // `encounter_count` of them so encounters are not always in the first
+        // rooms; `encounter_system` populates a room when a player enters it.
         let encounter_count: usize =
             rng.get_range(self.min_encounter_count, self.max_encounter_count);
-        let mut encounter_room_handles: Vec<_> =
-            room_handles.iter().take(encounter_count).collect();
+        let mut encounter_room_handles: Vec<_> = room_handles.iter().collect();
         encounter_room_handles.shuffle(&mut rng);
-        for r in encounter_room_handles {
+        for r in encounter_room_handles.into_iter().take(encounter_count) {
             if let Some(encounter_id) = self.encounter_ids_sampler.sample(&mut rng) {
-                if let Some(encounter) = ecs.db.encounters().id().find(*encounter_id) {
-                    encounter.populate(&r);
-                }
+                r.insert_new_pending_encounter(*encounter_id);
             }
         }
 
@@ -180,14 +183,11 @@ impl LocationMap {
         }
 
         MapGenerationResult {
+            location_map_entity_id: Some(location_map_entity_id),
             main_room_ids: room_handles[..main_room_count]
                 .iter()
                 .map(|h| h.entity_id())
                 .collect(),
-            extra_room_ids: room_handles[main_room_count..]
-                .iter()
-                .map(|h| h.entity_id())
-                .collect(),
         }
     }
 }
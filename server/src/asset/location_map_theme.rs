@@ -9,8 +9,8 @@ use spacetimedb::{rand::rngs::StdRng, table, SpacetimeType};
 
 #[derive(Debug, Clone, SpacetimeType)]
 pub struct EntityBlobSample {
-    weight: u8,
-    blob: EntityBlob,
+    pub weight: u8,
+    pub blob: EntityBlob,
 }
 
 impl WeightedSample for EntityBlobSample {
@@ -25,7 +25,7 @@ impl WeightedSample for EntityBlobSample {
 
 #[derive(Debug, Clone, SpacetimeType)]
 pub struct EntityBlobsSampler {
-    selections: Vec<EntityBlobSample>,
+    pub selections: Vec<EntityBlobSample>,
 }
 
 impl WeightedSampler for EntityBlobsSampler {
@@ -37,9 +37,12 @@ impl WeightedSampler for EntityBlobsSampler {
 }
 
 #[table(accessor = location_map_themes)]
+#[derive(Debug, Clone)]
 pub struct LocationMapTheme {
     #[primary_key]
     pub id: u32,
+    #[unique]
+    pub name: String,
     pub decorations_selector: EntityBlobsSampler,
     pub min_decoration_count: u8,
     pub max_decoration_count: u8,
@@ -1,28 +1,44 @@
+use std::collections::{HashMap, HashSet};
+
 use ecs::WithEcs;
-use spacetimedb::{reducer, table, ReducerContext, SpacetimeType, Table};
+use spacetimedb::{
+    reducer, spacetimedb_lib::bsatn, table, ReducerContext, Serialize, SpacetimeType, Table,
+};
 
 use crate::{
     action::{action_steps, actions, Action, ActionStep},
-    appearance::{appearance_features, AppearanceFeature},
+    admin::AdminGuard,
+    appearance::{
+        appearance_features, locales, localized_appearance_features, AppearanceFeature, Locale,
+        LocalizedAppearanceFeature,
+    },
     asset::{
         baseline::{baselines, Baseline},
         encounter::{encounter_blobs, encounters, Encounter, EncounterBlob},
+        level::{levels, Level},
         location_map::{
             location_map_connections, location_maps, LocationMap, LocationMapConnection,
         },
         location_map_theme::{location_map_themes, LocationMapTheme},
         r#trait::{traits, Trait},
+        stat_block::StatBlock,
     },
-    entity::{EntityBlob, InstantiateEntityBlob, NewEntityHandle},
+    entity::*,
+    entity_handle_extension::EntityHandleExtension,
+    prominence::{prominence_rules, ProminenceRule},
+    store::StoredRow,
 };
 
 pub mod baseline;
 pub mod encounter;
+pub mod level;
 pub mod location_map;
 pub mod location_map_theme;
+pub mod reference;
 pub mod rng_range;
 pub mod stat_block;
 pub mod r#trait;
+pub mod validation;
 pub mod weighted_sampler;
 
 #[derive(Debug, Clone, SpacetimeType, PartialEq, Eq, Hash)]
@@ -31,45 +47,72 @@ pub enum SpecialEntityBlobKey {
 }
 
 #[table(accessor = special_entity_blobs)]
-struct SpecialEntityBlob {
+#[derive(Debug, Clone)]
+pub struct SpecialEntityBlob {
+    #[primary_key]
+    pub key: SpecialEntityBlobKey,
+    pub blob: EntityBlob,
+}
+
+const ASSET_PACK_VERSION_ID: u8 = 0;
+
+/// Version of the loaded asset pack. `update_assets` only accepts newer packs.
+#[table(accessor = asset_pack_versions, public)]
+pub struct AssetPackVersion {
     #[primary_key]
-    key: SpecialEntityBlobKey,
-    blob: EntityBlob,
+    id: u8,
+    pub version: u32,
 }
 
 secador::secador!(
-    (assets, Asset),
+    (assets, Asset, key),
     [
-        (actions, Action),
-        (action_steps, ActionStep),
-        (appearance_features, AppearanceFeature),
-        (baselines, Baseline),
-        (traits, Trait),
-        (encounter_blobs, EncounterBlob),
-        (encounters, Encounter),
-        (location_map_themes, LocationMapTheme),
-        (location_maps, LocationMap),
-        (location_map_connections, LocationMapConnection),
+        (actions, Action, id),
+        (action_steps, ActionStep, id),
+        (appearance_features, AppearanceFeature, index),
+        (locales, Locale, id),
+        (
+            localized_appearance_features,
+            LocalizedAppearanceFeature,
+            id
+        ),
+        (baselines, Baseline, id),
+        (traits, Trait, id),
+        (levels, Level, level),
+        (encounter_blobs, EncounterBlob, id),
+        (encounters, Encounter, id),
+        (location_map_themes, LocationMapTheme, id),
+        (location_maps, LocationMap, id),
+        (location_map_connections, LocationMapConnection, id),
+        (prominence_rules, ProminenceRule, condition),
     ],
     {
         #[derive(SpacetimeType)]
-        struct AssetPack {
+        pub struct AssetPack {
+            pub version: u32,
+
             __seca: __1,
-            __assets: Vec<__Asset>,
+            pub __assets: Vec<__Asset>,
 
-            instantiate_entity_blobs: Vec<EntityBlob>,
+            pub instantiate_entity_blobs: Vec<EntityBlob>,
 
-            new_player_blob: EntityBlob,
+            pub new_player_blob: EntityBlob,
         }
         #[reducer]
-        fn push_assets(ctx: &ReducerContext, asset_pack: AssetPack) -> Result<(), String> {
+        fn push_assets(ctx: &ReducerContext, mut asset_pack: AssetPack) -> Result<(), String> {
             log::debug!("Loading asset pack from {}.", ctx.sender());
+            ctx.require_admin()?;
 
             if ctx.get_new_player_blob().is_some() {
                 log::debug!("Assets are already populated. Skipped loading.");
                 return Ok(());
             }
 
+            let errors = load_errors(ctx, &mut asset_pack);
+            if !errors.is_empty() {
+                return Err(validation::report(&errors));
+            }
+
             seca!(1);
             for asset in asset_pack.__assets {
                 ctx.db.__assets().insert(asset);
@@ -84,21 +127,209 @@ secador::secador!(
                 blob: asset_pack.new_player_blob,
             });
 
+            ctx.db.asset_pack_versions().insert(AssetPackVersion {
+                id: ASSET_PACK_VERSION_ID,
+                version: asset_pack.version,
+            });
+
+            Ok(())
+        }
+
+        /// Replaces the loaded assets with a newer pack, replacing changed rows and
+        /// deleting rows the pack no longer contains. `instantiate_entity_blobs`
+        /// are only instantiated by `push_assets` and are ignored here.
+        #[reducer]
+        fn update_assets(ctx: &ReducerContext, mut asset_pack: AssetPack) -> Result<(), String> {
+            log::debug!(
+                "Updating assets to version {} from {}.",
+                asset_pack.version,
+                ctx.sender()
+            );
+            ctx.require_admin()?;
+
+            let current_version = ctx
+                .db
+                .asset_pack_versions()
+                .id()
+                .find(ASSET_PACK_VERSION_ID)
+                .ok_or("Assets are not loaded yet. Use push_assets first.")?;
+            if asset_pack.version <= current_version.version {
+                return Err(format!(
+                    "Asset pack version {} is not newer than loaded version {}.",
+                    asset_pack.version, current_version.version
+                ));
+            }
+
+            let errors = load_errors(ctx, &mut asset_pack);
+            if !errors.is_empty() {
+                return Err(validation::report(&errors));
+            }
+
+            let changed_baseline_ids = changed_stat_block_ids(
+                ctx.db.baselines().iter().map(|b| (b.id, b.stat_block)),
+                asset_pack.baselines.iter().map(|b| (b.id, &b.stat_block)),
+            );
+            let changed_trait_ids = changed_stat_block_ids(
+                ctx.db.traits().iter().map(|t| (t.id, t.stat_block)),
+                asset_pack.traits.iter().map(|t| (t.id, &t.stat_block)),
+            );
+            let prominence_rules_changed = {
+                let loaded: HashMap<_, _> = ctx
+                    .db
+                    .prominence_rules()
+                    .iter()
+                    .map(|r| (r.condition, r.weight))
+                    .collect();
+                let new: HashMap<_, _> = asset_pack
+                    .prominence_rules
+                    .iter()
+                    .map(|r| (r.condition, r.weight))
+                    .collect();
+                loaded != new
+            };
+
+            // Changed rows are deleted before any are inserted, so renaming or
+            // swapping a `#[unique] name` never conflicts with a row not yet updated.
+            seca!(1);
+            {
+                let mut new_rows = HashMap::new();
+                for a in &asset_pack.__assets {
+                    new_rows.insert(a.__key, encode_row(a)?);
+                }
+                // Collected first, so the table is not modified while its
+                // iterator is live.
+                let mut stale_keys = vec![];
+                for a in ctx.db.__assets().iter() {
+                    if new_rows.get(&a.__key) != Some(&encode_row(&a)?) {
+                        stale_keys.push(a.__key);
+                    }
+                }
+                for key in stale_keys {
+                    ctx.db.__assets().__key().delete(key);
+                }
+                for a in asset_pack.__assets {
+                    if ctx.db.__assets().__key().find(a.__key).is_none() {
+                        ctx.db.__assets().insert(a);
+                    }
+                }
+            }
+
+            ctx.db
+                .special_entity_blobs()
+                .key()
+                .update(SpecialEntityBlob {
+                    key: SpecialEntityBlobKey::NewPlayer,
+                    blob: asset_pack.new_player_blob,
+                });
+
+            ctx.db.asset_pack_versions().id().update(AssetPackVersion {
+                id: ASSET_PACK_VERSION_ID,
+                version: asset_pack.version,
+            });
+
+            let ecs = ctx.ecs();
+            for e in ecs.iter_baseline() {
+                if changed_baseline_ids.contains(&e.baseline().baseline_id) {
+                    e.upsert_new_total_stat_block_dirty_flag();
+                }
+            }
+            for e in ecs.iter_traits() {
+                if e.traits()
+                    .trait_ids
+                    .iter()
+                    .any(|id| changed_trait_ids.contains(id))
+                {
+                    e.upsert_new_traits_stat_block_dirty_flag();
+                }
+            }
+            // Every entity with a prominence fact has a prominence, since the
+            // system computes it whenever a fact changes.
+            if prominence_rules_changed {
+                let prominent: Vec<_> = ecs.iter_entity_prominence().collect();
+                for e in prominent {
+                    e.generate_prominence();
+                }
+            }
+
             Ok(())
         }
     }
 );
 
+/// Resolves the names the pack references, by the pack's own names first and
+/// then the loaded ones, and validates the result.
+pub fn load_errors(
+    ctx: &ReducerContext,
+    asset_pack: &mut AssetPack,
+) -> Vec<validation::AssetValidationError> {
+    let names = reference::AssetNames::of_pack(asset_pack).with_loaded(ctx);
+    let errors = reference::resolve_names(asset_pack, &names);
+    if !errors.is_empty() {
+        return errors;
+    }
+    validation::validate(asset_pack)
+}
+
+/// Encodes an asset row, so rows can be compared without `PartialEq`.
+fn encode_row(row: &impl Serialize) -> Result<Vec<u8>, String> {
+    bsatn::to_vec(row).map_err(|err| format!("Cannot encode asset row. {}", err))
+}
+
+/// Ids whose stat block differs between the loaded and the new assets, including
+/// ids present on only one side.
+fn changed_stat_block_ids<'a>(
+    loaded: impl Iterator<Item = (u32, StatBlock)>,
+    new: impl Iterator<Item = (u32, &'a StatBlock)>,
+) -> HashSet<u32> {
+    let loaded: HashMap<u32, StatBlock> = loaded.collect();
+    let mut changed: HashSet<u32> = HashSet::new();
+    let mut seen: HashSet<u32> = HashSet::new();
+    for (id, stat_block) in new {
+        seen.insert(id);
+        if loaded.get(&id) != Some(stat_block) {
+            changed.insert(id);
+        }
+    }
+    changed.extend(loaded.keys().filter(|id| !seen.contains(*id)));
+    changed
+}
+
 pub trait ReducerContextExtension {
     fn get_new_player_blob(&self) -> Option<EntityBlob>;
 }
 
 impl ReducerContextExtension for ReducerContext {
     fn get_new_player_blob(&self) -> Option<EntityBlob> {
-        self.db
-            .special_entity_blobs()
-            .key()
-            .find(SpecialEntityBlobKey::NewPlayer)
-            .map(|b| b.blob)
+        SpecialEntityBlob::find(self, &SpecialEntityBlobKey::NewPlayer).map(|b| b.blob)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_stat_block_ids_covers_edits_additions_and_removals() {
+        let unchanged = StatBlock {
+            attack: 1,
+            ..Default::default()
+        };
+        let edited = StatBlock {
+            attack: 2,
+            ..Default::default()
+        };
+        let loaded = vec![
+            (1, unchanged.clone()),
+            (2, unchanged.clone()),
+            (3, unchanged.clone()),
+        ];
+        let new = [(1, &unchanged), (2, &edited), (4, &unchanged)];
+
+        let mut changed: Vec<u32> = changed_stat_block_ids(loaded.into_iter(), new.into_iter())
+            .into_iter()
+            .collect();
+        changed.sort();
+
+        assert_eq!(changed, vec![2, 3, 4]);
     }
 }
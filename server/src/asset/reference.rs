@@ -0,0 +1,469 @@
+use std::{collections::HashMap, fmt};
+
+use spacetimedb::{ReducerContext, SpacetimeType};
+
+use crate::{
+    action::{Action, ActionEffect},
+    asset::{
+        baseline::Baseline, encounter::EncounterBlob, location_map_theme::LocationMapTheme,
+        r#trait::Trait, validation::AssetValidationError, AssetPack,
+    },
+    entity::{ActionsComponent, BaselineComponent, EntityBlob, TraitsComponent},
+    store::StoredRow,
+};
+
+/// Names for a blob's `baseline`, `traits` and `actions` components, whose
+/// fields only hold ids. Loading a pack adds the named ids to the components
+/// and clears this field.
+#[derive(Debug, Clone, SpacetimeType, PartialEq, Default)]
+pub struct BlobNames {
+    pub baseline: Option<String>,
+    pub traits: Vec<String>,
+    pub actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    Action,
+    AppearanceFeature,
+    Baseline,
+    Trait,
+    EncounterBlob,
+    Encounter,
+    LocationMapTheme,
+    LocationMap,
+}
+
+impl fmt::Display for AssetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AssetKind::Action => "action",
+            AssetKind::AppearanceFeature => "appearance feature",
+            AssetKind::Baseline => "baseline",
+            AssetKind::Trait => "trait",
+            AssetKind::EncounterBlob => "encounter blob",
+            AssetKind::Encounter => "encounter",
+            AssetKind::LocationMapTheme => "location map theme",
+            AssetKind::LocationMap => "location map",
+        })
+    }
+}
+
+/// Asset ids keyed by name. Loading a pack and the offline asset compiler both
+/// resolve names through it.
+#[derive(Debug, Default)]
+pub struct AssetNames {
+    ids: HashMap<AssetKind, HashMap<String, u32>>,
+}
+
+impl AssetNames {
+    /// Names the pack's actions, baselines, traits, encounter blobs and location
+    /// map themes.
+    pub fn of_pack(asset_pack: &AssetPack) -> Self {
+        let mut names = AssetNames::default();
+        for a in &asset_pack.actions {
+            names.insert(AssetKind::Action, &a.name, a.id);
+        }
+        for b in &asset_pack.baselines {
+            names.insert(AssetKind::Baseline, &b.name, b.id);
+        }
+        for t in &asset_pack.traits {
+            names.insert(AssetKind::Trait, &t.name, t.id);
+        }
+        for b in &asset_pack.encounter_blobs {
+            names.insert(AssetKind::EncounterBlob, &b.name, b.id);
+        }
+        for t in &asset_pack.location_map_themes {
+            names.insert(AssetKind::LocationMapTheme, &t.name, t.id);
+        }
+        names
+    }
+
+    /// Adds the loaded assets' names the pack does not use, so a name keeps
+    /// resolving during the update that renames its asset.
+    pub fn with_loaded(mut self, ctx: &ReducerContext) -> Self {
+        self.fallback(AssetKind::Action, Action::iter(ctx).map(|a| (a.name, a.id)));
+        self.fallback(
+            AssetKind::Baseline,
+            Baseline::iter(ctx).map(|b| (b.name, b.id)),
+        );
+        self.fallback(AssetKind::Trait, Trait::iter(ctx).map(|t| (t.name, t.id)));
+        self.fallback(
+            AssetKind::EncounterBlob,
+            EncounterBlob::iter(ctx).map(|b| (b.name, b.id)),
+        );
+        self.fallback(
+            AssetKind::LocationMapTheme,
+            LocationMapTheme::iter(ctx).map(|t| (t.name, t.id)),
+        );
+        self
+    }
+
+    fn fallback(&mut self, kind: AssetKind, entries: impl Iterator<Item = (String, u32)>) {
+        let by_name = self.ids.entry(kind).or_default();
+        for (name, id) in entries {
+            by_name.entry(name).or_insert(id);
+        }
+    }
+
+    /// Names an asset, returning `false` if its kind already had that name.
+    pub fn insert(&mut self, kind: AssetKind, name: &str, id: u32) -> bool {
+        self.ids
+            .entry(kind)
+            .or_default()
+            .insert(name.to_string(), id)
+            .is_none()
+    }
+
+    pub fn resolve(&self, kind: AssetKind, name: &str) -> Option<u32> {
+        self.ids
+            .get(&kind)
+            .and_then(|by_name| by_name.get(name))
+            .copied()
+    }
+}
+
+struct Resolver<'a> {
+    names: &'a AssetNames,
+    errors: Vec<AssetValidationError>,
+}
+
+impl Resolver<'_> {
+    fn id(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: u64,
+        kind: AssetKind,
+        name: &str,
+    ) -> Option<u32> {
+        let id = self.names.resolve(kind, name);
+        if id.is_none() {
+            self.errors.push(AssetValidationError {
+                asset_kind,
+                asset_id,
+                message: format!("Unknown {} {:?}.", kind, name),
+            });
+        }
+        id
+    }
+
+    /// Replaces `id` with the id of `name`, if a name is given, and clears it.
+    fn named_id(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: u64,
+        kind: AssetKind,
+        name: &mut Option<String>,
+        id: &mut u32,
+    ) {
+        if let Some(name) = name.take() {
+            if let Some(named_id) = self.id(asset_kind, asset_id, kind, &name) {
+                *id = named_id;
+            }
+        }
+    }
+
+    /// Adds the ids of `names` to `ids` and clears the names.
+    fn named_ids(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: u64,
+        kind: AssetKind,
+        names: &mut Vec<String>,
+        ids: &mut Vec<u32>,
+    ) {
+        let names = std::mem::take(names);
+        ids.extend(self.ids(asset_kind, asset_id, kind, &names));
+    }
+
+    fn ids(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: u64,
+        kind: AssetKind,
+        names: &[String],
+    ) -> Vec<u32> {
+        names
+            .iter()
+            .filter_map(|name| self.id(asset_kind, asset_id, kind, name))
+            .collect()
+    }
+
+    fn blob(&mut self, asset_kind: &'static str, asset_id: u64, blob: &mut EntityBlob) {
+        let Some(names) = blob.names.take() else {
+            return;
+        };
+        if let Some(name) = &names.baseline {
+            if let Some(baseline_id) = self.id(asset_kind, asset_id, AssetKind::Baseline, name) {
+                blob.baseline = Some(BaselineComponent {
+                    entity_id: 0,
+                    baseline_id,
+                });
+            }
+        }
+        if !names.traits.is_empty() {
+            let ids = self.ids(asset_kind, asset_id, AssetKind::Trait, &names.traits);
+            blob.traits
+                .get_or_insert_with(|| TraitsComponent {
+                    entity_id: 0,
+                    trait_ids: vec![],
+                })
+                .trait_ids
+                .extend(ids);
+        }
+        if !names.actions.is_empty() {
+            let ids = self.ids(asset_kind, asset_id, AssetKind::Action, &names.actions);
+            blob.actions
+                .get_or_insert_with(|| ActionsComponent {
+                    entity_id: 0,
+                    action_ids: vec![],
+                })
+                .action_ids
+                .extend(ids);
+        }
+    }
+}
+
+/// Moves every name the pack references into the matching id field, with the
+/// id `names` gives it, reporting the names it does not know.
+pub fn resolve_names(asset_pack: &mut AssetPack, names: &AssetNames) -> Vec<AssetValidationError> {
+    let mut r = Resolver {
+        names,
+        errors: vec![],
+    };
+
+    for s in &mut asset_pack.action_steps {
+        // A name on a step of another effect is left for validation to report.
+        if let ActionEffect::AddTrait(t) | ActionEffect::RemoveTrait(t) = &mut s.action_effect {
+            r.named_id("action_step", s.id, AssetKind::Trait, &mut s.trait_name, t);
+        }
+    }
+    for b in &mut asset_pack.baselines {
+        r.named_ids(
+            "baseline",
+            b.id.into(),
+            AssetKind::Action,
+            &mut b.action_names,
+            &mut b.stat_block.action_ids,
+        );
+    }
+    for t in &mut asset_pack.traits {
+        r.named_ids(
+            "trait",
+            t.id.into(),
+            AssetKind::Action,
+            &mut t.action_names,
+            &mut t.stat_block.action_ids,
+        );
+    }
+    for l in &mut asset_pack.levels {
+        r.named_ids(
+            "level",
+            l.level.into(),
+            AssetKind::Trait,
+            &mut l.trait_names,
+            &mut l.trait_ids,
+        );
+    }
+    for b in &mut asset_pack.encounter_blobs {
+        r.blob("encounter_blob", b.id.into(), &mut b.blob);
+    }
+    for e in &mut asset_pack.encounters {
+        let id = e.id.into();
+        r.named_id(
+            "encounter",
+            id,
+            AssetKind::EncounterBlob,
+            &mut e.categoric_blob_name,
+            &mut e.categoric_blob_id,
+        );
+        r.named_ids(
+            "encounter",
+            id,
+            AssetKind::EncounterBlob,
+            &mut e.blob_names,
+            &mut e.blob_ids,
+        );
+    }
+    for t in &mut asset_pack.location_map_themes {
+        for s in t
+            .rooms_selector
+            .selections
+            .iter_mut()
+            .chain(&mut t.paths_selector.selections)
+            .chain(&mut t.decorations_selector.selections)
+        {
+            r.blob("location_map_theme", t.id.into(), &mut s.blob);
+        }
+    }
+    for m in &mut asset_pack.location_maps {
+        r.named_id(
+            "location_map",
+            m.id.into(),
+            AssetKind::LocationMapTheme,
+            &mut m.theme_name,
+            &mut m.theme_id,
+        );
+    }
+    // These blobs have no id of their own, so errors report their index.
+    for (i, b) in asset_pack.instantiate_entity_blobs.iter_mut().enumerate() {
+        r.blob("instantiate_entity_blob", i as u64, b);
+    }
+    r.blob("new_player_blob", 0, &mut asset_pack.new_player_blob);
+
+    r.errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::{ActionStep, ActionType, Intensity},
+        asset::{level::Level, stat_block::StatBlock},
+    };
+
+    fn pack() -> AssetPack {
+        AssetPack {
+            version: 0,
+            actions: vec![Action {
+                id: 4,
+                name: "punch".to_string(),
+                action_type: ActionType::Attack,
+                renderer: None,
+            }],
+            action_steps: vec![ActionStep {
+                id: 9,
+                action_id: 4,
+                sequence_index: 0,
+                action_effect: ActionEffect::AddTrait(0),
+                intensity: Intensity::Normal,
+                trait_name: Some("enraged".to_string()),
+            }],
+            appearance_features: vec![],
+            locales: vec![],
+            localized_appearance_features: vec![],
+            baselines: vec![Baseline {
+                id: 2,
+                name: "human".to_string(),
+                stat_block: StatBlock::default(),
+                action_names: vec!["punch".to_string()],
+            }],
+            traits: vec![Trait {
+                id: 7,
+                name: "enraged".to_string(),
+                stat_block: StatBlock::default(),
+                action_names: vec![],
+            }],
+            levels: vec![],
+            encounter_blobs: vec![],
+            encounters: vec![],
+            location_map_themes: vec![],
+            location_maps: vec![],
+            location_map_connections: vec![],
+            prominence_rules: vec![],
+            instantiate_entity_blobs: vec![],
+            new_player_blob: EntityBlob::default(),
+        }
+    }
+
+    fn level(trait_names: &[&str]) -> Level {
+        Level {
+            level: 1,
+            xp: 0,
+            trait_ids: vec![],
+            trait_names: trait_names.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn names_are_resolved_into_ids() {
+        let mut pack = pack();
+        pack.new_player_blob.names = Some(BlobNames {
+            baseline: Some("human".to_string()),
+            traits: vec!["enraged".to_string()],
+            actions: vec![],
+        });
+        let names = AssetNames::of_pack(&pack);
+
+        assert_eq!(resolve_names(&mut pack, &names), vec![]);
+
+        assert_eq!(
+            pack.baselines
+                .first()
+                .map(|b| b.stat_block.action_ids.clone()),
+            Some(vec![4])
+        );
+        assert!(matches!(
+            pack.action_steps.first().map(|s| &s.action_effect),
+            Some(ActionEffect::AddTrait(7))
+        ));
+        assert_eq!(
+            pack.baselines.first().map(|b| b.action_names.len()),
+            Some(0)
+        );
+        assert_eq!(
+            pack.action_steps.first().map(|s| s.trait_name.clone()),
+            Some(None)
+        );
+        assert_eq!(pack.new_player_blob.names, None);
+        assert_eq!(
+            pack.new_player_blob.baseline.map(|b| b.baseline_id),
+            Some(2)
+        );
+        assert_eq!(
+            pack.new_player_blob.traits.map(|t| t.trait_ids),
+            Some(vec![7])
+        );
+    }
+
+    #[test]
+    fn unknown_names_are_reported() {
+        let mut pack = pack();
+        pack.levels = vec![level(&["enraged", "calm"])];
+        pack.new_player_blob.names = Some(BlobNames {
+            baseline: Some("elf".to_string()),
+            ..Default::default()
+        });
+        let names = AssetNames::of_pack(&pack);
+
+        let errors: Vec<String> = resolve_names(&mut pack, &names)
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "level 1: Unknown trait \"calm\".",
+                "new_player_blob 0: Unknown baseline \"elf\".",
+            ]
+        );
+    }
+
+    #[test]
+    fn loaded_names_resolve_after_the_pack_names() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        for (id, name) in [(5, "calm"), (6, "enraged")] {
+            Trait::insert(
+                &ctx,
+                Trait {
+                    id,
+                    name: name.to_string(),
+                    stat_block: StatBlock::default(),
+                    action_names: vec![],
+                },
+            );
+        }
+        let mut pack = pack();
+        pack.levels = vec![level(&["calm", "enraged"])];
+        let names = AssetNames::of_pack(&pack).with_loaded(&ctx);
+
+        assert_eq!(resolve_names(&mut pack, &names), vec![]);
+
+        assert_eq!(
+            pack.levels.first().map(|l| l.trait_ids.clone()),
+            Some(vec![5, 7])
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use spacetimedb::rand::{rngs::StdRng, RngCore};
+use spacetimedb::rand::RngCore;
 
 pub trait RngRange {
     fn get_range<T, U>(&mut self, min: T, max: T) -> U
@@ -7,7 +7,7 @@ pub trait RngRange {
         U: TryFrom<u32> + Default;
 }
 
-impl RngRange for StdRng {
+impl<R: RngCore> RngRange for R {
     fn get_range<T, U>(&mut self, min: T, max: T) -> U
     where
         T: Into<u32>,
@@ -29,7 +29,7 @@ impl RngRange for StdRng {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use spacetimedb::rand::SeedableRng;
+    use spacetimedb::rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn results_stay_within_the_half_open_range() {
@@ -1,5 +1,21 @@
 secador::secador_multi!(
-    seca_int!(stat, [attack, mhp, defense, mep]),
+    seca_int!(
+        stat,
+        [
+            attack,
+            mhp,
+            defense,
+            mep,
+            fire_resistance,
+            cold_resistance,
+            lightning_resistance,
+            poison_resistance,
+            accuracy,
+            evasion,
+            crit_chance,
+            crit_multiplier
+        ]
+    ),
     seca_id_vec!(
         (stat, StatType),
         [(action_ids, ActionId), (appearance_feature_ids, u32)]
@@ -7,11 +23,11 @@ secador::secador_multi!(
     {
         use std::ops::AddAssign;
 
-        use crate::action::ActionId;
+        use crate::action::{ActionId, DamageType};
         use derive_builder::Builder;
         use spacetimedb::SpacetimeType;
 
-        #[derive(Debug, Clone, SpacetimeType, Builder, Default)]
+        #[derive(Debug, Clone, SpacetimeType, Builder, Default, PartialEq)]
         #[builder(default)]
         pub struct StatBlock {
             __seca_int: __1,
@@ -34,26 +50,44 @@ secador::secador_multi!(
                 }
             }
         }
+
+        impl StatBlock {
+            /// Flat reduction applied to incoming damage of `damage_type`.
+            pub fn resistance(&self, damage_type: DamageType) -> i32 {
+                match damage_type {
+                    DamageType::Physical => self.defense,
+                    DamageType::Fire => self.fire_resistance,
+                    DamageType::Cold => self.cold_resistance,
+                    DamageType::Lightning => self.lightning_resistance,
+                    DamageType::Poison => self.poison_resistance,
+                }
+            }
+        }
     }
 );
 
 #[cfg(test)]
 mod tests {
     use super::StatBlock;
+    use crate::action::DamageType;
 
     #[test]
     fn add_assign_sums_int_stats() {
-        let mut a = StatBlock::default();
-        a.attack = 1;
-        a.mhp = 2;
-        a.defense = 3;
-        a.mep = 4;
-
-        let mut b = StatBlock::default();
-        b.attack = 10;
-        b.mhp = 20;
-        b.defense = 30;
-        b.mep = 40;
+        let mut a = StatBlock {
+            attack: 1,
+            mhp: 2,
+            defense: 3,
+            mep: 4,
+            ..Default::default()
+        };
+
+        let b = StatBlock {
+            attack: 10,
+            mhp: 20,
+            defense: 30,
+            mep: 40,
+            ..Default::default()
+        };
 
         a += &b;
 
@@ -65,13 +99,17 @@ mod tests {
 
     #[test]
     fn add_assign_unions_id_vecs_without_duplicates() {
-        let mut a = StatBlock::default();
-        a.action_ids = vec![1, 2];
-        a.appearance_feature_ids = vec![7];
-
-        let mut b = StatBlock::default();
-        b.action_ids = vec![2, 3]; // 2 already present
-        b.appearance_feature_ids = vec![7, 8]; // 7 already present
+        let mut a = StatBlock {
+            action_ids: vec![1, 2],
+            appearance_feature_ids: vec![7],
+            ..Default::default()
+        };
+
+        let b = StatBlock {
+            action_ids: vec![2, 3],             // 2 already present
+            appearance_feature_ids: vec![7, 8], // 7 already present
+            ..Default::default()
+        };
 
         a += &b;
 
@@ -81,14 +119,33 @@ mod tests {
 
     #[test]
     fn add_assign_dedups_within_the_incoming_block() {
-        let mut a = StatBlock::default();
-        a.action_ids = vec![1];
+        let mut a = StatBlock {
+            action_ids: vec![1],
+            ..Default::default()
+        };
 
-        let mut b = StatBlock::default();
-        b.action_ids = vec![2, 2, 2]; // internal duplicates
+        let b = StatBlock {
+            action_ids: vec![2, 2, 2], // internal duplicates
+            ..Default::default()
+        };
 
         a += &b;
 
         assert_eq!(a.action_ids, vec![1, 2]);
     }
+
+    #[test]
+    fn resistance_uses_defense_for_physical_damage() {
+        let s = StatBlock {
+            defense: 5,
+            fire_resistance: 7,
+            poison_resistance: -2,
+            ..Default::default()
+        };
+
+        assert_eq!(s.resistance(DamageType::Physical), 5);
+        assert_eq!(s.resistance(DamageType::Fire), 7);
+        assert_eq!(s.resistance(DamageType::Cold), 0);
+        assert_eq!(s.resistance(DamageType::Poison), -2);
+    }
 }
@@ -10,4 +10,7 @@ pub struct Trait {
     #[unique]
     pub name: String,
     pub stat_block: StatBlock,
+    /// Names of actions added to the stat block's `action_ids` when the pack is
+    /// loaded.
+    pub action_names: Vec<String>,
 }
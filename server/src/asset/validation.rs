@@ -0,0 +1,632 @@
+use std::{collections::HashSet, fmt, hash::Hash};
+
+use crate::{
+    action::ActionEffect,
+    asset::{
+        stat_block::StatBlock,
+        weighted_sampler::{WeightedSample, WeightedSampler},
+        AssetPack,
+    },
+    entity::EntityBlob,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetValidationError {
+    pub asset_kind: &'static str,
+    pub asset_id: u64,
+    pub message: String,
+}
+
+impl fmt::Display for AssetValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.asset_kind, self.asset_id, self.message)
+    }
+}
+
+/// Formats every error on its own line, for returning from a reducer.
+pub fn report(errors: &[AssetValidationError]) -> String {
+    let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    format!(
+        "Asset pack failed validation with {} error(s):\n{}",
+        errors.len(),
+        lines.join("\n")
+    )
+}
+
+struct Validator {
+    errors: Vec<AssetValidationError>,
+}
+
+impl Validator {
+    fn error(&mut self, asset_kind: &'static str, asset_id: impl Into<u64>, message: String) {
+        self.errors.push(AssetValidationError {
+            asset_kind,
+            asset_id: asset_id.into(),
+            message,
+        });
+    }
+
+    /// Collects the keys of a table, reporting any key that appears twice.
+    fn unique_keys<K: Copy + Eq + Hash + Into<u64>>(
+        &mut self,
+        asset_kind: &'static str,
+        keys: impl IntoIterator<Item = K>,
+    ) -> HashSet<K> {
+        let mut set = HashSet::new();
+        for k in keys {
+            if !set.insert(k) {
+                self.error(asset_kind, k, "Duplicate id.".to_string());
+            }
+        }
+        set
+    }
+
+    fn unique_names<'a>(
+        &mut self,
+        asset_kind: &'static str,
+        names: impl IntoIterator<Item = (u32, &'a String)>,
+    ) {
+        let mut set = HashSet::new();
+        for (id, name) in names {
+            if !set.insert(name) {
+                self.error(asset_kind, id, format!("Duplicate name {:?}.", name));
+            }
+        }
+    }
+
+    fn reference<K: Copy + Eq + Hash + fmt::Display>(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: impl Into<u64>,
+        field: &str,
+        keys: &HashSet<K>,
+        key: K,
+    ) {
+        if !keys.contains(&key) {
+            self.error(
+                asset_kind,
+                asset_id,
+                format!("{} {} does not exist.", field, key),
+            );
+        }
+    }
+
+    /// Reports names loading did not move into their id field.
+    fn unresolved<'a>(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: impl Into<u64> + Copy,
+        field: &str,
+        names: impl IntoIterator<Item = &'a String>,
+    ) {
+        for name in names {
+            self.error(
+                asset_kind,
+                asset_id,
+                format!("{} {:?} is not resolved.", field, name),
+            );
+        }
+    }
+
+    fn sampler<S: WeightedSampler>(
+        &mut self,
+        asset_kind: &'static str,
+        asset_id: impl Into<u64>,
+        field: &str,
+        sampler: &S,
+    ) {
+        let total_weight = sampler
+            .selections()
+            .iter()
+            .fold(0u32, |total, s| total.saturating_add(s.weight()));
+        if total_weight == 0 {
+            self.error(
+                asset_kind,
+                asset_id,
+                format!("{} has no selection with a positive weight.", field),
+            );
+        }
+    }
+}
+
+struct Keys {
+    actions: HashSet<u32>,
+    appearance_features: HashSet<u32>,
+    baselines: HashSet<u32>,
+    traits: HashSet<u32>,
+}
+
+impl Keys {
+    fn stat_block(
+        &self,
+        v: &mut Validator,
+        asset_kind: &'static str,
+        asset_id: u32,
+        stat_block: &StatBlock,
+    ) {
+        for id in &stat_block.action_ids {
+            v.reference(asset_kind, asset_id, "action", &self.actions, *id);
+        }
+        for index in &stat_block.appearance_feature_ids {
+            v.reference(
+                asset_kind,
+                asset_id,
+                "appearance feature",
+                &self.appearance_features,
+                *index,
+            );
+        }
+    }
+
+    fn blob(
+        &self,
+        v: &mut Validator,
+        asset_kind: &'static str,
+        asset_id: impl Into<u64> + Copy,
+        blob: &EntityBlob,
+    ) {
+        if blob.blob_id != 0 || blob.deactivated_entity_id.is_some() {
+            v.error(
+                asset_kind,
+                asset_id,
+                "Blob has the key of a deactivated entity.".to_string(),
+            );
+        }
+        if blob.names.is_some() {
+            v.error(
+                asset_kind,
+                asset_id,
+                "Blob names are not resolved.".to_string(),
+            );
+        }
+        if let Some(b) = &blob.baseline {
+            v.reference(
+                asset_kind,
+                asset_id,
+                "baseline",
+                &self.baselines,
+                b.baseline_id,
+            );
+        }
+        if let Some(t) = &blob.traits {
+            for id in &t.trait_ids {
+                v.reference(asset_kind, asset_id, "trait", &self.traits, *id);
+            }
+        }
+        if let Some(a) = &blob.actions {
+            for id in &a.action_ids {
+                v.reference(asset_kind, asset_id, "action", &self.actions, *id);
+            }
+        }
+    }
+}
+
+/// Checks ids and cross-references of an asset pack, returning every problem found.
+pub fn validate(asset_pack: &AssetPack) -> Vec<AssetValidationError> {
+    let mut v = Validator { errors: Vec::new() };
+
+    let keys = Keys {
+        actions: v.unique_keys("action", asset_pack.actions.iter().map(|a| a.id)),
+        appearance_features: v.unique_keys(
+            "appearance_feature",
+            asset_pack.appearance_features.iter().map(|a| a.index),
+        ),
+        baselines: v.unique_keys("baseline", asset_pack.baselines.iter().map(|b| b.id)),
+        traits: v.unique_keys("trait", asset_pack.traits.iter().map(|t| t.id)),
+    };
+    v.unique_keys("locale", asset_pack.locales.iter().map(|l| l.id));
+    v.unique_keys(
+        "localized_appearance_feature",
+        asset_pack
+            .localized_appearance_features
+            .iter()
+            .map(|f| f.id),
+    );
+    v.unique_keys("action_step", asset_pack.action_steps.iter().map(|s| s.id));
+    v.unique_keys("level", asset_pack.levels.iter().map(|l| l.level));
+    let encounter_blobs = v.unique_keys(
+        "encounter_blob",
+        asset_pack.encounter_blobs.iter().map(|b| b.id),
+    );
+    let encounters = v.unique_keys("encounter", asset_pack.encounters.iter().map(|e| e.id));
+    let themes = v.unique_keys(
+        "location_map_theme",
+        asset_pack.location_map_themes.iter().map(|t| t.id),
+    );
+    let location_maps = v.unique_keys(
+        "location_map",
+        asset_pack.location_maps.iter().map(|m| m.id),
+    );
+    v.unique_keys(
+        "location_map_connection",
+        asset_pack.location_map_connections.iter().map(|c| c.id),
+    );
+    // Rules are keyed by their condition, so errors report their index.
+    let mut conditions = HashSet::new();
+    for (i, r) in asset_pack.prominence_rules.iter().enumerate() {
+        if !conditions.insert(r.condition) {
+            v.error(
+                "prominence_rule",
+                i as u64,
+                format!("Duplicate condition {:?}.", r.condition),
+            );
+        }
+    }
+
+    v.unique_names("action", asset_pack.actions.iter().map(|a| (a.id, &a.name)));
+    v.unique_names("locale", asset_pack.locales.iter().map(|l| (l.id, &l.code)));
+    v.unique_names(
+        "baseline",
+        asset_pack.baselines.iter().map(|b| (b.id, &b.name)),
+    );
+    v.unique_names("trait", asset_pack.traits.iter().map(|t| (t.id, &t.name)));
+    v.unique_names(
+        "encounter_blob",
+        asset_pack.encounter_blobs.iter().map(|b| (b.id, &b.name)),
+    );
+    v.unique_names(
+        "location_map_theme",
+        asset_pack
+            .location_map_themes
+            .iter()
+            .map(|t| (t.id, &t.name)),
+    );
+
+    let locale_codes: HashSet<&String> = asset_pack.locales.iter().map(|l| &l.code).collect();
+    let mut translations = HashSet::new();
+    for f in &asset_pack.localized_appearance_features {
+        let asset_kind = "localized_appearance_feature";
+        v.reference(asset_kind, f.id, "locale", &locale_codes, &f.locale);
+        v.reference(
+            asset_kind,
+            f.id,
+            "appearance feature",
+            &keys.appearance_features,
+            f.index,
+        );
+        if !translations.insert((&f.locale, f.index)) {
+            v.error(
+                asset_kind,
+                f.id,
+                format!(
+                    "Duplicate translation of appearance feature {} into {:?}.",
+                    f.index, f.locale
+                ),
+            );
+        }
+    }
+
+    for s in &asset_pack.action_steps {
+        v.reference("action_step", s.id, "action", &keys.actions, s.action_id);
+        if let ActionEffect::AddTrait(id) | ActionEffect::RemoveTrait(id) = &s.action_effect {
+            v.reference("action_step", s.id, "trait", &keys.traits, *id);
+        }
+        v.unresolved("action_step", s.id, "trait", &s.trait_name);
+    }
+
+    for b in &asset_pack.baselines {
+        keys.stat_block(&mut v, "baseline", b.id, &b.stat_block);
+        v.unresolved("baseline", b.id, "action", &b.action_names);
+    }
+    for t in &asset_pack.traits {
+        keys.stat_block(&mut v, "trait", t.id, &t.stat_block);
+        v.unresolved("trait", t.id, "action", &t.action_names);
+    }
+    for l in &asset_pack.levels {
+        for id in &l.trait_ids {
+            v.reference("level", l.level, "trait", &keys.traits, *id);
+        }
+        v.unresolved("level", l.level, "trait", &l.trait_names);
+    }
+
+    for b in &asset_pack.encounter_blobs {
+        keys.blob(&mut v, "encounter_blob", b.id, &b.blob);
+    }
+    for e in &asset_pack.encounters {
+        v.reference(
+            "encounter",
+            e.id,
+            "categoric blob",
+            &encounter_blobs,
+            e.categoric_blob_id,
+        );
+        for id in &e.blob_ids {
+            v.reference("encounter", e.id, "blob", &encounter_blobs, *id);
+        }
+        v.unresolved("encounter", e.id, "categoric blob", &e.categoric_blob_name);
+        v.unresolved("encounter", e.id, "blob", &e.blob_names);
+    }
+
+    for t in &asset_pack.location_map_themes {
+        v.sampler(
+            "location_map_theme",
+            t.id,
+            "rooms_selector",
+            &t.rooms_selector,
+        );
+        v.sampler(
+            "location_map_theme",
+            t.id,
+            "paths_selector",
+            &t.paths_selector,
+        );
+        if t.max_decoration_count > 0 {
+            v.sampler(
+                "location_map_theme",
+                t.id,
+                "decorations_selector",
+                &t.decorations_selector,
+            );
+        }
+        for s in t
+            .rooms_selector
+            .selections()
+            .iter()
+            .chain(t.paths_selector.selections())
+            .chain(t.decorations_selector.selections())
+        {
+            keys.blob(&mut v, "location_map_theme", t.id, s.value());
+        }
+    }
+
+    for m in &asset_pack.location_maps {
+        v.reference("location_map", m.id, "theme", &themes, m.theme_id);
+        v.unresolved("location_map", m.id, "theme", &m.theme_name);
+        if m.max_encounter_count > 0 {
+            v.sampler(
+                "location_map",
+                m.id,
+                "encounter_ids_sampler",
+                &m.encounter_ids_sampler,
+            );
+        }
+        for s in m.encounter_ids_sampler.selections() {
+            v.reference("location_map", m.id, "encounter", &encounters, *s.value());
+        }
+    }
+    for c in &asset_pack.location_map_connections {
+        for id in [c.exit_location_map_id, c.destination_location_map_id] {
+            v.reference(
+                "location_map_connection",
+                c.id,
+                "location map",
+                &location_maps,
+                id,
+            );
+        }
+    }
+
+    // These blobs have no id of their own, so errors report their index.
+    for (i, b) in asset_pack.instantiate_entity_blobs.iter().enumerate() {
+        keys.blob(&mut v, "instantiate_entity_blob", i as u64, b);
+    }
+    keys.blob(&mut v, "new_player_blob", 0u64, &asset_pack.new_player_blob);
+
+    v.errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::{Action, ActionStep, ActionType, Intensity},
+        appearance::{
+            AppearanceFeature, AppearanceFeatureType, Locale, LocalizedAppearanceFeature,
+        },
+        asset::{baseline::Baseline, encounter::Encounter},
+        entity::BaselineComponent,
+        prominence::{ProminenceCondition, ProminenceRule},
+    };
+
+    fn empty_pack() -> AssetPack {
+        AssetPack {
+            version: 0,
+            actions: vec![],
+            action_steps: vec![],
+            appearance_features: vec![],
+            locales: vec![],
+            localized_appearance_features: vec![],
+            baselines: vec![],
+            traits: vec![],
+            levels: vec![],
+            encounter_blobs: vec![],
+            encounters: vec![],
+            location_map_themes: vec![],
+            location_maps: vec![],
+            location_map_connections: vec![],
+            prominence_rules: vec![],
+            instantiate_entity_blobs: vec![],
+            new_player_blob: EntityBlob::default(),
+        }
+    }
+
+    fn action(id: u32, name: &str) -> Action {
+        Action {
+            id,
+            name: name.to_string(),
+            action_type: ActionType::Attack,
+            renderer: None,
+        }
+    }
+
+    #[test]
+    fn an_empty_pack_is_valid() {
+        assert_eq!(validate(&empty_pack()), vec![]);
+    }
+
+    #[test]
+    fn every_missing_reference_is_reported() {
+        let mut pack = empty_pack();
+        pack.actions = vec![action(1, "punch")];
+        pack.action_steps = vec![ActionStep {
+            id: 10,
+            action_id: 2,
+            sequence_index: 0,
+            action_effect: ActionEffect::Rest,
+            intensity: Intensity::Normal,
+            trait_name: None,
+        }];
+        pack.baselines = vec![Baseline {
+            id: 3,
+            name: "human".to_string(),
+            stat_block: StatBlock {
+                action_ids: vec![1, 4],
+                ..Default::default()
+            },
+            action_names: vec![],
+        }];
+        pack.encounters = vec![Encounter {
+            id: 5,
+            categoric_blob_id: 6,
+            blob_ids: vec![],
+            categoric_blob_name: None,
+            blob_names: vec![],
+            respawn_delay: None,
+        }];
+        pack.new_player_blob = EntityBlob {
+            baseline: Some(BaselineComponent {
+                entity_id: 0,
+                baseline_id: 7,
+            }),
+            ..Default::default()
+        };
+
+        let errors: Vec<(&str, u64)> = validate(&pack)
+            .iter()
+            .map(|e| (e.asset_kind, e.asset_id))
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                ("action_step", 10),
+                ("baseline", 3),
+                ("encounter", 5),
+                ("new_player_blob", 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_and_names_are_reported() {
+        let mut pack = empty_pack();
+        pack.actions = vec![action(1, "punch"), action(1, "kick"), action(2, "kick")];
+
+        let errors = validate(&pack);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Duplicate id.");
+        assert_eq!(errors[1].asset_id, 2);
+    }
+
+    #[test]
+    fn duplicate_prominence_conditions_are_reported() {
+        let rule = |condition, weight| ProminenceRule { condition, weight };
+        let mut pack = empty_pack();
+        pack.prominence_rules = vec![
+            rule(ProminenceCondition::Hp, 1),
+            rule(ProminenceCondition::Item, 2),
+            rule(ProminenceCondition::Hp, 3),
+        ];
+
+        let errors = validate(&pack);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].asset_kind, "prominence_rule");
+        assert_eq!(errors[0].asset_id, 2);
+        assert_eq!(errors[0].message, "Duplicate condition Hp.");
+    }
+
+    #[test]
+    fn localized_features_need_a_locale_and_one_translation_each() {
+        let localized = |id: u64, locale: &str, index: u32| LocalizedAppearanceFeature {
+            id,
+            locale: locale.to_string(),
+            index,
+            text: "ゴブリン".to_string(),
+            appearance_feature_type: AppearanceFeatureType::Noun,
+            priority: 0,
+        };
+        let mut pack = empty_pack();
+        pack.appearance_features = vec![AppearanceFeature {
+            index: 1,
+            text: "goblin".to_string(),
+            appearance_feature_type: AppearanceFeatureType::Noun,
+            priority: 0,
+        }];
+        pack.locales = vec![Locale {
+            id: 1,
+            code: "ja".to_string(),
+            ..Default::default()
+        }];
+        pack.localized_appearance_features = vec![
+            localized(1, "ja", 1),
+            localized(2, "ja", 1),
+            localized(3, "fr", 1),
+            localized(4, "ja", 2),
+        ];
+
+        let errors: Vec<(u64, String)> = validate(&pack)
+            .into_iter()
+            .map(|e| (e.asset_id, e.message))
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    2,
+                    "Duplicate translation of appearance feature 1 into \"ja\".".to_string()
+                ),
+                (3, "locale fr does not exist.".to_string()),
+                (4, "appearance feature 2 does not exist.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn instantiate_entity_blob_errors_report_the_blob_index() {
+        let mut pack = empty_pack();
+        pack.instantiate_entity_blobs = vec![
+            EntityBlob::default(),
+            EntityBlob {
+                baseline: Some(BaselineComponent {
+                    entity_id: 0,
+                    baseline_id: 4,
+                }),
+                ..Default::default()
+            },
+        ];
+
+        let errors = validate(&pack);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "instantiate_entity_blob 1: baseline 4 does not exist."
+        );
+    }
+
+    #[test]
+    fn asset_blobs_with_a_deactivation_key_are_reported() {
+        let mut pack = empty_pack();
+        pack.new_player_blob = EntityBlob {
+            blob_id: 3,
+            ..Default::default()
+        };
+        pack.instantiate_entity_blobs = vec![EntityBlob {
+            deactivated_entity_id: Some(9),
+            ..Default::default()
+        }];
+
+        let errors: Vec<String> = validate(&pack).iter().map(|e| e.to_string()).collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                "instantiate_entity_blob 0: Blob has the key of a deactivated entity.",
+                "new_player_blob 0: Blob has the key of a deactivated entity.",
+            ]
+        );
+    }
+}
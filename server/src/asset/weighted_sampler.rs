@@ -94,8 +94,14 @@ mod tests {
     fn samples_are_drawn_in_proportion_to_weight() {
         let sampler = Sampler {
             selections: vec![
-                Sample { weight: 5, value: 0 },
-                Sample { weight: 4, value: 1 },
+                Sample {
+                    weight: 5,
+                    value: 0,
+                },
+                Sample {
+                    weight: 4,
+                    value: 1,
+                },
             ],
         };
         let counts = counts(&sampler, 90_000);
@@ -113,12 +119,21 @@ mod tests {
         // shorted a unit of weight. A single-weight tail must still appear.
         let sampler = Sampler {
             selections: vec![
-                Sample { weight: 1, value: 0 },
-                Sample { weight: 1, value: 1 },
+                Sample {
+                    weight: 1,
+                    value: 0,
+                },
+                Sample {
+                    weight: 1,
+                    value: 1,
+                },
             ],
         };
         let counts = counts(&sampler, 20_000);
-        assert!(counts.iter().all(|&c| c > 0), "unreachable slot: {counts:?}");
+        assert!(
+            counts.iter().all(|&c| c > 0),
+            "unreachable slot: {counts:?}"
+        );
         let ratio = counts[0] as f64 / counts[1] as f64;
         assert!(
             (ratio - 1.0).abs() < 0.05,
@@ -0,0 +1,137 @@
+use spacetimedb::{rand::RngCore, SpacetimeType};
+
+use crate::asset::{rng_range::RngRange, stat_block::StatBlock};
+
+#[derive(Debug, Clone, Copy, SpacetimeType, PartialEq, Eq)]
+pub enum AttackOutcome {
+    Hit,
+    Critical,
+    Miss,
+}
+
+/// Rolls an attack of `damage` from `attacker` against `target`.
+///
+/// The hit chance is `100 + accuracy - evasion` percent, and a hit becomes
+/// critical with `crit_chance` percent, scaling damage by `crit_multiplier`
+/// percent. Multipliers below 100 are treated as 100.
+pub fn resolve_attack(
+    rng: &mut impl RngCore,
+    damage: i32,
+    attacker: &StatBlock,
+    target: &StatBlock,
+) -> (AttackOutcome, i32) {
+    let hit_chance = 100i32
+        .saturating_add(attacker.accuracy)
+        .saturating_sub(target.evasion);
+    if !roll_percent(rng, hit_chance) {
+        return (AttackOutcome::Miss, 0);
+    }
+
+    if !roll_percent(rng, attacker.crit_chance) {
+        return (AttackOutcome::Hit, damage);
+    }
+
+    let multiplier = i64::from(attacker.crit_multiplier.max(100));
+    let critical_damage = i64::from(damage)
+        .saturating_mul(multiplier)
+        .checked_div(100)
+        .unwrap_or_default();
+    (
+        AttackOutcome::Critical,
+        i32::try_from(critical_damage).unwrap_or(i32::MAX),
+    )
+}
+
+fn roll_percent(rng: &mut impl RngCore, chance: i32) -> bool {
+    let roll: i32 = rng.get_range(0u32, 100u32);
+    roll < chance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spacetimedb::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn default_stats_always_hit_for_full_damage() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let s = StatBlock::default();
+        for _ in 0..1_000 {
+            assert_eq!(resolve_attack(&mut rng, 7, &s, &s), (AttackOutcome::Hit, 7));
+        }
+    }
+
+    #[test]
+    fn evasion_beyond_accuracy_always_misses() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let attacker = StatBlock {
+            accuracy: 10,
+            ..Default::default()
+        };
+        let target = StatBlock {
+            evasion: 110,
+            ..Default::default()
+        };
+        for _ in 0..1_000 {
+            assert_eq!(
+                resolve_attack(&mut rng, 7, &attacker, &target),
+                (AttackOutcome::Miss, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn certain_crits_apply_the_multiplier() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let attacker = StatBlock {
+            crit_chance: 100,
+            crit_multiplier: 150,
+            ..Default::default()
+        };
+        let target = StatBlock::default();
+        assert_eq!(
+            resolve_attack(&mut rng, 10, &attacker, &target),
+            (AttackOutcome::Critical, 15)
+        );
+    }
+
+    #[test]
+    fn crit_multipliers_below_100_do_not_reduce_damage() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let attacker = StatBlock {
+            crit_chance: 100,
+            crit_multiplier: 20,
+            ..Default::default()
+        };
+        let target = StatBlock::default();
+        assert_eq!(
+            resolve_attack(&mut rng, 10, &attacker, &target),
+            (AttackOutcome::Critical, 10)
+        );
+    }
+
+    #[test]
+    fn outcomes_are_reproducible_under_a_fixed_seed() {
+        let attacker = StatBlock {
+            crit_chance: 30,
+            crit_multiplier: 200,
+            ..Default::default()
+        };
+        let target = StatBlock {
+            evasion: 40,
+            ..Default::default()
+        };
+        let roll_all = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..100)
+                .map(|_| resolve_attack(&mut rng, 10, &attacker, &target))
+                .collect::<Vec<_>>()
+        };
+
+        let outcomes = roll_all(42);
+        assert_eq!(outcomes, roll_all(42));
+        assert!(outcomes.contains(&(AttackOutcome::Miss, 0)));
+        assert!(outcomes.contains(&(AttackOutcome::Hit, 10)));
+        assert!(outcomes.contains(&(AttackOutcome::Critical, 20)));
+    }
+}
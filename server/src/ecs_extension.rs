@@ -1,9 +1,21 @@
 use crate::{
-    asset::ReducerContextExtension, entity::*,
+    asset::{location_map::LocationMap, ReducerContextExtension},
+    entity::*,
     entity_handle_extension::InstantiateEntityBlobExtension,
+    store::StoredRow,
 };
 use ecs::Ecs;
-use spacetimedb::Identity;
+use spacetimedb::{table, Identity};
+
+/// The blob of an offline player, keyed by identity so reconnecting finds it
+/// without scanning `entity_blobs`.
+#[table(accessor = deactivated_players)]
+#[derive(Debug, Clone)]
+pub struct DeactivatedPlayer {
+    #[primary_key]
+    pub identity: Identity,
+    pub blob_id: u64,
+}
 
 pub trait EcsExtension<'a> {
     fn new_room(self, blob: EntityBlob, location_map_entity_id: u64) -> EntityHandle<'a>;
@@ -13,15 +25,24 @@ pub trait EcsExtension<'a> {
         location_entity_id: u64,
         destination_entity_id: u64,
     ) -> EntityHandle<'a>;
-    fn from_player_identity(
+    fn delete_room(self, room_entity_id: u64);
+    fn find_by_player_identity(
         self,
         identity: Identity,
     ) -> Option<player_controller_component::WithComponent<EntityHandle<'a>>>;
-    fn from_name(self, name: &str) -> Option<name_component::WithComponent<EntityHandle<'a>>>;
+    fn deactivate_player(self, entity_id: u64) -> Option<EntityBlob>;
+    fn reactivate_player(self, identity: Identity) -> Option<EntityHandle<'a>>;
+    fn find_by_name(self, name: &str) -> Option<name_component::WithComponent<EntityHandle<'a>>>;
     fn new_player(
         self,
         identity: Identity,
     ) -> Result<player_controller_component::WithComponent<EntityHandle<'a>>, String>;
+    fn new_in_room(self, blob: EntityBlob, room_entity_id: u64) -> EntityHandle<'a>;
+    fn regenerate_location_map(
+        self,
+        location_map_entity_id: u64,
+        rng_seed: u64,
+    ) -> Result<u64, String>;
 }
 
 impl<'a> EcsExtension<'a> for Ecs<'a> {
@@ -43,23 +64,58 @@ impl<'a> EcsExtension<'a> for Ecs<'a> {
             .upsert_new_path(destination_entity_id)
             .into_handle()
     }
-    fn from_player_identity(
+
+    /// Deletes a room with the paths into and out of it. Its other occupants
+    /// only lose their location, so nothing is left pointing at the room.
+    fn delete_room(self, room_entity_id: u64) {
+        // Exits are not deleted by the room's relations, since deleting a
+        // location only nullifies it.
+        let exit_ids: Vec<u64> = self
+            .iter_location_by_location_entity_id(&room_entity_id)
+            .with_path()
+            .map(|p| p.entity_id())
+            .collect();
+        for id in exit_ids {
+            self.find(id).delete();
+        }
+        self.find(room_entity_id).delete();
+    }
+    fn find_by_player_identity(
         self,
         identity: Identity,
     ) -> Option<player_controller_component::WithComponent<EntityHandle<'a>>> {
-        self.db
-            .player_controller_components()
-            .identity()
-            .find(identity)
-            .map(|p| self.into_player_controller_handle(p))
+        self.iter_player_controller_by_identity(&identity).next()
     }
 
-    fn from_name(self, name: &str) -> Option<name_component::WithComponent<EntityHandle<'a>>> {
-        self.db
-            .name_components()
-            .name()
-            .find(name.to_string())
-            .map(|n| self.into_name_handle(n))
+    /// Parks a player in `entity_blobs` until its identity connects again.
+    fn deactivate_player(self, entity_id: u64) -> Option<EntityBlob> {
+        let player = self.find(entity_id);
+        let identity = player.player_controller()?.identity;
+        let blob = player.deactivate(0, None);
+        // Replaces a row left by a reactivation whose blob was gone.
+        DeactivatedPlayer::delete(&self, &identity);
+        DeactivatedPlayer::insert(
+            &self,
+            DeactivatedPlayer {
+                identity,
+                blob_id: blob.blob_id,
+            },
+        );
+        Some(blob)
+    }
+
+    /// Restores the player of this identity deactivated while offline.
+    fn reactivate_player(self, identity: Identity) -> Option<EntityHandle<'a>> {
+        let parked = DeactivatedPlayer::find(&self, &identity)?;
+        // The row is only dropped once the player is back, so a failed
+        // reactivation loses nothing.
+        let e = self.reactivate(parked.blob_id)?;
+        DeactivatedPlayer::delete(&self, &identity);
+        Some(e)
+    }
+
+    fn find_by_name(self, name: &str) -> Option<name_component::WithComponent<EntityHandle<'a>>> {
+        self.iter_name_by_name(&name.to_string()).next()
     }
 
     fn new_player(
@@ -77,12 +133,329 @@ impl<'a> EcsExtension<'a> for Ecs<'a> {
                 )
                 .upsert_new_allegiance(
                     // WIP NewPlayerAllegiance SpecialEntityComponent?
-                    self.from_name("allegiance1")
+                    self.find_by_name("allegiance1")
                         .ok_or("Cannot find starting allegiance.")?
                         .entity_id(),
                 )
+                .upsert_new_experience(0, 0)
                 .into_handle()
                 .upsert_new_player_controller(identity)
         })
     }
+
+    fn new_in_room(self, blob: EntityBlob, room_entity_id: u64) -> EntityHandle<'a> {
+        self.new()
+            .instantiate_blob_dirty(blob)
+            .upsert_new_location(room_entity_id)
+            .into_handle()
+    }
+
+    /// Replaces a generated map with one from the same asset and a new seed.
+    /// Players inside, and paths leading into or out of the map, are moved to
+    /// the first main room of the new map. Everything else inside is deleted.
+    /// Returns the new map entity id.
+    fn regenerate_location_map(
+        self,
+        location_map_entity_id: u64,
+        rng_seed: u64,
+    ) -> Result<u64, String> {
+        let map_entity = self.find(location_map_entity_id);
+        let source = map_entity.location_map_source().ok_or(format!(
+            "Entity {} is not a generated location map.",
+            location_map_entity_id
+        ))?;
+        let mut location_map = LocationMap::find(&self, &source.location_map_id).ok_or(format!(
+            "Cannot find location map {}.",
+            source.location_map_id
+        ))?;
+
+        let room_ids: Vec<u64> = self
+            .iter_location_map_by_location_map_entity_id(&location_map_entity_id)
+            .map(|room| room.entity_id())
+            .collect();
+        let player_ids: Vec<u64> = room_ids
+            .iter()
+            .flat_map(|room_id| {
+                self.iter_location_by_location_entity_id(room_id)
+                    .with_player_controller()
+                    .map(|p| p.entity_id())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let entrance_ids: Vec<u64> = room_ids
+            .iter()
+            .flat_map(|room_id| {
+                self.iter_path_by_destination_entity_id(room_id)
+                    .filter(|p| {
+                        p.location()
+                            .is_none_or(|l| !room_ids.contains(&l.location_entity_id))
+                    })
+                    .map(|p| p.entity_id())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let exit_ids: Vec<u64> = room_ids
+            .iter()
+            .flat_map(|room_id| {
+                self.iter_location_by_location_entity_id(room_id)
+                    .with_path()
+                    .filter(|p| !room_ids.contains(&p.path().destination_entity_id))
+                    .map(|p| p.entity_id())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let occupant_ids: Vec<u64> = room_ids
+            .iter()
+            .flat_map(|room_id| {
+                self.iter_location_by_location_entity_id(room_id)
+                    .without_path()
+                    .map(|l| l.entity_id())
+                    .filter(|id| !player_ids.contains(id))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        // Detach everything that outlives the map from its rooms, so the
+        // cascades below leave it in place.
+        for id in player_ids.iter().chain(&exit_ids) {
+            self.find(*id).delete_location();
+        }
+        for id in &entrance_ids {
+            self.find(*id).delete_path();
+        }
+        // Deleting a room only clears the location of what is inside it, so
+        // the rest of the contents are deleted first. The rooms then go with
+        // the paths between them, and the map after them.
+        for id in occupant_ids {
+            self.find(id).delete();
+        }
+        for room_id in room_ids {
+            self.delete_room(room_id);
+        }
+        map_entity.delete();
+
+        location_map.rng_seed = Some(rng_seed);
+        let result = location_map.generate_entities(self);
+        match result.main_room_ids.first().copied() {
+            Some(room_entity_id) => {
+                for id in player_ids.iter().chain(&exit_ids) {
+                    self.find(*id).upsert_new_location(room_entity_id);
+                }
+                for id in entrance_ids {
+                    self.find(id).upsert_new_path(room_entity_id);
+                }
+            }
+            None => {
+                for id in exit_ids.into_iter().chain(entrance_ids) {
+                    self.find(id).delete();
+                }
+            }
+        }
+
+        result
+            .location_map_entity_id
+            .ok_or("Map regeneration did not create a map entity.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeactivatedPlayer, EcsExtension};
+    use crate::{
+        asset::{
+            location_map::{EncounterIdsSampler, Layout, LocationMap},
+            location_map_theme::{EntityBlobSample, EntityBlobsSampler, LocationMapTheme},
+        },
+        entity::*,
+        store::StoredRow,
+    };
+    use ecs::WithEcs;
+    use spacetimedb::{Identity, ReducerContext};
+
+    fn sampler() -> EntityBlobsSampler {
+        EntityBlobsSampler {
+            selections: vec![EntityBlobSample {
+                weight: 1,
+                blob: EntityBlob::default(),
+            }],
+        }
+    }
+
+    /// Generates a two-room map, returning its entity id and first main room.
+    fn generate_map(ctx: &ReducerContext) -> Result<(u64, u64), String> {
+        LocationMapTheme::insert(
+            ctx,
+            LocationMapTheme {
+                id: 1,
+                name: "cave".to_string(),
+                decorations_selector: EntityBlobsSampler { selections: vec![] },
+                min_decoration_count: 0,
+                max_decoration_count: 0,
+                paths_selector: sampler(),
+                rooms_selector: sampler(),
+            },
+        );
+        let location_map = LocationMap::insert(
+            ctx,
+            LocationMap {
+                id: 1,
+                name: "cave".to_string(),
+                theme_id: 1,
+                theme_name: None,
+                layout: Layout::Path,
+                rng_seed: None,
+                extra_room_count: 0,
+                main_room_count: 2,
+                loop_count: 0,
+                encounter_ids_sampler: EncounterIdsSampler { selections: vec![] },
+                min_encounter_count: 0,
+                max_encounter_count: 0,
+            },
+        );
+        let result = location_map.generate_entities(ctx.ecs());
+        match (result.location_map_entity_id, result.main_room_ids.first()) {
+            (Some(map_id), Some(room_id)) => Ok((map_id, *room_id)),
+            _ => Err("The map was not generated.".to_string()),
+        }
+    }
+
+    #[test]
+    fn regenerating_a_map_keeps_players_and_paths_crossing_into_it() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = ReducerContext::__dummy();
+        let ecs = ctx.ecs();
+        let (map_id, room_id) = generate_map(&ctx)?;
+        let outside_id = ecs.new().entity_id();
+        let entrance_id = ecs
+            .new_path(EntityBlob::default(), outside_id, room_id)
+            .entity_id();
+        let exit_id = ecs
+            .new_path(EntityBlob::default(), room_id, outside_id)
+            .entity_id();
+        let player_id = ecs
+            .new_in_room(EntityBlob::default(), room_id)
+            .upsert_new_player_controller(Identity::ZERO)
+            .entity_id();
+        let occupant_id = ecs.new_in_room(EntityBlob::default(), room_id).entity_id();
+
+        let new_map_id = ecs.regenerate_location_map(map_id, 9)?;
+
+        let new_room_id = ecs
+            .find(entrance_id)
+            .path()
+            .map(|p| p.destination_entity_id)
+            .ok_or("The entrance lost its path.")?;
+        assert_eq!(
+            ecs.find(new_room_id)
+                .location_map()
+                .map(|m| m.location_map_entity_id),
+            Some(new_map_id)
+        );
+        let location_of = |id| ecs.find(id).location().map(|l| l.location_entity_id);
+        assert_eq!(location_of(entrance_id), Some(outside_id));
+        assert_eq!(location_of(exit_id), Some(new_room_id));
+        assert_eq!(
+            ecs.find(exit_id).path().map(|p| p.destination_entity_id),
+            Some(outside_id)
+        );
+        assert_eq!(location_of(player_id), Some(new_room_id));
+        assert_eq!(location_of(occupant_id), None);
+        assert!(!ecs.iter_location().any(|l| l.entity_id() == occupant_id));
+        Ok(())
+    }
+
+    #[test]
+    fn deleting_a_room_evicts_its_occupants_and_deletes_its_paths() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = ReducerContext::__dummy();
+        let ecs = ctx.ecs();
+        let room_id = ecs.new().entity_id();
+        let outside_id = ecs.new().entity_id();
+        let entrance_id = ecs
+            .new_path(EntityBlob::default(), outside_id, room_id)
+            .entity_id();
+        let exit_id = ecs
+            .new_path(EntityBlob::default(), room_id, outside_id)
+            .entity_id();
+        let player_id = ecs
+            .new_in_room(EntityBlob::default(), room_id)
+            .upsert_new_player_controller(Identity::ZERO)
+            .entity_id();
+
+        ecs.delete_room(room_id);
+
+        let player = ecs.find(player_id);
+        assert_eq!(
+            (
+                player.location().is_some(),
+                player.player_controller().is_some()
+            ),
+            (false, true)
+        );
+        // The entrance goes with its destination, and the exit with its
+        // location, so no path is left dangling.
+        assert!(ecs.find(entrance_id).location().is_none());
+        assert!(ecs.find(exit_id).location().is_none());
+        assert!(ecs.iter_path().next().is_none());
+        assert_eq!(ecs.iter_location().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn players_are_reactivated_by_identity() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = ReducerContext::__dummy();
+        let ecs = ctx.ecs();
+        ecs.new().upsert_new_name("other".to_string());
+        let player_id = ecs
+            .new()
+            .upsert_new_name("player".to_string())
+            .upsert_new_player_controller(Identity::ZERO)
+            .entity_id();
+
+        let blob = ecs
+            .deactivate_player(player_id)
+            .ok_or("The player was not deactivated.")?;
+        assert_ne!(blob.blob_id, 0);
+        assert!(ecs.find_by_player_identity(Identity::ZERO).is_none());
+
+        let player = ecs
+            .reactivate_player(Identity::ZERO)
+            .ok_or("The player was not reactivated.")?;
+        assert_eq!(player.entity_id(), player_id);
+        assert_eq!(player.name().map(|n| n.name), Some("player".to_string()));
+        assert!(DeactivatedPlayer::iter(&ctx).next().is_none());
+        assert!(ecs.reactivate_player(Identity::ZERO).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn a_missing_blob_keeps_the_player_parked() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = ReducerContext::__dummy();
+        DeactivatedPlayer::insert(
+            &ctx,
+            DeactivatedPlayer {
+                identity: Identity::ZERO,
+                blob_id: 7,
+            },
+        );
+
+        assert!(ctx.ecs().reactivate_player(Identity::ZERO).is_none());
+        assert!(DeactivatedPlayer::find(&ctx, &Identity::ZERO).is_some());
+
+        let player_id = ctx
+            .ecs()
+            .new()
+            .upsert_new_player_controller(Identity::ZERO)
+            .entity_id();
+        let blob = ctx
+            .ecs()
+            .deactivate_player(player_id)
+            .ok_or("The player was not deactivated.")?;
+        assert_eq!(
+            DeactivatedPlayer::find(&ctx, &Identity::ZERO).map(|p| p.blob_id),
+            Some(blob.blob_id)
+        );
+        Ok(())
+    }
 }
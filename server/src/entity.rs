@@ -1,4 +1,8 @@
-use crate::{action::ActionId, asset::stat_block::StatBlock};
+use crate::{
+    action::ActionId,
+    asset::{reference::BlobNames, stat_block::StatBlock},
+    entity_handle_extension::{mark_total_stat_block_dirty, mark_traits_stat_block_dirty},
+};
 use ecs::entity;
 use spacetimedb::{Identity, SpacetimeType, Timestamp};
 
@@ -14,8 +18,26 @@ entity!(
         entity_id: EntityId,
     }
 
+    /// The table holds the blobs of deactivated entities, such as offline
+    /// players. Asset blobs are never rows of it, so validation keeps their
+    /// key at zero and `deactivated_entity_id` unset.
     #[blob(table = entity_blobs)]
-    pub struct EntityBlob;
+    pub struct EntityBlob {
+        #[primary_key]
+        #[auto_inc]
+        pub blob_id: u64,
+        #[deactivated_entity]
+        pub deactivated_entity_id: Option<EntityId>,
+        /// Only set on asset blobs, until their pack is loaded.
+        pub names: Option<BlobNames>,
+    }
+
+    #[changes(
+        table = component_changes,
+        cursors = component_change_cursors,
+        systems = [entity_prominence_system],
+    )]
+    pub struct ComponentChange;
 
     #[component(name in name_components)]
     struct NameComponent {
@@ -26,26 +48,40 @@ entity!(
     #[component(location in location_components)]
     struct LocationComponent {
         #[index(btree)]
+        #[relation(nullify)]
         pub location_entity_id: EntityId,
     }
 
+    #[track_changes]
     #[component(path in path_components)]
     struct PathComponent {
         #[index(btree)]
+        #[relation(cascade)]
         pub destination_entity_id: EntityId,
     }
 
     #[component(allegiance in allegiance_components)]
     struct AllegianceComponent {
         #[index(btree)]
+        #[relation(nullify)]
         pub allegiance_entity_id: EntityId,
     }
 
+    #[hooks(
+      on_insert = mark_total_stat_block_dirty,
+      on_update = mark_total_stat_block_dirty,
+      on_remove = mark_total_stat_block_dirty,
+    )]
     #[component(baseline in baseline_components)]
     struct BaselineComponent {
         pub baseline_id: u32,
     }
 
+    #[hooks(
+      on_insert = mark_traits_stat_block_dirty,
+      on_update = mark_traits_stat_block_dirty,
+      on_remove = mark_traits_stat_block_dirty,
+    )]
     #[component(traits in traits_components)]
     struct TraitsComponent {
         pub trait_ids: Vec<u32>,
@@ -55,15 +91,19 @@ entity!(
       equipment_stat_block_cache in equipment_stat_block_cache_components,
       status_stat_block_cache in status_stat_block_cache_components,
       traits_stat_block_cache in traits_stat_block_cache_components,
+      total_stat_block_cache in total_stat_block_cache_components,
     )]
     struct StatBlockCacheComponent {
         pub stat_block: StatBlock,
     }
 
     // TODO Equipment and Status Effects
+    #[track_changes(item, equipped)]
     #[component(
       traits_stat_block_dirty_flag in traits_stat_block_dirty_flag_components,
       total_stat_block_dirty_flag in total_stat_block_dirty_flag_components,
+      item in item_components,
+      equipped in equipped_components,
     )]
     struct FlagComponent {}
 
@@ -72,6 +112,7 @@ entity!(
         pub attack: i32,
     }
 
+    #[track_changes]
     #[component(hp in hp_components)]
     struct HpComponent {
         pub hp: i32,
@@ -87,12 +128,30 @@ entity!(
         pub mep: i32,
     }
 
+    #[component(experience in experience_components)]
+    struct ExperienceComponent {
+        pub xp: u32,
+        pub level: u32,
+    }
+
+    #[component(experience_reward in experience_reward_components)]
+    struct ExperienceRewardComponent {
+        pub xp: u32,
+    }
+
+    #[component(last_attacker in last_attacker_components)]
+    struct LastAttackerComponent {
+        pub attacker_entity_id: EntityId,
+    }
+
+    #[track_changes]
     #[component(player_controller in player_controller_components)]
     struct PlayerControllerComponent {
         #[unique]
         pub identity: Identity,
     }
 
+    #[track_changes]
     #[component(enemy_controller in enemy_controller_components)]
     struct EnemyControllerComponent {
         // TODO Add calibration properties?
@@ -132,6 +191,7 @@ entity!(
     #[component(
       entity_deletion_timer in entity_deletion_timer_components,
       player_deactivation_timer in player_deactivation_timer_components,
+      encounter_respawn_timer in encounter_respawn_timer_components,
     )]
     struct TimerComponent {
         pub timestamp: Timestamp,
@@ -139,11 +199,33 @@ entity!(
 
     #[component(location_map in location_map_components)]
     struct LocationMapComponent {
+        #[index(btree)]
+        #[relation(cascade)]
         pub location_map_entity_id: EntityId,
     }
 
+    #[component(location_map_source in location_map_source_components)]
+    struct LocationMapSourceComponent {
+        pub location_map_id: u32,
+    }
+
+    #[component(
+      pending_encounter in pending_encounter_components,
+      active_encounter in active_encounter_components,
+    )]
+    struct EncounterComponent {
+        pub encounter_id: u32,
+    }
+
     #[component(appearance_features in appearance_features_components)]
     struct AppearanceFeaturesComponent {
         pub appearance_feature_indexes: Vec<u32>,
     }
+
+    #[component(locale in locale_components)]
+    struct LocaleComponent {
+        /// Code of the player's `Locale`, which is the default one if no
+        /// loaded locale has it.
+        pub locale: String,
+    }
 );
@@ -1,19 +1,38 @@
 use crate::{
-    action::{actions, ActionId, ActionType},
-    asset::stat_block::StatBlock,
+    action::{Action, ActionId, ActionType},
+    asset::{r#trait::Trait, stat_block::StatBlock},
     entity::*,
+    prominence::{prominence, ProminenceFacts, ProminenceRule},
+    store::StoredRow,
 };
 
-pub trait EntityHandleExtension {
+/// Hook for the baseline component, whose stat block is part of the total.
+pub fn mark_total_stat_block_dirty(e: &EntityHandle, _: &BaselineComponent) {
+    e.clone().upsert_new_total_stat_block_dirty_flag();
+}
+
+/// Hook for the traits component, whose stat blocks are cached separately.
+pub fn mark_traits_stat_block_dirty(e: &EntityHandle, _: &TraitsComponent) {
+    e.clone().upsert_new_traits_stat_block_dirty_flag();
+}
+
+pub trait EntityHandleExtension: Sized {
     fn apply_stat_block(self, stat_block: StatBlock) -> Self;
     fn set_mhp(self, mhp: i32) -> Self;
     fn set_defense(self, defense: i32) -> Self;
     fn set_mep(self, mep: i32) -> Self;
+    fn set_hp(self, hp: i32) -> Result<Self, String>;
+    fn set_ep(self, ep: i32) -> Result<Self, String>;
+    fn kill(self) -> Result<Self, String>;
+    fn revive(self) -> Result<Self, String>;
     fn set_actions(self, action_ids: Vec<ActionId>) -> Self;
     fn set_appearance_feature_ids(self, appearance_feature_ids: Vec<u32>) -> Self;
+    fn add_trait(self, trait_id: u32) -> Result<Self, String>;
+    fn remove_trait(self, trait_id: u32) -> Self;
     fn generate_prominence(self) -> Self;
     fn allegiance_id(&self) -> Option<u64>;
     fn is_ally(&self, other_entity_id: u64) -> bool;
+    fn is_dead(&self) -> bool;
     fn set_queued_action_state(self, action_id: ActionId, target_entity_id: u64) -> Self;
     fn shift_queued_action_state(self) -> Self;
     fn can_target_other(&self, other_entity_id: u64, action_id: ActionId) -> bool;
@@ -23,6 +42,8 @@ impl<'a, T: WithEntityHandle<'a> + InstantiateEntityBlob> EntityHandleExtension
     fn apply_stat_block(self, stat_block: StatBlock) -> Self {
         self.to_handle()
             .clone()
+            .upsert_new_total_stat_block_cache(stat_block.clone())
+            .into_handle()
             .upsert_new_attack(stat_block.attack)
             .set_mhp(stat_block.mhp)
             .set_mep(stat_block.mep)
@@ -65,6 +86,56 @@ impl<'a, T: WithEntityHandle<'a> + InstantiateEntityBlob> EntityHandleExtension
         self
     }
 
+    fn set_hp(self, hp: i32) -> Result<Self, String> {
+        let e = self.to_handle();
+        let mut c = e
+            .hp()
+            .ok_or_else(|| format!("Entity {} has no hp.", e.entity_id()))?;
+        c.hp = hp.clamp(0, c.mhp.max(0));
+        e.update_hp(c);
+        Ok(self)
+    }
+
+    fn set_ep(self, ep: i32) -> Result<Self, String> {
+        let e = self.to_handle();
+        let mut c = e
+            .ep()
+            .ok_or_else(|| format!("Entity {} has no ep.", e.entity_id()))?;
+        c.ep = ep.clamp(0, c.mep.max(0));
+        e.update_ep(c);
+        Ok(self)
+    }
+
+    /// Queues lethal damage rather than zeroing hp, so `hp_system` runs the
+    /// death as it would for an attack and credits the last attacker.
+    fn kill(self) -> Result<Self, String> {
+        let e = self.to_handle();
+        let mut c = e
+            .hp()
+            .ok_or_else(|| format!("Entity {} has no hp.", e.entity_id()))?;
+        c.accumulated_damage = c
+            .accumulated_damage
+            .max(c.hp.saturating_add(c.accumulated_healing));
+        e.update_hp(c);
+        Ok(self)
+    }
+
+    /// Restores full hp and clears what a death left behind: pending damage,
+    /// the killer credited for it, and any scheduled deletion.
+    fn revive(self) -> Result<Self, String> {
+        let e = self.to_handle();
+        let mut c = e
+            .hp()
+            .ok_or_else(|| format!("Entity {} has no hp.", e.entity_id()))?;
+        c.hp = c.mhp.max(0);
+        c.accumulated_damage = 0;
+        c.accumulated_healing = 0;
+        e.update_hp(c);
+        e.delete_last_attacker();
+        e.delete_entity_deletion_timer();
+        Ok(self)
+    }
+
     fn set_actions(self, action_ids: Vec<ActionId>) -> Self {
         let e = self.to_handle();
         if let Some(mut c) = e.actions() {
@@ -88,20 +159,49 @@ impl<'a, T: WithEntityHandle<'a> + InstantiateEntityBlob> EntityHandleExtension
         self
     }
 
-    fn generate_prominence(self) -> Self {
+    fn add_trait(self, trait_id: u32) -> Result<Self, String> {
         let e = self.to_handle();
-        let mut prominence = 0;
-        if e.path().is_some() {
-            prominence |= 1 << 8;
+        if Trait::find(&e.ecs(), &trait_id).is_none() {
+            return Err(format!("Cannot find trait {}.", trait_id));
         }
-        // TODO Add other controller types.
-        if e.player_controller().is_some() {
-            prominence |= 1 << 7;
+        if let Some(mut c) = e.traits() {
+            if c.trait_ids.contains(&trait_id) {
+                return Ok(self);
+            }
+            c.trait_ids.push(trait_id);
+            e.update_traits(c);
+        } else {
+            e.insert_new_traits(vec![trait_id]);
         }
-        if e.hp().is_some() {
-            prominence |= 1 << 6;
+        Ok(self)
+    }
+
+    // No existence check, so ids of traits removed from the assets can still be dropped.
+    fn remove_trait(self, trait_id: u32) -> Self {
+        let e = self.to_handle();
+        if let Some(mut c) = e.traits() {
+            if c.trait_ids.contains(&trait_id) {
+                c.trait_ids.retain(|id| *id != trait_id);
+                e.update_traits(c);
+            }
+        }
+        self
+    }
+
+    fn generate_prominence(self) -> Self {
+        let e = self.to_handle();
+        let rules: Vec<ProminenceRule> = ProminenceRule::iter(&e.ecs()).collect();
+        let prominence = prominence(&rules, &ProminenceFacts::of(e));
+        match e.entity_prominence() {
+            Some(p) if p.prominence == prominence => {}
+            Some(mut p) => {
+                p.prominence = prominence;
+                e.update_entity_prominence(p);
+            }
+            None => {
+                e.insert_new_entity_prominence(prominence);
+            }
         }
-        e.insert_new_entity_prominence(prominence);
         self
     }
 
@@ -126,6 +226,11 @@ impl<'a, T: WithEntityHandle<'a> + InstantiateEntityBlob> EntityHandleExtension
         }
     }
 
+    /// Entities without hp, such as rooms and paths, are never dead.
+    fn is_dead(&self) -> bool {
+        self.to_handle().hp().is_some_and(|hp| hp.hp <= 0)
+    }
+
     fn set_queued_action_state(self, action_id: ActionId, target_entity_id: u64) -> Self {
         let e = self.to_handle();
         e.delete_queued_action_state();
@@ -149,22 +254,32 @@ impl<'a, T: WithEntityHandle<'a> + InstantiateEntityBlob> EntityHandleExtension
 
     fn can_target_other(&self, other_entity_id: u64, action_id: ActionId) -> bool {
         let e = self.to_handle();
-        if let Some(a) = e.ecs().db.actions().id().find(action_id) {
+        if let Some(a) = Action::find(&e.ecs(), &action_id) {
             let o = e.ecs().find(other_entity_id);
             // TODO Add same-location check as a separate function, which is also used to validate individual effects before they're resolved.
-            match a.action_type {
-                ActionType::Attack => o.hp().is_some() && !self.is_ally(other_entity_id),
-                ActionType::Buff => o.hp().is_some() && self.is_ally(other_entity_id),
-                ActionType::Equip => true,     // WIP
-                ActionType::Inventory => true, // WIP
-                ActionType::Move => o.path().is_some(),
-            }
+            can_target(
+                &a.action_type,
+                o.hp().is_some(),
+                o.path().is_some(),
+                self.is_ally(other_entity_id),
+            )
         } else {
             false
         }
     }
 }
 
+/// Whether an action of `action_type` may target an entity with the given traits.
+pub fn can_target(action_type: &ActionType, has_hp: bool, has_path: bool, is_ally: bool) -> bool {
+    match action_type {
+        ActionType::Attack => has_hp && !is_ally,
+        ActionType::Buff => has_hp && is_ally,
+        ActionType::Equip => true,     // WIP
+        ActionType::Inventory => true, // WIP
+        ActionType::Move => has_path,
+    }
+}
+
 pub trait InstantiateEntityBlobExtension {
     fn instantiate_blob_dirty(self, blob: EntityBlob) -> Self;
 }
@@ -173,9 +288,72 @@ impl<'a, T: WithEntityHandle<'a> + EntityHandleExtension + InstantiateEntityBlob
     InstantiateEntityBlobExtension for T
 {
     fn instantiate_blob_dirty(self, blob: EntityBlob) -> Self {
-        let e = self.to_handle();
-        e.insert_new_traits_stat_block_dirty_flag();
-        e.insert_new_total_stat_block_dirty_flag();
+        // Baseline and traits hooks mark their stat blocks dirty, but an entity
+        // with neither still needs its total stat block applied.
+        self.to_handle()
+            .clone()
+            .upsert_new_total_stat_block_dirty_flag();
         self.instantiate_blob(blob)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EntityHandleExtension;
+    use crate::{entity::*, system::hp_system};
+    use ecs::WithEcs;
+
+    #[test]
+    fn remove_trait_drops_ids_without_a_trait_row() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        let e = ctx.ecs().new();
+        e.clone().upsert_new_traits(vec![7, 9]);
+
+        let e = e.remove_trait(7);
+
+        assert_eq!(e.traits().map(|t| t.trait_ids), Some(vec![9]));
+        assert!(e.add_trait(7).is_err());
+    }
+
+    #[test]
+    fn kill_leaves_the_death_to_hp_system() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        let killer = ctx.ecs().new();
+        killer.clone().upsert_new_experience(0, 1);
+        let e = ctx.ecs().new();
+        e.clone().upsert_new_hp(6, 10, 0, 1, 3);
+        e.clone().upsert_new_experience_reward(5);
+        e.clone().upsert_new_last_attacker(killer.entity_id());
+
+        let e = e.kill()?;
+        hp_system(ctx.ecs());
+
+        assert_eq!(e.hp().map(|hp| hp.hp), Some(0));
+        assert_eq!(killer.experience().map(|x| x.xp), Some(5));
+        Ok(())
+    }
+
+    #[test]
+    fn revive_clears_death_and_deletion_state() -> Result<(), String> {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        let killer = ctx.ecs().new();
+        let e = ctx.ecs().new();
+        e.clone().upsert_new_hp(0, 10, 0, 4, 0);
+        e.clone().upsert_new_last_attacker(killer.entity_id());
+        e.clone().upsert_new_entity_deletion_timer(ctx.timestamp);
+
+        let e = e.revive()?;
+
+        assert_eq!(
+            e.hp()
+                .map(|hp| (hp.hp, hp.accumulated_damage, hp.accumulated_healing)),
+            Some((10, 0, 0))
+        );
+        assert!(e.last_attacker().is_none());
+        assert!(e.entity_deletion_timer().is_none());
+        Ok(())
+    }
+}
@@ -7,11 +7,17 @@ secador::secador!(
     ],
     {
         use ecs::Ecs;
-        use spacetimedb::{table, SpacetimeType, Table, Timestamp};
+        use spacetimedb::{table, SpacetimeType, Timestamp};
 
         use crate::{
-            action::{ActionEffect, ActionId},
-            entity::{hp_components, location_components, path_components},
+            action::{ActionEffect, ActionId, AttackRenderer, Intensity},
+            combat::AttackOutcome,
+            entity::{
+                __hp__Option, __hp__OptionGet, __last_attacker__Option, __location__Option,
+                __location__OptionGet, __path__OptionGet, FindEntityHandle,
+            },
+            entity_handle_extension::EntityHandleExtension,
+            store::StoredRow,
         };
 
         #[derive(Debug, Clone, SpacetimeType)]
@@ -30,6 +36,12 @@ secador::secador!(
             pub owner_entity_id: u64,
             pub event_type: EventType,
             pub target_entity_id: u64,
+            /// Roll result of an attack effect, or `None` for any other event.
+            pub attack_outcome: Option<AttackOutcome>,
+            /// Intensity of the action step behind an effect event.
+            pub intensity: Option<Intensity>,
+            /// Renderer of the action behind an event, if it is an attack.
+            pub renderer: Option<AttackRenderer>,
         }
 
         impl EntityEvent {
@@ -42,53 +54,44 @@ secador::secador!(
                         ActionEffect::Buff(_) => true,
                         ActionEffect::Rest => false,
                         ActionEffect::Move => {
-                            match ecs.db.path_components().entity_id().find(target_entity_id) {
-                                None => {}
-                                Some(path_component) => {
-                                    match ecs
-                                        .db
-                                        .location_components()
-                                        .entity_id()
-                                        .find(self.owner_entity_id)
-                                    {
-                                        None => {}
-                                        Some(mut location_component) => {
-                                            location_component.location_entity_id =
-                                                path_component.destination_entity_id;
-                                            ecs.db
-                                                .location_components()
-                                                .entity_id()
-                                                .update(location_component);
-                                        }
-                                    }
-                                }
+                            if let (Some(path_component), Some(mut location_component)) = (
+                                ecs.find(target_entity_id).path(),
+                                ecs.find(self.owner_entity_id).location(),
+                            ) {
+                                location_component.location_entity_id =
+                                    path_component.destination_entity_id;
+                                ecs.find(self.owner_entity_id)
+                                    .update_location(location_component);
                             }
                             true
                         }
                         ActionEffect::Attack(damage) => {
-                            let target_hp =
-                                ecs.db.hp_components().entity_id().find(target_entity_id);
-                            match target_hp {
-                                None => {}
-                                Some(mut target_hp) => {
-                                    target_hp.accumulated_damage += damage;
-                                    ecs.db.hp_components().entity_id().update(target_hp);
-                                }
+                            let target = ecs.find(target_entity_id);
+                            if let Some(mut target_hp) = target.hp() {
+                                target_hp.accumulated_damage += damage.amount;
+                                target.update_hp(target_hp);
+                                target.upsert_new_last_attacker(self.owner_entity_id);
                             }
                             true
                         }
                         ActionEffect::Heal(heal) => {
-                            let target_hp =
-                                ecs.db.hp_components().entity_id().find(target_entity_id);
-                            match target_hp {
-                                None => {}
-                                Some(mut target_hp) => {
-                                    target_hp.accumulated_healing += heal;
-                                    ecs.db.hp_components().entity_id().update(target_hp);
-                                }
+                            let target = ecs.find(target_entity_id);
+                            if let Some(mut target_hp) = target.hp() {
+                                target_hp.accumulated_healing += heal;
+                                target.update_hp(target_hp);
                             }
                             true
                         }
+                        ActionEffect::AddTrait(trait_id) => {
+                            if let Err(err) = ecs.find(target_entity_id).add_trait(*trait_id) {
+                                log::warn!("Event {} failed to add trait. {}", self.id, err);
+                            }
+                            true
+                        }
+                        ActionEffect::RemoveTrait(trait_id) => {
+                            ecs.find(target_entity_id).remove_trait(*trait_id);
+                            true
+                        }
                         ActionEffect::Take => true,    // WIP
                         ActionEffect::Drop => true,    // WIP
                         ActionEffect::Equip => true,   // WIP
@@ -98,7 +101,7 @@ secador::secador!(
 
                 if is_observable {
                     self.id = 0;
-                    ecs.db.observable_events().insert(self);
+                    EntityEvent::insert(&ecs, self);
                 }
             }
         }
@@ -125,6 +128,9 @@ secador::secador!(
                     owner_entity_id,
                     event_type,
                     target_entity_id,
+                    attack_outcome: None,
+                    intensity: None,
+                    renderer: None,
                 }
             }
         }
@@ -10,12 +10,19 @@
 #![deny(clippy::unimplemented)]
 #![deny(clippy::unreachable)]
 
-mod action;
-mod appearance;
-mod asset;
+pub mod action;
+mod admin;
+pub mod appearance;
+pub mod asset;
+mod combat;
 mod ecs_extension;
-mod entity;
+pub mod entity;
 mod entity_handle_extension;
 mod event;
+mod look;
+pub mod prominence;
 mod reducers;
+#[cfg(test)]
+mod simulation;
+mod store;
 mod system;
@@ -0,0 +1,163 @@
+use std::cmp::Reverse;
+
+use spacetimedb::{view, SpacetimeType, ViewContext};
+
+use crate::{
+    action::{actions__view, ActionId},
+    appearance::{AppearanceExtension, Locale},
+    entity::*,
+    entity_handle_extension::can_target,
+};
+
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct LookOccupant {
+    pub entity_id: u64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub prominence: i32,
+    /// The caller's actions which may target this occupant.
+    pub action_ids: Vec<ActionId>,
+}
+
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct LookExit {
+    pub entity_id: u64,
+    pub name: Option<String>,
+    pub destination_entity_id: u64,
+    pub destination_name: Option<String>,
+    /// The caller's actions which may target this exit.
+    pub action_ids: Vec<ActionId>,
+}
+
+/// What the caller sees at their location, localized to their locale.
+#[derive(Debug, Clone, SpacetimeType)]
+pub struct Look {
+    pub room_entity_id: u64,
+    pub room_name: Option<String>,
+    pub room_description: Option<String>,
+    /// Occupants other than the caller and exits, most prominent first.
+    pub occupants: Vec<LookOccupant>,
+    pub exits: Vec<LookExit>,
+    /// The caller's actions which may target the caller.
+    pub self_action_ids: Vec<ActionId>,
+}
+
+struct Looker<'a> {
+    ctx: &'a ViewContext,
+    entity_id: u64,
+    allegiance_entity_id: Option<u64>,
+    action_ids: Vec<ActionId>,
+}
+
+impl Looker<'_> {
+    fn name(&self, entity_id: u64) -> Option<String> {
+        self.ctx
+            .db
+            .name_components()
+            .entity_id()
+            .find(entity_id)
+            .map(|n| n.name)
+    }
+
+    fn action_ids(&self, target_entity_id: u64) -> Vec<ActionId> {
+        let db = &self.ctx.db;
+        let has_hp = db.hp_components().entity_id().find(target_entity_id).is_some();
+        let has_path = db
+            .path_components()
+            .entity_id()
+            .find(target_entity_id)
+            .is_some();
+        let is_ally = target_entity_id == self.entity_id
+            || self.allegiance_entity_id.is_some_and(|a| {
+                db.allegiance_components()
+                    .entity_id()
+                    .find(target_entity_id)
+                    .is_some_and(|o| o.allegiance_entity_id == a)
+            });
+        self.action_ids
+            .iter()
+            .filter(|id| {
+                db.actions()
+                    .id()
+                    .find(**id)
+                    .is_some_and(|a| can_target(&a.action_type, has_hp, has_path, is_ally))
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[view(accessor = look, public)]
+fn look(ctx: &ViewContext) -> Option<Look> {
+    let db = &ctx.db;
+    let entity_id = db
+        .player_controller_components()
+        .identity()
+        .find(ctx.sender())?
+        .entity_id;
+    let room_entity_id = db
+        .location_components()
+        .entity_id()
+        .find(entity_id)?
+        .location_entity_id;
+    let locale: Locale = ctx.player_locale(ctx.sender());
+    let looker = Looker {
+        ctx,
+        entity_id,
+        allegiance_entity_id: db
+            .allegiance_components()
+            .entity_id()
+            .find(entity_id)
+            .map(|a| a.allegiance_entity_id),
+        action_ids: db
+            .actions_components()
+            .entity_id()
+            .find(entity_id)
+            .map(|a| a.action_ids)
+            .unwrap_or_default(),
+    };
+
+    let mut occupants = vec![];
+    let mut exits = vec![];
+    for l in db
+        .location_components()
+        .location_entity_id()
+        .filter(room_entity_id)
+    {
+        if l.entity_id == entity_id {
+            continue;
+        }
+        if let Some(p) = db.path_components().entity_id().find(l.entity_id) {
+            exits.push(LookExit {
+                entity_id: l.entity_id,
+                name: looker.name(l.entity_id),
+                destination_entity_id: p.destination_entity_id,
+                destination_name: looker.name(p.destination_entity_id),
+                action_ids: looker.action_ids(l.entity_id),
+            });
+        } else {
+            occupants.push(LookOccupant {
+                entity_id: l.entity_id,
+                name: looker.name(l.entity_id),
+                description: ctx.describe_entity(l.entity_id, &locale),
+                prominence: db
+                    .entity_prominence_components()
+                    .entity_id()
+                    .find(l.entity_id)
+                    .map_or(0, |p| p.prominence),
+                action_ids: looker.action_ids(l.entity_id),
+            });
+        }
+    }
+    occupants.sort_by_key(|o| (Reverse(o.prominence), o.entity_id));
+    exits.sort_by_key(|e| e.entity_id);
+
+    Some(Look {
+        room_entity_id,
+        room_name: looker.name(room_entity_id),
+        room_description: ctx.describe_entity(room_entity_id, &locale),
+        occupants,
+        exits,
+        self_action_ids: looker.action_ids(entity_id),
+    })
+}
@@ -0,0 +1,128 @@
+use spacetimedb::{table, SpacetimeType};
+
+use crate::entity::*;
+
+/// A fact about an entity which contributes to its prominence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SpacetimeType)]
+pub enum ProminenceCondition {
+    Path,
+    PlayerController,
+    EnemyController,
+    Hp,
+    /// Has hp, and it has reached 0.
+    Downed,
+    Item,
+    Equipped,
+}
+
+/// Prominence is the sum of the weights of every matching rule. Rules are
+/// asset content, at most one per condition.
+#[table(accessor = prominence_rules)]
+#[derive(Debug, Clone)]
+pub struct ProminenceRule {
+    #[primary_key]
+    pub condition: ProminenceCondition,
+    pub weight: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProminenceFacts {
+    pub path: bool,
+    pub player_controller: bool,
+    pub enemy_controller: bool,
+    pub hp: Option<i32>,
+    pub item: bool,
+    pub equipped: bool,
+}
+
+impl ProminenceFacts {
+    pub fn of(e: &EntityHandle) -> Self {
+        Self {
+            path: e.path().is_some(),
+            player_controller: e.player_controller().is_some(),
+            enemy_controller: e.enemy_controller().is_some(),
+            hp: e.hp().map(|hp| hp.hp),
+            item: e.item().is_some(),
+            equipped: e.equipped().is_some(),
+        }
+    }
+
+    fn matches(&self, condition: ProminenceCondition) -> bool {
+        match condition {
+            ProminenceCondition::Path => self.path,
+            ProminenceCondition::PlayerController => self.player_controller,
+            ProminenceCondition::EnemyController => self.enemy_controller,
+            ProminenceCondition::Hp => self.hp.is_some(),
+            ProminenceCondition::Downed => self.hp.is_some_and(|hp| hp <= 0),
+            ProminenceCondition::Item => self.item,
+            ProminenceCondition::Equipped => self.equipped,
+        }
+    }
+}
+
+pub fn prominence(rules: &[ProminenceRule], facts: &ProminenceFacts) -> i32 {
+    rules
+        .iter()
+        .filter(|r| facts.matches(r.condition))
+        .fold(0i32, |total, r| total.saturating_add(r.weight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(condition: ProminenceCondition, weight: i32) -> ProminenceRule {
+        ProminenceRule { condition, weight }
+    }
+
+    #[test]
+    fn roles_are_ordered_by_prominence() {
+        // Paths sort first, then players, enemies, other creatures and finally
+        // items. Downed creatures lose the weight of being a creature.
+        let rules = [
+            rule(ProminenceCondition::Path, 1 << 8),
+            rule(ProminenceCondition::PlayerController, 1 << 7),
+            rule(ProminenceCondition::EnemyController, 1 << 6),
+            rule(ProminenceCondition::Hp, 1 << 5),
+            rule(ProminenceCondition::Downed, -(1 << 5)),
+            rule(ProminenceCondition::Equipped, 1 << 4),
+            rule(ProminenceCondition::Item, 1 << 3),
+        ];
+        let p = |facts: ProminenceFacts| prominence(&rules, &facts);
+        let path = p(ProminenceFacts {
+            path: true,
+            ..Default::default()
+        });
+        let player = p(ProminenceFacts {
+            player_controller: true,
+            hp: Some(10),
+            ..Default::default()
+        });
+        let enemy = p(ProminenceFacts {
+            enemy_controller: true,
+            hp: Some(10),
+            ..Default::default()
+        });
+        let downed_enemy = p(ProminenceFacts {
+            enemy_controller: true,
+            hp: Some(0),
+            ..Default::default()
+        });
+        let equipped_item = p(ProminenceFacts {
+            item: true,
+            equipped: true,
+            ..Default::default()
+        });
+        let item = p(ProminenceFacts {
+            item: true,
+            ..Default::default()
+        });
+
+        assert!(path > player);
+        assert!(player > enemy);
+        assert!(enemy > downed_enemy);
+        assert!(downed_enemy > equipped_item);
+        assert!(equipped_item > item);
+        assert!(item > p(ProminenceFacts::default()));
+    }
+}
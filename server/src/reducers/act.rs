@@ -7,7 +7,7 @@ use crate::{
 
 #[reducer]
 pub fn act(ctx: &ReducerContext, action_id: ActionId, target_entity_id: u64) -> Result<(), String> {
-    if let Some(p) = ctx.ecs().from_player_identity(ctx.sender()) {
+    if let Some(p) = ctx.ecs().find_by_player_identity(ctx.sender()) {
         if p.can_target_other(target_entity_id, action_id) {
             p.set_queued_action_state(action_id, target_entity_id);
             Ok(())
@@ -0,0 +1,28 @@
+use spacetimedb::{reducer, Identity, ReducerContext, Table};
+
+use crate::admin::{admins, Admin, AdminGuard};
+
+#[reducer]
+pub fn grant_admin(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    ctx.require_admin()?;
+    if ctx.is_admin(identity) {
+        return Ok(());
+    }
+    ctx.db.admins().insert(Admin { identity });
+    ctx.audit(format!("grant_admin {}", identity));
+    Ok(())
+}
+
+#[reducer]
+pub fn revoke_admin(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    ctx.require_admin()?;
+    if !ctx.is_admin(identity) {
+        return Err(format!("{} is not an admin.", identity));
+    }
+    if ctx.db.admins().count() <= 1 {
+        return Err("Cannot revoke the last admin.".to_string());
+    }
+    ctx.db.admins().identity().delete(identity);
+    ctx.audit(format!("revoke_admin {}", identity));
+    Ok(())
+}
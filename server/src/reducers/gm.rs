@@ -0,0 +1,162 @@
+use ecs::{Ecs, WithEcs};
+use spacetimedb::{reducer, ReducerContext};
+
+use crate::{
+    admin::AdminGuard,
+    asset::{baseline::baselines, encounter::encounter_blobs},
+    ecs_extension::EcsExtension,
+    entity::*,
+    entity_handle_extension::EntityHandleExtension,
+};
+
+fn find_entity(ecs: Ecs, entity_id: u64) -> Result<EntityHandle, String> {
+    if ecs.db.entities().id().find(entity_id).is_some() {
+        Ok(ecs.find(entity_id))
+    } else {
+        Err(format!("Cannot find entity {}.", entity_id))
+    }
+}
+
+fn find_room(ecs: Ecs, room_entity_id: u64) -> Result<EntityHandle, String> {
+    let room = find_entity(ecs, room_entity_id)?;
+    if room.location_map().is_some() {
+        Ok(room)
+    } else {
+        Err(format!("Entity {} is not a room.", room_entity_id))
+    }
+}
+
+#[reducer]
+pub fn gm_teleport(
+    ctx: &ReducerContext,
+    entity_id: u64,
+    room_entity_id: u64,
+) -> Result<(), String> {
+    ctx.require_admin()?;
+    let ecs = ctx.ecs();
+    find_room(ecs, room_entity_id)?;
+    find_entity(ecs, entity_id)?.upsert_new_location(room_entity_id);
+    ctx.audit(format!("gm_teleport {} {}", entity_id, room_entity_id));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_spawn_encounter_blob(
+    ctx: &ReducerContext,
+    encounter_blob_id: u32,
+    room_entity_id: u64,
+) -> Result<(), String> {
+    ctx.require_admin()?;
+    let ecs = ctx.ecs();
+    find_room(ecs, room_entity_id)?;
+    let blob = ecs
+        .db
+        .encounter_blobs()
+        .id()
+        .find(encounter_blob_id)
+        .ok_or(format!("Cannot find encounter blob {}.", encounter_blob_id))?
+        .blob;
+    let e = ecs.new_in_room(blob, room_entity_id);
+    ctx.audit(format!(
+        "gm_spawn_encounter_blob {} {} -> {}",
+        encounter_blob_id,
+        room_entity_id,
+        e.entity_id()
+    ));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_spawn_with_traits(
+    ctx: &ReducerContext,
+    baseline_id: u32,
+    trait_ids: Vec<u32>,
+    room_entity_id: u64,
+) -> Result<(), String> {
+    ctx.require_admin()?;
+    let ecs = ctx.ecs();
+    find_room(ecs, room_entity_id)?;
+    if ecs.db.baselines().id().find(baseline_id).is_none() {
+        return Err(format!("Cannot find baseline {}.", baseline_id));
+    }
+    let mut e = ecs
+        .new_in_room(EntityBlob::default(), room_entity_id)
+        .upsert_new_baseline(baseline_id)
+        .into_handle();
+    for trait_id in &trait_ids {
+        e = e.add_trait(*trait_id)?;
+    }
+    ctx.audit(format!(
+        "gm_spawn_with_traits {} {:?} {} -> {}",
+        baseline_id,
+        trait_ids,
+        room_entity_id,
+        e.entity_id()
+    ));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_set_hp(ctx: &ReducerContext, entity_id: u64, hp: i32) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.set_hp(hp)?;
+    ctx.audit(format!("gm_set_hp {} {}", entity_id, hp));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_set_ep(ctx: &ReducerContext, entity_id: u64, ep: i32) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.set_ep(ep)?;
+    ctx.audit(format!("gm_set_ep {} {}", entity_id, ep));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_add_trait(ctx: &ReducerContext, entity_id: u64, trait_id: u32) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.add_trait(trait_id)?;
+    ctx.audit(format!("gm_add_trait {} {}", entity_id, trait_id));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_remove_trait(ctx: &ReducerContext, entity_id: u64, trait_id: u32) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.remove_trait(trait_id);
+    ctx.audit(format!("gm_remove_trait {} {}", entity_id, trait_id));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_kill(ctx: &ReducerContext, entity_id: u64) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.kill()?;
+    ctx.audit(format!("gm_kill {}", entity_id));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_revive(ctx: &ReducerContext, entity_id: u64) -> Result<(), String> {
+    ctx.require_admin()?;
+    find_entity(ctx.ecs(), entity_id)?.revive()?;
+    ctx.audit(format!("gm_revive {}", entity_id));
+    Ok(())
+}
+
+#[reducer]
+pub fn gm_regenerate_map(
+    ctx: &ReducerContext,
+    location_map_entity_id: u64,
+    rng_seed: u64,
+) -> Result<(), String> {
+    ctx.require_admin()?;
+    let new_location_map_entity_id = ctx
+        .ecs()
+        .regenerate_location_map(location_map_entity_id, rng_seed)?;
+    ctx.audit(format!(
+        "gm_regenerate_map {} {} -> {}",
+        location_map_entity_id, rng_seed, new_location_map_entity_id
+    ));
+    Ok(())
+}
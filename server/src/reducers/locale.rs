@@ -0,0 +1,23 @@
+use ecs::WithEcs;
+use spacetimedb::{reducer, ReducerContext};
+
+use crate::{
+    appearance::{locales, Locale},
+    ecs_extension::EcsExtension,
+    entity::*,
+};
+
+/// Sets the locale used for the calling player's descriptions, by the code of
+/// a loaded locale or of the default one.
+#[reducer]
+pub fn set_locale(ctx: &ReducerContext, locale: String) -> Result<(), String> {
+    if locale != Locale::default().code && ctx.db.locales().code().find(&locale).is_none() {
+        return Err(format!("Cannot find locale {:?}.", locale));
+    }
+    let p = ctx
+        .ecs()
+        .find_by_player_identity(ctx.sender())
+        .ok_or("Cannot find a player entity.")?;
+    p.upsert_new_locale(locale);
+    Ok(())
+}
@@ -2,6 +2,7 @@ use ecs::WithEcs;
 use spacetimedb::{reducer, ReducerContext, ScheduleAt, Table, TimeDuration};
 
 use crate::{
+    admin::{admins, Admin, AdminGuard},
     asset::ReducerContextExtension,
     ecs_extension::EcsExtension,
     entity::*,
@@ -9,11 +10,20 @@ use crate::{
 };
 
 mod act;
+mod admin;
+mod gm;
+mod locale;
 mod system;
 mod system_timer;
 
 #[reducer(init)]
 pub fn init(ctx: &ReducerContext) -> Result<(), String> {
+    // The publishing identity becomes the first admin.
+    ctx.db.admins().insert(Admin {
+        identity: ctx.sender(),
+    });
+    log::info!("Bootstrapped {} as admin.", ctx.sender());
+
     ctx.db.system_timers().insert(SystemTimer {
         scheduled_id: 0,
         scheduled_at: ScheduleAt::Interval(TimeDuration::from_micros(1000000)),
@@ -27,7 +37,7 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
     if ctx.get_new_player_blob().is_none() {
         log::debug!("Connected {} before asset initialization.", ctx.sender());
         Ok(())
-    } else if let Some(p) = ctx.ecs().from_player_identity(ctx.sender()) {
+    } else if let Some(p) = ctx.ecs().find_by_player_identity(ctx.sender()) {
         p.delete_player_deactivation_timer();
         log::debug!(
             "Reconnected {} to {} and removed deactivation timer.",
@@ -35,6 +45,13 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
             p.entity_id()
         );
         Ok(())
+    } else if let Some(p) = ctx.ecs().reactivate_player(ctx.sender()) {
+        log::debug!(
+            "Reconnected {} to reactivated {}.",
+            ctx.sender(),
+            p.entity_id()
+        );
+        Ok(())
     } else {
         match ctx.ecs().new_player(ctx.sender()) {
             Ok(p) => {
@@ -45,10 +62,12 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
                 );
                 Ok(())
             }
+            Err(err) if ctx.is_admin(ctx.sender()) => {
+                // Admins may connect to repair content that prevents player creation.
+                log::warn!("Connected admin {} without a player. {}", ctx.sender(), err);
+                Ok(())
+            }
             Err(err) => {
-                // WIP Check if connected user is admin and DB is not initialized.
-                // If admin and DB is not ready, do not emit any error.
-                // Otherwise, emit the error.
                 log::debug!(
                     "Connected {}, but no player could be found or created. {}",
                     ctx.sender(),
@@ -62,7 +81,7 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
 
 #[reducer(client_disconnected)]
 pub fn identity_disconnected(ctx: &ReducerContext) {
-    if let Some(e) = ctx.ecs().from_player_identity(ctx.sender()) {
+    if let Some(e) = ctx.ecs().find_by_player_identity(ctx.sender()) {
         if e.player_deactivation_timer().is_none() {
             if let Some(timestamp) = ctx
                 .timestamp
@@ -0,0 +1,474 @@
+//! Headless runs of the server systems over the in-memory tables, for
+//! regression tests. A run loads an `AssetPack`, connects scripted players and
+//! calls `execute_all_systems` once per tick, recording the `EntityEvent`
+//! stream. The clock starts at the run's seed and advances by the system timer
+//! interval, so every reducer RNG, which is seeded from the timestamp, repeats
+//! from run to run.
+//!
+//! Recordings are compared against `src/simulation/golden/<name>.txt`. Run the
+//! tests with `UPDATE_GOLDEN=1` to rewrite the golden files after an intended
+//! rules or balance change, and review their diff.
+
+use std::{fs, path::PathBuf};
+
+use ecs::WithEcs;
+use spacetimedb::{Identity, ReducerContext, TimeDuration, Timestamp};
+
+use crate::{
+    action::ActionId,
+    asset::{self, validation, AssetPack, SpecialEntityBlob, SpecialEntityBlobKey},
+    ecs_extension::EcsExtension,
+    entity::*,
+    entity_handle_extension::EntityHandleExtension,
+    event::EntityEvent,
+    store::StoredRow,
+    system::execute_all_systems,
+};
+
+/// Interval of the system timer scheduled by `init`.
+const TICK_MICROS: i64 = 1_000_000;
+
+/// What a scripted player does, as the matching reducer would for them.
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
+    /// Connects the player, creating it on its first connection.
+    Connect,
+    /// Queues an action, as `act` does.
+    Act(ActionId, Target),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Target {
+    Entity(u64),
+    /// The first living enemy in the player's room.
+    EnemyInRoom,
+}
+
+pub struct Simulation {
+    seed: u32,
+    script: Vec<(u32, u8, Command)>,
+}
+
+/// The identity of scripted player `player`.
+pub fn player_identity(player: u8) -> Identity {
+    let mut bytes = [0; 32];
+    bytes[0] = player;
+    Identity::from_byte_array(bytes)
+}
+
+secador::secador!(
+    assets,
+    [
+        actions,
+        action_steps,
+        baselines,
+        traits,
+        levels,
+        encounter_blobs,
+        encounters,
+        location_map_themes,
+        location_maps,
+        location_map_connections,
+        prominence_rules,
+    ],
+    {
+        /// Loads the pack as `push_assets` does, except for the appearance
+        /// features, which only `look` reads.
+        fn load_asset_pack(ctx: &ReducerContext, mut asset_pack: AssetPack) -> Result<(), String> {
+            let errors = asset::load_errors(ctx, &mut asset_pack);
+            if !errors.is_empty() {
+                return Err(validation::report(&errors));
+            }
+
+            seca!(1);
+            for row in asset_pack.__assets {
+                StoredRow::insert(ctx, row);
+            }
+
+            for b in asset_pack.instantiate_entity_blobs {
+                ctx.ecs().new().instantiate_blob(b);
+            }
+
+            SpecialEntityBlob::insert(
+                ctx,
+                SpecialEntityBlob {
+                    key: SpecialEntityBlobKey::NewPlayer,
+                    blob: asset_pack.new_player_blob,
+                },
+            );
+
+            Ok(())
+        }
+    }
+);
+
+impl Simulation {
+    /// Starts a run on empty in-memory tables with the pack loaded.
+    pub fn new(asset_pack: AssetPack, seed: u32) -> Result<Self, String> {
+        ecs::memory::reset();
+        let simulation = Self {
+            seed,
+            script: vec![],
+        };
+        load_asset_pack(&simulation.context(0), asset_pack)?;
+        Ok(simulation)
+    }
+
+    /// Has `player` issue `command` at the start of `tick`, before the systems
+    /// run.
+    pub fn at(mut self, tick: u32, player: u8, command: Command) -> Self {
+        self.script.push((tick, player, command));
+        self
+    }
+
+    fn context(&self, tick: u32) -> ReducerContext {
+        let mut ctx = ReducerContext::__dummy();
+        let micros = i64::from(self.seed) + i64::from(tick) * TICK_MICROS;
+        ctx.timestamp = Timestamp::UNIX_EPOCH
+            .checked_add(TimeDuration::from_micros(micros))
+            .unwrap_or(Timestamp::UNIX_EPOCH);
+        ctx
+    }
+
+    /// Runs `ticks` ticks, returning one line per scripted command and event,
+    /// followed by the final state of each scripted player.
+    pub fn run(self, ticks: u32) -> String {
+        let mut recording = String::new();
+        for tick in 0..ticks {
+            let ctx = self.context(tick);
+            for (_, player, command) in self.script.iter().filter(|(at, ..)| *at == tick) {
+                let outcome = issue(&ctx, player_identity(*player), *command);
+                recording += &format!("{tick:>4} player {player} {command:?}: {outcome}\n");
+            }
+            execute_all_systems(ctx.ecs());
+            // Event tables keep no rows past the transaction that inserted them.
+            for event in ecs::memory::take::<EntityEvent>("observable_events") {
+                recording += &format!("{tick:>4} {}\n", describe(&event));
+            }
+        }
+        let ctx = self.context(ticks);
+        let mut players: Vec<u8> = self.script.iter().map(|(_, player, _)| *player).collect();
+        players.sort_unstable();
+        players.dedup();
+        for player in players {
+            recording += &format!("end  player {player}: {}\n", summarize(&ctx, player));
+        }
+        recording
+    }
+}
+
+fn issue(ctx: &ReducerContext, identity: Identity, command: Command) -> String {
+    let ecs = ctx.ecs();
+    match command {
+        Command::Connect => match ecs.find_by_player_identity(identity) {
+            Some(p) => format!("reconnected entity {}", p.entity_id()),
+            None => match ecs.new_player(identity) {
+                Ok(p) => format!("created entity {}", p.entity_id()),
+                Err(err) => err,
+            },
+        },
+        Command::Act(action_id, target) => {
+            let Some(p) = ecs.find_by_player_identity(identity) else {
+                return "no player".to_string();
+            };
+            let target_entity_id = match target {
+                Target::Entity(entity_id) => Some(entity_id),
+                Target::EnemyInRoom => p.location().and_then(|l| {
+                    ecs.iter_location_by_location_entity_id(&l.location_entity_id)
+                        .with_enemy_controller()
+                        .find(|e| e.hp().is_some_and(|hp| hp.hp > 0))
+                        .map(|e| e.entity_id())
+                }),
+            };
+            match target_entity_id {
+                Some(t) if p.can_target_other(t, action_id) => {
+                    p.set_queued_action_state(action_id, t);
+                    format!("queued on entity {t}")
+                }
+                Some(t) => format!("cannot target entity {t}"),
+                None => "no target".to_string(),
+            }
+        }
+    }
+}
+
+fn summarize(ctx: &ReducerContext, player: u8) -> String {
+    let Some(p) = ctx.ecs().find_by_player_identity(player_identity(player)) else {
+        return "no player".to_string();
+    };
+    let mut line = format!("entity {}", p.entity_id());
+    if let Some(hp) = p.hp() {
+        line += &format!(" hp {}/{}", hp.hp, hp.mhp);
+    }
+    if let Some(experience) = p.experience() {
+        line += &format!(" level {} xp {}", experience.level, experience.xp);
+    }
+    if let Some(traits) = p.traits() {
+        line += &format!(" traits {:?}", traits.trait_ids);
+    }
+    line
+}
+
+fn describe(event: &EntityEvent) -> String {
+    let EntityEvent {
+        id,
+        owner_entity_id,
+        event_type,
+        target_entity_id,
+        attack_outcome,
+        intensity,
+        renderer,
+        ..
+    } = event;
+    let mut line = format!("#{id} {owner_entity_id} -> {target_entity_id} {event_type:?}");
+    for detail in [
+        attack_outcome.as_ref().map(|o| format!("{o:?}")),
+        intensity.map(|i| format!("{i:?}")),
+        renderer.as_ref().map(|r| format!("{r:?}")),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        line += " ";
+        line += &detail;
+    }
+    line
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "src", "simulation", "golden"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{name}.txt"))
+}
+
+/// Compares a recording with its golden file, first rewriting the file when
+/// `UPDATE_GOLDEN` is set.
+pub fn assert_golden(name: &str, recording: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, recording));
+        assert!(written.is_ok(), "cannot write {}", path.display());
+    }
+    let golden = fs::read_to_string(&path).unwrap_or_default();
+    let first_difference = recording
+        .lines()
+        .zip(golden.lines())
+        .position(|(r, g)| r != g)
+        .unwrap_or(recording.lines().count().min(golden.lines().count()));
+    assert!(
+        recording == golden,
+        "recording differs from {} at line {}; rerun with UPDATE_GOLDEN=1 to accept it\n\
+         recorded: {:?}\n\
+         golden:   {:?}",
+        path.display(),
+        first_difference + 1,
+        recording.lines().nth(first_difference),
+        golden.lines().nth(first_difference),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::{Action, ActionEffect, ActionStep, ActionType, Damage, DamageType, Intensity},
+        asset::{
+            baseline::Baseline,
+            encounter::{Encounter, EncounterBlob},
+            level::Level,
+            location_map::{EncounterIdSample, EncounterIdsSampler, Layout, LocationMap},
+            location_map_theme::{EntityBlobSample, EntityBlobsSampler, LocationMapTheme},
+            r#trait::Trait,
+            stat_block::StatBlock,
+        },
+    };
+
+    const PUNCH: ActionId = 1;
+
+    fn sampler(blob: EntityBlob) -> EntityBlobsSampler {
+        EntityBlobsSampler {
+            selections: vec![EntityBlobSample { weight: 1, blob }],
+        }
+    }
+
+    /// A cave of one room holding a pair of slimes, after the example content.
+    fn cave_pack() -> AssetPack {
+        AssetPack {
+            version: 1,
+            actions: vec![Action {
+                id: PUNCH,
+                name: "punch".to_string(),
+                action_type: ActionType::Attack,
+                renderer: None,
+            }],
+            action_steps: vec![
+                ActionStep {
+                    id: 1,
+                    action_id: PUNCH,
+                    sequence_index: 0,
+                    action_effect: ActionEffect::Rest,
+                    intensity: Intensity::Normal,
+                    trait_name: None,
+                },
+                ActionStep {
+                    id: 2,
+                    action_id: PUNCH,
+                    sequence_index: 1,
+                    action_effect: ActionEffect::Attack(Damage {
+                        amount: 3,
+                        damage_type: DamageType::Physical,
+                    }),
+                    intensity: Intensity::Normal,
+                    trait_name: None,
+                },
+            ],
+            appearance_features: vec![],
+            locales: vec![],
+            localized_appearance_features: vec![],
+            baselines: vec![
+                Baseline {
+                    id: 1,
+                    name: "human".to_string(),
+                    stat_block: StatBlock {
+                        action_ids: vec![PUNCH],
+                        attack: 2,
+                        mhp: 30,
+                        mep: 10,
+                        ..Default::default()
+                    },
+                    action_names: vec![],
+                },
+                Baseline {
+                    id: 2,
+                    name: "slime".to_string(),
+                    stat_block: StatBlock {
+                        action_ids: vec![PUNCH],
+                        attack: 1,
+                        mhp: 8,
+                        ..Default::default()
+                    },
+                    action_names: vec![],
+                },
+            ],
+            traits: vec![Trait {
+                id: 1,
+                name: "veteran".to_string(),
+                stat_block: StatBlock {
+                    mhp: 5,
+                    ..Default::default()
+                },
+                action_names: vec![],
+            }],
+            levels: vec![
+                Level {
+                    level: 1,
+                    xp: 0,
+                    trait_ids: vec![],
+                    trait_names: vec![],
+                },
+                Level {
+                    level: 2,
+                    xp: 10,
+                    trait_ids: vec![],
+                    trait_names: vec!["veteran".to_string()],
+                },
+            ],
+            encounter_blobs: vec![
+                EncounterBlob {
+                    id: 1,
+                    name: "enemy".to_string(),
+                    blob: EntityBlob::default(),
+                },
+                EncounterBlob {
+                    id: 2,
+                    name: "slime".to_string(),
+                    blob: EntityBlob {
+                        baseline: Some(BaselineComponent::new(2)),
+                        hp: Some(HpComponent::new(8, 8, 0, 0, 0)),
+                        experience_reward: Some(ExperienceRewardComponent::new(10)),
+                        enemy_controller: Some(EnemyControllerComponent::new()),
+                        ..Default::default()
+                    },
+                },
+            ],
+            encounters: vec![Encounter {
+                id: 1,
+                categoric_blob_id: 0,
+                categoric_blob_name: Some("enemy".to_string()),
+                blob_ids: vec![],
+                blob_names: vec!["slime".to_string(); 2],
+                respawn_delay: None,
+            }],
+            location_map_themes: vec![LocationMapTheme {
+                id: 1,
+                name: "cave".to_string(),
+                decorations_selector: EntityBlobsSampler { selections: vec![] },
+                min_decoration_count: 0,
+                max_decoration_count: 0,
+                paths_selector: sampler(EntityBlob::default()),
+                rooms_selector: sampler(EntityBlob::default()),
+            }],
+            location_maps: vec![LocationMap {
+                id: 1,
+                name: "cave".to_string(),
+                theme_id: 0,
+                theme_name: Some("cave".to_string()),
+                layout: Layout::Path,
+                rng_seed: None,
+                extra_room_count: 0,
+                main_room_count: 1,
+                loop_count: 0,
+                encounter_ids_sampler: EncounterIdsSampler {
+                    selections: vec![EncounterIdSample { weight: 1, id: 1 }],
+                },
+                min_encounter_count: 1,
+                max_encounter_count: 2,
+            }],
+            location_map_connections: vec![],
+            prominence_rules: vec![],
+            instantiate_entity_blobs: vec![EntityBlob {
+                name: Some(NameComponent::new("allegiance1".to_string())),
+                ..Default::default()
+            }],
+            new_player_blob: EntityBlob {
+                baseline: Some(BaselineComponent::new(1)),
+                hp: Some(HpComponent::new(30, 30, 0, 0, 0)),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn a_player_clears_the_cave() -> Result<(), String> {
+        let recording = Simulation::new(cave_pack(), 7)?
+            .at(0, 1, Command::Connect)
+            .at(1, 1, Command::Act(PUNCH, Target::Entity(1)))
+            .at(3, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .at(6, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .at(9, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .at(12, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .at(15, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .at(18, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+            .run(24);
+
+        assert_golden("a_player_clears_the_cave", &recording);
+        Ok(())
+    }
+
+    #[test]
+    fn runs_with_the_same_seed_record_the_same_events() -> Result<(), String> {
+        let run = |seed| -> Result<String, String> {
+            Ok(Simulation::new(cave_pack(), seed)?
+                .at(0, 1, Command::Connect)
+                .at(2, 1, Command::Act(PUNCH, Target::EnemyInRoom))
+                .run(8))
+        };
+
+        assert_eq!(run(3)?, run(3)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! Row access for the plain tables systems read and write, such as the asset
+//! tables and `observable_events`. Like the `entity!` tables, their rows live
+//! in `ecs::memory` when the crate is compiled for `cargo test`, so systems
+//! can run headless.
+
+#[cfg(not(test))]
+use spacetimedb::Table;
+use spacetimedb::{Identity, ReducerContext};
+
+#[cfg(not(test))]
+use crate::{
+    action::{action_steps, actions},
+    asset::{
+        baseline::baselines,
+        encounter::{encounter_blobs, encounters},
+        level::levels,
+        location_map::{location_map_connections, location_maps},
+        location_map_theme::location_map_themes,
+        r#trait::traits,
+        special_entity_blobs,
+    },
+    ecs_extension::deactivated_players,
+    event::observable_events,
+    prominence::prominence_rules,
+};
+use crate::{
+    action::{Action, ActionId, ActionStep},
+    asset::{
+        baseline::Baseline,
+        encounter::{Encounter, EncounterBlob},
+        level::Level,
+        location_map::{LocationMap, LocationMapConnection},
+        location_map_theme::LocationMapTheme,
+        r#trait::Trait,
+        SpecialEntityBlob, SpecialEntityBlobKey,
+    },
+    ecs_extension::DeactivatedPlayer,
+    event::EntityEvent,
+    prominence::{ProminenceCondition, ProminenceRule},
+};
+
+/// Assigns the key of a row inserted with zero, as the host does for
+/// `#[auto_inc]` columns.
+#[cfg(test)]
+fn auto_inc(table: &'static str, key: &mut u64) {
+    if *key == 0 {
+        *key = ecs::memory::next_id(table);
+    }
+}
+
+/// Keeps the key of a row as given.
+#[cfg(test)]
+fn fixed_key<K>(_: &'static str, _: &mut K) {}
+
+pub trait StoredRow: Sized {
+    type Key;
+    fn find(ctx: &ReducerContext, key: &Self::Key) -> Option<Self>;
+    fn iter(ctx: &ReducerContext) -> impl Iterator<Item = Self>;
+    fn insert(ctx: &ReducerContext, row: Self) -> Self;
+    fn delete(ctx: &ReducerContext, key: &Self::Key) -> bool;
+}
+
+// `unique` is the `#[unique]` column the test backend checks on insert, or the
+// key again for tables without one.
+secador::secador!(
+    (table, Row, key, Key, key_sequence, unique),
+    [
+        (actions, Action, id, ActionId, fixed_key, name),
+        (action_steps, ActionStep, id, u64, fixed_key, id),
+        (baselines, Baseline, id, u32, fixed_key, name),
+        (traits, Trait, id, u32, fixed_key, name),
+        (levels, Level, level, u32, fixed_key, level),
+        (encounter_blobs, EncounterBlob, id, u32, fixed_key, name),
+        (encounters, Encounter, id, u32, fixed_key, id),
+        (
+            location_map_themes,
+            LocationMapTheme,
+            id,
+            u32,
+            fixed_key,
+            name
+        ),
+        (location_maps, LocationMap, id, u32, fixed_key, id),
+        (
+            location_map_connections,
+            LocationMapConnection,
+            id,
+            u32,
+            fixed_key,
+            id
+        ),
+        (
+            prominence_rules,
+            ProminenceRule,
+            condition,
+            ProminenceCondition,
+            fixed_key,
+            condition
+        ),
+        (
+            special_entity_blobs,
+            SpecialEntityBlob,
+            key,
+            SpecialEntityBlobKey,
+            fixed_key,
+            key
+        ),
+        (observable_events, EntityEvent, id, u64, auto_inc, id),
+        (
+            deactivated_players,
+            DeactivatedPlayer,
+            identity,
+            Identity,
+            fixed_key,
+            identity
+        ),
+    ],
+    {
+        seca!(2);
+        #[cfg(not(test))]
+        impl StoredRow for __Row {
+            type Key = __Key;
+            fn find(ctx: &ReducerContext, key: &Self::Key) -> Option<Self> {
+                ctx.db.__table().__key().find(key)
+            }
+            fn iter(ctx: &ReducerContext) -> impl Iterator<Item = Self> {
+                ctx.db.__table().iter()
+            }
+            fn insert(ctx: &ReducerContext, row: Self) -> Self {
+                ctx.db.__table().insert(row)
+            }
+            fn delete(ctx: &ReducerContext, key: &Self::Key) -> bool {
+                ctx.db.__table().__key().delete(key)
+            }
+        }
+        #[cfg(test)]
+        // Tables without a `#[unique]` column compare their key twice.
+        #[allow(clippy::eq_op, clippy::nonminimal_bool)]
+        impl StoredRow for __Row {
+            type Key = __Key;
+            fn find(ctx: &ReducerContext, key: &Self::Key) -> Option<Self> {
+                Self::iter(ctx).find(|row| row.__key == *key)
+            }
+            fn iter(_: &ReducerContext) -> impl Iterator<Item = Self> {
+                ecs::memory::rows::<Self>(stringify!(__table)).into_iter()
+            }
+            fn insert(_: &ReducerContext, mut row: Self) -> Self {
+                __key_sequence(stringify!(__table), &mut row.__key);
+                ecs::memory::insert_unique(stringify!(__table), row.clone(), |r: &Self, row| {
+                    r.__key == row.__key || r.__unique == row.__unique
+                });
+                row
+            }
+            fn delete(_: &ReducerContext, key: &Self::Key) -> bool {
+                ecs::memory::remove(stringify!(__table), |row: &Self| row.__key == *key).is_some()
+            }
+        }
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::StoredRow;
+    use crate::asset::{r#trait::Trait, stat_block::StatBlock};
+
+    #[test]
+    #[should_panic(expected = "Duplicate unique column value")]
+    fn memory_rows_reject_a_duplicate_unique_column() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        let veteran = |id| Trait {
+            id,
+            name: "veteran".to_string(),
+            stat_block: StatBlock::default(),
+            action_names: vec![],
+        };
+        Trait::insert(&ctx, veteran(1));
+        Trait::insert(&ctx, veteran(2));
+    }
+}
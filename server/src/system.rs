@@ -1,19 +1,37 @@
 use crate::{
-    action::{ActionEffect, ActionHandle},
+    action::{ActionEffect, ActionHandle, Damage, DamageType},
     asset::{
-        baseline::baselines, location_map::location_maps, r#trait::traits, stat_block::StatBlock,
+        baseline::Baseline,
+        encounter::Encounter,
+        level::{levels_gained, Level},
+        location_map::LocationMap,
+        r#trait::Trait,
+        stat_block::StatBlock,
     },
+    combat::resolve_attack,
+    ecs_extension::EcsExtension,
     entity::*,
     entity_handle_extension::EntityHandleExtension,
-    event::{observable_events, EventQueue, EventType, NewEvent},
+    event::{EntityEvent, EventQueue, EventType, NewEvent},
+    store::StoredRow,
 };
 use ecs::Ecs;
-use spacetimedb::{rand::seq::SliceRandom, Table};
-use std::cmp::{max, min};
+use spacetimedb::rand::seq::SliceRandom;
+use std::{
+    cmp::{max, min},
+    collections::BTreeSet,
+};
 
 pub fn hp_system(ecs: Ecs) {
     for mut e in ecs.iter_hp() {
         let hp = e.hp_mut();
+        if hp.accumulated_healing == 0
+            && hp.accumulated_damage == 0
+            && (0..=max(0, hp.mhp)).contains(&hp.hp)
+        {
+            continue;
+        }
+        let was_alive = hp.hp > 0;
         hp.hp = max(
             0,
             min(
@@ -23,7 +41,54 @@ pub fn hp_system(ecs: Ecs) {
         );
         hp.accumulated_healing = 0;
         hp.accumulated_damage = 0;
-        e.update_hp();
+        let is_alive = hp.hp > 0;
+        let e = e.update_hp();
+        if was_alive && !is_alive {
+            grant_kill_experience(ecs, e.entity_id());
+        }
+    }
+}
+
+fn grant_kill_experience(ecs: Ecs, entity_id: u64) {
+    let e = ecs.find(entity_id);
+    let (Some(reward), Some(last_attacker)) = (e.experience_reward(), e.last_attacker()) else {
+        return;
+    };
+    if let Some(mut a) = ecs.find(last_attacker.attacker_entity_id).with_experience() {
+        let experience = a.experience_mut();
+        experience.xp = experience.xp.saturating_add(reward.xp);
+        log::debug!(
+            "Entity {} gained {} xp for defeating {}.",
+            a.entity_id(),
+            reward.xp,
+            entity_id
+        );
+        a.update_experience();
+    }
+}
+
+pub fn experience_system(ecs: Ecs) {
+    let levels: Vec<_> = Level::iter(&ecs).collect();
+    for mut e in ecs.iter_experience() {
+        let experience = e.experience();
+        let gained = levels_gained(&levels, experience.level, experience.xp);
+        let Some(last) = gained.last() else {
+            continue;
+        };
+        log::debug!("Entity {} reached level {}.", e.entity_id(), last.level);
+        e.experience_mut().level = last.level;
+
+        let new_trait_ids: Vec<u32> = gained
+            .iter()
+            .flat_map(|l| l.trait_ids.iter().copied())
+            .collect();
+        let e = e.update_experience();
+        // `add_trait` skips traits the entity already has.
+        for trait_id in new_trait_ids {
+            if let Err(err) = e.clone().add_trait(trait_id) {
+                log::warn!("Entity {} cannot gain a trait: {}", e.entity_id(), err);
+            }
+        }
     }
 }
 
@@ -37,15 +102,21 @@ pub fn ep_system(ecs: Ecs) {
 
 pub fn shift_queued_action_system(ecs: Ecs) {
     for e in ecs.iter_queued_action_state() {
+        if e.is_dead() {
+            e.delete_queued_action_state();
+            continue;
+        }
         if e.action_state().is_none() {
             let e = e.into_handle().shift_queued_action_state();
             if let Some(a) = e.action_state() {
                 if e.can_target_other(a.target_entity_id, a.action_id) {
-                    ecs.db.observable_events().insert(ecs.new_event(
+                    let mut event = ecs.new_event(
                         a.entity_id,
                         EventType::StartAction(a.action_id),
                         a.target_entity_id,
-                    ));
+                    );
+                    event.renderer = ActionHandle::from_id(&ecs, a.action_id).renderer();
+                    EntityEvent::insert(&ecs, event);
                 } else {
                     log::warn!(
                         "Entity {} has invalid queued action target {} for action {}",
@@ -62,47 +133,85 @@ pub fn shift_queued_action_system(ecs: Ecs) {
 
 pub fn action_system(ecs: Ecs) {
     let mut queue = EventQueue::new();
+    let mut attack_rng = ecs.rng();
     for mut e in ecs.iter_action_state() {
+        if e.is_dead() {
+            e.delete_action_state();
+            continue;
+        }
         let action_state = e.action_state();
         let entity_id = action_state.entity_id;
         let action_handle = ActionHandle::from_id(&ecs, action_state.action_id);
 
-        let effect = action_handle.effect(action_state.sequence_index);
-        if let Some(ref effect) = effect {
+        if let Some(step) = action_handle.step(action_state.sequence_index) {
+            let renderer = action_handle.renderer();
+            let presented = |mut event: EntityEvent| {
+                event.intensity = Some(step.intensity);
+                event.renderer = renderer.clone();
+                event
+            };
+            let effect = &step.action_effect;
             match effect {
                 ActionEffect::Buff(_) => {
-                    queue.emit_early(ecs.new_event(
+                    queue.emit_early(presented(ecs.new_event(
                         entity_id,
                         EventType::ActionEffect(effect.to_owned()),
                         action_state.target_entity_id,
-                    ));
+                    )));
                 }
                 ActionEffect::Attack(damage) => {
                     let attack = e.attack().map(|c| c.attack).unwrap_or(0);
+                    let attacker_stat_block = e
+                        .total_stat_block_cache()
+                        .map(|c| c.stat_block)
+                        .unwrap_or_default();
                     let t = ecs.find(action_state.target_entity_id);
-                    let target_defense = t.hp().map(|c| c.defense).unwrap_or(0);
-                    queue.emit_middle(ecs.new_event(
-                        entity_id,
-                        EventType::ActionEffect(ActionEffect::Attack(max(
+                    let target_stat_block = t.total_stat_block_cache().map(|c| c.stat_block);
+                    // Physical damage uses the live defense on the hp component,
+                    // which may differ from the cached stat block.
+                    let target_resistance = match damage.damage_type {
+                        DamageType::Physical => t.hp().map(|c| c.defense).unwrap_or(0),
+                        damage_type => target_stat_block
+                            .as_ref()
+                            .map(|s| s.resistance(damage_type))
+                            .unwrap_or(0),
+                    };
+                    let (attack_outcome, amount) = resolve_attack(
+                        &mut attack_rng,
+                        max(
                             0,
-                            damage + attack - target_defense,
-                        ))),
+                            damage
+                                .amount
+                                .saturating_add(attack)
+                                .saturating_sub(target_resistance),
+                        ),
+                        &attacker_stat_block,
+                        &target_stat_block.unwrap_or_default(),
+                    );
+                    let mut event = ecs.new_event(
+                        entity_id,
+                        EventType::ActionEffect(ActionEffect::Attack(Damage {
+                            amount,
+                            damage_type: damage.damage_type,
+                        })),
                         action_state.target_entity_id,
-                    ));
+                    );
+                    event.attack_outcome = Some(attack_outcome);
+                    queue.emit_middle(presented(event));
                 }
                 ActionEffect::Heal(_) => {
-                    queue.emit_middle(ecs.new_event(
+                    queue.emit_middle(presented(ecs.new_event(
                         entity_id,
                         EventType::ActionEffect(effect.to_owned()),
                         action_state.target_entity_id,
-                    ));
+                    )));
                 }
                 _ => {
-                    queue.emit_late(ecs.new_event(
+                    queue.emit_late(presented(ecs.new_event(
                         entity_id,
                         EventType::ActionEffect(effect.to_owned()),
                         action_state.target_entity_id,
-                    ));
+                    )));
                 }
             }
         }
@@ -123,13 +232,25 @@ pub fn action_system(ecs: Ecs) {
     queue.resolve(ecs);
 }
 
+/// Recomputes prominence for entities whose prominence facts changed since
+/// the last run, writing it only where the value changed.
 pub fn entity_prominence_system(ecs: Ecs) {
-    for p in ecs.iter_entity_prominence() {
-        p.delete_entity_prominence();
-    }
-    for entity in ecs.db.entities().iter() {
-        ecs.find(entity.id).generate_prominence();
+    const SYSTEM: &str = "entity_prominence_system";
+    let entity_ids: BTreeSet<u64> = ecs
+        .iter_path_changes(SYSTEM)
+        .chain(ecs.iter_player_controller_changes(SYSTEM))
+        .chain(ecs.iter_enemy_controller_changes(SYSTEM))
+        .chain(ecs.iter_hp_changes(SYSTEM))
+        .chain(ecs.iter_item_changes(SYSTEM))
+        .chain(ecs.iter_equipped_changes(SYSTEM))
+        .map(|(e, _)| e.entity_id())
+        .collect();
+    for entity_id in entity_ids {
+        if let Some(e) = ecs.try_find(entity_id) {
+            e.generate_prominence();
+        }
     }
+    ecs.advance_change_cursor(SYSTEM);
 }
 
 pub fn entity_deletion_timer_system(ecs: Ecs) {
@@ -143,34 +264,38 @@ pub fn entity_deletion_timer_system(ecs: Ecs) {
 pub fn player_deactivation_timer_system(ecs: Ecs) {
     for t in ecs.iter_player_deactivation_timer() {
         if t.player_deactivation_timer().timestamp <= ecs.timestamp {
-            t.delete_player_deactivation_timer().delete_location();
+            // The rooms may be gone once the player returns, so the activation
+            // system places them again.
             // WIP Add a checkpoint_location_component to place player after login.
+            let p = t.delete_player_deactivation_timer();
+            p.delete_location();
+            ecs.deactivate_player(p.entity_id());
         }
     }
 }
 
 pub fn entity_stats_system(ecs: Ecs) {
     for f in ecs.iter_traits_stat_block_dirty_flag() {
-        if let Some(c) = ecs.find(f.entity_id()).with_traits() {
-            let mut stat_block = StatBlock::default();
-            for id in &c.traits().trait_ids {
-                if let Some(t) = ecs.db.traits().id().find(id) {
+        let mut stat_block = StatBlock::default();
+        if let Some(c) = f.traits() {
+            for id in &c.trait_ids {
+                if let Some(t) = Trait::find(&ecs, id) {
                     stat_block += &t.stat_block;
                 }
             }
-
-            f.upsert_new_total_stat_block_dirty_flag()
-                .upsert_new_traits_stat_block_cache(stat_block)
-                .delete_traits_stat_block_dirty_flag()
-                .into_handle();
         }
+
+        f.upsert_new_total_stat_block_dirty_flag()
+            .upsert_new_traits_stat_block_cache(stat_block)
+            .delete_traits_stat_block_dirty_flag()
+            .into_handle();
     }
 
     for f in ecs.iter_total_stat_block_dirty_flag() {
         log::debug!("Entity {} is computing total stat block.", f.entity_id());
         let mut stat_block = { f.baseline() }
-            .and_then(|b| ecs.db.baselines().id().find(b.baseline_id))
-            .map_or_else(|| StatBlock::default(), |b| b.stat_block);
+            .and_then(|b| Baseline::find(&ecs, &b.baseline_id))
+            .map_or_else(StatBlock::default, |b| b.stat_block);
 
         if let Some(t) = f.traits_stat_block_cache() {
             stat_block += &t.stat_block;
@@ -184,10 +309,9 @@ pub fn entity_stats_system(ecs: Ecs) {
 
 pub fn player_activation_system(ecs: Ecs) {
     for p in ecs.iter_player_controller() {
-        // WIP Do NOT add a location if player is inactive. Consider adding a flag when deactivating.
         if p.location().is_none() {
             // WIP Add checkpoint component to select a specific location map.
-            if let Some(m) = ecs.db.location_maps().iter().next() {
+            if let Some(m) = LocationMap::iter(&ecs).next() {
                 let map_generation_result = m.generate_entities(ecs);
                 // WIP Add checkpoint location to select a specific room.
                 // WIP Consider adding rng seed to checkpoint to allow same map to regen.
@@ -199,15 +323,75 @@ pub fn player_activation_system(ecs: Ecs) {
     }
 }
 
+fn is_room_cleared(ecs: Ecs, room_entity_id: u64) -> bool {
+    !ecs.iter_location_by_location_entity_id(&room_entity_id)
+        .any(|e| e.enemy_controller().is_some() && e.hp().is_none_or(|hp| hp.hp > 0))
+}
+
+pub fn encounter_system(ecs: Ecs) {
+    for t in ecs.iter_encounter_respawn_timer() {
+        if t.encounter_respawn_timer().timestamp <= ecs.timestamp {
+            let t = t.delete_encounter_respawn_timer();
+            if let Some(a) = t.active_encounter() {
+                t.delete_active_encounter();
+                t.upsert_new_pending_encounter(a.encounter_id);
+            }
+        }
+    }
+
+    for r in ecs
+        .iter_active_encounter()
+        .without_encounter_respawn_timer()
+    {
+        if !is_room_cleared(ecs, r.entity_id()) {
+            continue;
+        }
+        let respawn_timestamp = Encounter::find(&ecs, &r.active_encounter().encounter_id)
+            .and_then(|e| e.respawn_delay)
+            .and_then(|d| ecs.timestamp.checked_add(d));
+        if let Some(timestamp) = respawn_timestamp {
+            r.insert_new_encounter_respawn_timer(timestamp);
+        } else {
+            r.delete_active_encounter();
+        }
+    }
+
+    for p in ecs.iter_player_controller().with_location() {
+        let room = ecs.find(p.location().location_entity_id);
+        if let Some(r) = room.with_pending_encounter() {
+            let encounter_id = r.pending_encounter().encounter_id;
+            if let Some(encounter) = Encounter::find(&ecs, &encounter_id) {
+                log::debug!(
+                    "Player {} entered room {} and spawned encounter {}.",
+                    p.entity_id(),
+                    r.entity_id(),
+                    encounter_id
+                );
+                encounter.populate(r.to_handle());
+            }
+            r.delete_pending_encounter()
+                .upsert_new_active_encounter(encounter_id);
+        }
+    }
+}
+
 pub fn enemy_control_system(ecs: Ecs) {
     // TODO Build cache of players-by-location.
-    let mut players: Vec<_> = ecs.iter_player_controller().with_location().collect();
+    let mut players: Vec<_> = ecs
+        .iter_player_controller()
+        .with_location()
+        .filter(|p| !p.is_dead())
+        .collect();
     let mut player_shuffle_rng = ecs.rng();
-    for e in ecs.iter_enemy_controller().with_location().with_actions() {
-        if e.action_state().is_some() {
+    for e in ecs
+        .iter_enemy_controller()
+        .with_location()
+        .with_actions()
+        .without_action_state()
+    {
+        if e.is_dead() {
             continue;
         }
-
         let mut p = None;
         players.shuffle(&mut player_shuffle_rng);
         for t in &players {
@@ -241,7 +425,253 @@ pub fn execute_all_systems(ecs: Ecs) {
     entity_prominence_system(ecs);
     entity_deletion_timer_system(ecs);
     player_deactivation_timer_system(ecs);
+    experience_system(ecs);
     entity_stats_system(ecs);
     player_activation_system(ecs);
+    encounter_system(ecs);
     enemy_control_system(ecs);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        action_system, encounter_system, entity_prominence_system, experience_system, hp_system,
+    };
+    use crate::{
+        action::{Action, ActionEffect, ActionStep, ActionType, Damage, DamageType, Intensity},
+        asset::{
+            encounter::{Encounter, EncounterBlob},
+            level::Level,
+            r#trait::Trait,
+            stat_block::StatBlock,
+        },
+        entity::*,
+        prominence::{ProminenceCondition, ProminenceRule},
+        store::StoredRow,
+    };
+    use ecs::WithEcs;
+    use spacetimedb::{Identity, ReducerContext, TimeDuration};
+
+    // Like every test of this crate, this runs against `ecs::memory`; the
+    // SpacetimeDB table code is only exercised by a published module.
+    #[test]
+    fn hp_system_applies_damage_and_rewards_the_killer() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        let attacker = ctx.ecs().new();
+        attacker.clone().upsert_new_experience(0, 1);
+        let target = ctx.ecs().new();
+        target.clone().upsert_new_hp(5, 10, 0, 7, 0);
+        target.clone().upsert_new_experience_reward(3);
+        target
+            .clone()
+            .upsert_new_last_attacker(attacker.entity_id());
+        let survivor = ctx.ecs().new();
+        survivor.clone().upsert_new_hp(5, 10, 0, 2, 4);
+
+        hp_system(ctx.ecs());
+
+        assert_eq!(target.hp().map(|hp| hp.hp), Some(0));
+        assert_eq!(
+            survivor
+                .hp()
+                .map(|hp| (hp.hp, hp.accumulated_damage, hp.accumulated_healing)),
+            Some((7, 0, 0))
+        );
+        assert_eq!(attacker.experience().map(|e| e.xp), Some(3));
+    }
+
+    #[test]
+    fn level_ups_grant_each_trait_once() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        for (id, name) in [(1, "veteran"), (2, "hardy")] {
+            Trait::insert(
+                &ctx,
+                Trait {
+                    id,
+                    name: name.to_string(),
+                    stat_block: StatBlock::default(),
+                    action_names: vec![],
+                },
+            );
+        }
+        Level::insert(
+            &ctx,
+            Level {
+                level: 2,
+                xp: 10,
+                trait_ids: vec![1, 2],
+                trait_names: vec![],
+            },
+        );
+        let e = ctx.ecs().new();
+        e.clone().upsert_new_experience(10, 1);
+        e.clone().upsert_new_traits(vec![1]);
+
+        experience_system(ctx.ecs());
+
+        assert_eq!(e.experience().map(|x| x.level), Some(2));
+        assert_eq!(e.traits().map(|t| t.trait_ids), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn physical_attacks_use_live_defense_over_the_cached_stat_block() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        Action::insert(
+            &ctx,
+            Action {
+                id: 1,
+                name: "punch".to_string(),
+                action_type: ActionType::Attack,
+                renderer: None,
+            },
+        );
+        ActionStep::insert(
+            &ctx,
+            ActionStep {
+                id: 1,
+                action_id: 1,
+                sequence_index: 0,
+                action_effect: ActionEffect::Attack(Damage {
+                    amount: 5,
+                    damage_type: DamageType::Physical,
+                }),
+                intensity: Intensity::Normal,
+                trait_name: None,
+            },
+        );
+        let target = ctx.ecs().new();
+        target.clone().upsert_new_hp(10, 10, 1, 0, 0);
+        target.clone().upsert_new_total_stat_block_cache(StatBlock {
+            defense: 10,
+            ..Default::default()
+        });
+        let attacker = ctx.ecs().new();
+        attacker
+            .clone()
+            .upsert_new_action_state(target.entity_id(), 1, 0);
+
+        action_system(ctx.ecs());
+
+        assert_eq!(target.hp().map(|hp| hp.accumulated_damage), Some(4));
+    }
+
+    #[test]
+    fn prominence_uses_the_loaded_rules() {
+        ecs::memory::reset();
+        let ctx = spacetimedb::ReducerContext::__dummy();
+        for (condition, weight) in [
+            (ProminenceCondition::Hp, 32),
+            (ProminenceCondition::Downed, -32),
+            (ProminenceCondition::Item, 8),
+        ] {
+            ProminenceRule::insert(&ctx, ProminenceRule { condition, weight });
+        }
+        let creature = ctx.ecs().new();
+        creature.clone().upsert_new_hp(5, 10, 0, 0, 0);
+        let item = ctx.ecs().new();
+        item.clone().upsert_new_item();
+
+        entity_prominence_system(ctx.ecs());
+
+        assert_eq!(creature.entity_prominence().map(|p| p.prominence), Some(32));
+        assert_eq!(item.entity_prominence().map(|p| p.prominence), Some(8));
+
+        creature.clone().upsert_new_hp(0, 10, 0, 0, 0);
+        entity_prominence_system(ctx.ecs());
+
+        assert_eq!(creature.entity_prominence().map(|p| p.prominence), Some(0));
+    }
+
+    #[test]
+    fn encounters_respawn_after_the_room_is_cleared() {
+        ecs::memory::reset();
+        let ctx = ReducerContext::__dummy();
+        let delay = TimeDuration::from_micros(1_000);
+        EncounterBlob::insert(
+            &ctx,
+            EncounterBlob {
+                id: 1,
+                name: "enemy".to_string(),
+                blob: EntityBlob::default(),
+            },
+        );
+        EncounterBlob::insert(
+            &ctx,
+            EncounterBlob {
+                id: 2,
+                name: "slime".to_string(),
+                blob: EntityBlob {
+                    hp: Some(HpComponent::new(8, 8, 0, 0, 0)),
+                    enemy_controller: Some(EnemyControllerComponent::new()),
+                    ..Default::default()
+                },
+            },
+        );
+        Encounter::insert(
+            &ctx,
+            Encounter {
+                id: 1,
+                categoric_blob_id: 1,
+                blob_ids: vec![2, 2],
+                categoric_blob_name: None,
+                blob_names: vec![],
+                respawn_delay: Some(delay),
+            },
+        );
+        let room = ctx.ecs().new();
+        room.clone().upsert_new_pending_encounter(1);
+        let room_id = room.entity_id();
+        let living_enemies = || {
+            ctx.ecs()
+                .iter_location_by_location_entity_id(&room_id)
+                .filter(|e| e.enemy_controller().is_some() && e.hp().is_some_and(|hp| hp.hp > 0))
+                .count()
+        };
+
+        // Nothing spawns until a player enters the room.
+        encounter_system(ctx.ecs());
+        assert_eq!(living_enemies(), 0);
+        let player = ctx.ecs().new();
+        player
+            .clone()
+            .upsert_new_player_controller(Identity::ZERO)
+            .upsert_new_location(room_id);
+        encounter_system(ctx.ecs());
+        assert_eq!(living_enemies(), 2);
+        assert_eq!(room.pending_encounter().map(|e| e.encounter_id), None);
+        assert_eq!(room.active_encounter().map(|e| e.encounter_id), Some(1));
+
+        encounter_system(ctx.ecs());
+        assert!(room.encounter_respawn_timer().is_none());
+
+        for e in ctx.ecs().iter_location_by_location_entity_id(&room_id) {
+            if e.enemy_controller().is_some() {
+                e.upsert_new_hp(0, 8, 0, 0, 0);
+            }
+        }
+        encounter_system(ctx.ecs());
+        assert_eq!(
+            room.encounter_respawn_timer().map(|t| t.timestamp),
+            ctx.timestamp.checked_add(delay)
+        );
+
+        // Once the timer expires, the encounter waits for the next visit.
+        player.clone().delete_location();
+        let mut later = ReducerContext::__dummy();
+        later.timestamp = room
+            .encounter_respawn_timer()
+            .map_or(ctx.timestamp, |t| t.timestamp);
+        encounter_system(later.ecs());
+        assert!(room.encounter_respawn_timer().is_none());
+        assert_eq!(room.active_encounter().map(|e| e.encounter_id), None);
+        assert_eq!(room.pending_encounter().map(|e| e.encounter_id), Some(1));
+
+        player.upsert_new_location(room_id);
+        encounter_system(later.ecs());
+        assert_eq!(living_enemies(), 2);
+        assert_eq!(room.active_encounter().map(|e| e.encounter_id), Some(1));
+    }
+}
@@ -14,7 +14,13 @@ const attackEvent = (
   ({
     ownerEntityId: owner,
     targetEntityId: target,
-    eventType: { tag: "ActionEffect", value: { tag: "Attack", value } },
+    eventType: {
+      tag: "ActionEffect",
+      value: {
+        tag: "Attack",
+        value: { amount: value, damageType: { tag: "Physical" } },
+      },
+    },
   }) as unknown as EntityEvent;
 
 test("EventsPanel narrates observable events as they arrive", () => {
@@ -1,16 +1,35 @@
-import { Action, ActionEffect, ActionType } from "../../stdb/types";
+import {
+  Action,
+  ActionEffect,
+  ActionType,
+  ArmamentType,
+  Intensity,
+  SpeedType,
+  WeightType,
+} from "../../stdb/types";
+
+export type ActionStepAsset = ActionEffect & { intensity?: Intensity["tag"] };
 
 export type ActionAsset = {
   name: Action["name"];
   type: ActionType["tag"]; // WIP Remove actionType, and derive it from steps.
   appearance: { displayName: string; beginTemplate: string };
-  steps: ActionEffect[];
+  renderer?: {
+    weight: WeightType["tag"];
+    speed: SpeedType["tag"];
+    armament: ArmamentType["tag"];
+  };
+  steps: ActionStepAsset[];
 };
 
 const Rest = { tag: "Rest" } as const satisfies ActionEffect;
 const Move = { tag: "Move" } as const satisfies ActionEffect;
-const Attack = (value: number) =>
-  ({ tag: "Attack", value }) as const satisfies ActionEffect;
+const Attack = (amount: number, intensity: Intensity["tag"] = "Normal") =>
+  ({
+    tag: "Attack",
+    value: { amount, damageType: { tag: "Physical" } },
+    intensity,
+  }) as const satisfies ActionStepAsset;
 const Heal = (value: number) =>
   ({ tag: "Heal", value }) as const satisfies ActionEffect;
 
@@ -40,6 +59,7 @@ export const ACTIONS = [
       displayName: "Bop",
       beginTemplate: "{0:sentence:subject} wound up to bop {1:object}.",
     },
+    renderer: { weight: "Neutral", speed: "Neutral", armament: "Fist" },
     steps: [Rest, Rest, Attack(1), Rest],
   },
   {
@@ -49,7 +69,8 @@ export const ACTIONS = [
       displayName: "Boppity Bop",
       beginTemplate: "{0:sentence:subject} wound up to boppity-bop {1:object}.",
     },
-    steps: [Rest, Rest, Attack(1), Rest, Attack(1), Rest, Rest],
+    renderer: { weight: "Light", speed: "Fast", armament: "Fist" },
+    steps: [Rest, Rest, Attack(1), Rest, Attack(1, "Powerful"), Rest, Rest],
   },
   {
     name: "divine_heal",
@@ -69,6 +90,7 @@ export const ACTIONS = [
       beginTemplate:
         "{0:sentence:subject} sprayed a glob of slime at {1:object}.",
     },
+    renderer: { weight: "Heavy", speed: "Slow", armament: "Spout" },
     steps: [Rest, Rest, Rest, Attack(1), Rest, Rest],
   },
   {
@@ -78,6 +100,7 @@ export const ACTIONS = [
       displayName: "Scratch",
       beginTemplate: "{0:sentence:subject} brandished its claws at {1:object}.",
     },
+    renderer: { weight: "Light", speed: "Fast", armament: "Claw" },
     steps: [Rest, Rest, Attack(1), Rest, Rest, Rest, Rest],
   },
 ] as const satisfies readonly ActionAsset[];
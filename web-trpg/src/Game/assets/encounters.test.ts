@@ -7,21 +7,19 @@ import {
   getEncounterIdsSampler,
 } from "./encounters";
 
-test("getEncounters resolves categoric and blob names to ids", () => {
+test("getEncounters references categoric and blob names", () => {
   const encounters = getEncounters();
   expect(encounters.length).toBe(ENCOUNTERS.length);
   const slime1 = encounters[0];
-  expect(slime1.categoricBlobId).toBe(
-    ENCOUNTER_BLOBS.findIndex((b) => b.name === "encounter_enemy"),
-  );
-  expect(slime1.blobIds).toEqual([
-    ENCOUNTER_BLOBS.findIndex((b) => b.name === "slime"),
-  ]);
+  expect(slime1.categoricBlobName).toBe("encounter_enemy");
+  expect(slime1.blobNames).toEqual(["slime"]);
+  expect(slime1.blobIds).toEqual([]);
 });
 
-test("getEncounterBlobs builds an EntityBlob per blob with a sequential id", () => {
+test("getEncounterBlobs builds a named EntityBlob per blob with a sequential id", () => {
   const blobs = getEncounterBlobs();
   expect(blobs.map((b) => b.id)).toEqual(ENCOUNTER_BLOBS.map((_, i) => i));
+  expect(blobs.map((b) => b.name)).toEqual(ENCOUNTER_BLOBS.map((b) => b.name));
 });
 
 test("getEncounterIdsSampler resolves encounter names to weighted ids", () => {
@@ -37,6 +37,7 @@ export const ENCOUNTER_BLOBS = [
 export const getEncounterBlobs = (): EncounterBlob[] =>
   ENCOUNTER_BLOBS.map((asset, id) => ({
     id,
+    name: asset.name,
     blob: getEntityBlob(asset.blob),
   }));
 
@@ -45,7 +46,7 @@ export type EncounterAsset = Simplify<
     name: string;
     categoricBlobName: (typeof CATEGORICAL_BLOBS)[number]["name"];
     blobNames: (typeof ENCOUNTER_BLOBS)[number]["name"][];
-  } & Omit<Encounter, "id" | "categoricBlobId" | "blobIds">
+  } & Omit<Encounter, "id" | "categoricBlobId" | "blobIds" | "respawnDelay">
 >;
 
 export const ENCOUNTERS = [
@@ -84,12 +85,11 @@ export const ENCOUNTERS = [
 export const getEncounters = (): Encounter[] =>
   ENCOUNTERS.map((asset, id) => ({
     id,
-    categoricBlobId: ENCOUNTER_BLOBS.findIndex(
-      (b) => b.name === asset.categoricBlobName,
-    ),
-    blobIds: asset.blobNames.map((name) =>
-      ENCOUNTER_BLOBS.findIndex((b) => b.name === name),
-    ),
+    categoricBlobId: 0,
+    blobIds: [],
+    categoricBlobName: asset.categoricBlobName,
+    blobNames: [...asset.blobNames],
+    respawnDelay: undefined,
   }));
 
 export type EncountersSamplerAsset = Simplify<
@@ -27,6 +27,8 @@ test("getEntityBlob resolves a name and appearance features, omitting absent fie
   expect(blob.appearanceFeatures?.appearanceFeatureIndexes).toEqual([
     appearanceFeatures.findIndex((f) => f.name === "rock"),
   ]);
+  expect(blob.blobId).toBe(0n);
+  expect(blob.deactivatedEntityId).toBeUndefined();
   expect(blob.baseline).toBeUndefined();
   expect(blob.actionHotkeys).toBeUndefined();
 });
@@ -63,6 +63,9 @@ const getAppearanceFeatures = (
 export const getEntityBlob = (entityBlobAsset: EntityBlobAsset): EntityBlob => {
   return {
     ...entityBlobAsset,
+    // Asset blobs are not deactivated entities, so they carry no key.
+    blobId: 0n,
+    deactivatedEntityId: undefined,
     baseline: entityBlobAsset.baseline
       ? {
           entityId: 0n,
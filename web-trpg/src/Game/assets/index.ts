@@ -28,6 +28,9 @@ export type {
   LocationMapAsset,
 };
 
+/** Bump on every asset change, since `update_assets` only accepts newer packs. */
+export const ASSET_PACK_VERSION = 2;
+
 export const actions = ACTIONS as readonly ActionAsset[];
 export const appearanceFeatures =
   APPEARANCE_FEATURES as readonly AppearanceFeatureAsset[];
@@ -0,0 +1,9 @@
+import { test, expect } from "bun:test";
+import { LEVELS, getLevels } from "./levels";
+
+test("getLevels references traits by name", () => {
+  const levels = getLevels();
+  expect(levels.map((l) => l.level)).toEqual(LEVELS.map((l) => l.level));
+  expect(levels[1].traitNames).toEqual(["big"]);
+  expect(levels[1].traitIds).toEqual([]);
+});
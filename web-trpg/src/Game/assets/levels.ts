@@ -0,0 +1,23 @@
+import { Level } from "../../stdb/types";
+import { Simplify } from "../../structural/Simplify";
+import { TRAITS } from "./traits";
+
+export type LevelAsset = Simplify<
+  {
+    traitNames: (typeof TRAITS)[number]["name"][];
+  } & Omit<Level, "traitIds">
+>;
+
+export const LEVELS = [
+  { level: 1, xp: 0, traitNames: [] },
+  { level: 2, xp: 10, traitNames: ["big"] },
+  { level: 3, xp: 30, traitNames: ["huge"] },
+] as const satisfies readonly LevelAsset[];
+
+export const getLevels = (): Level[] =>
+  LEVELS.map((asset) => ({
+    level: asset.level,
+    xp: asset.xp,
+    traitIds: [],
+    traitNames: [...asset.traitNames],
+  }));
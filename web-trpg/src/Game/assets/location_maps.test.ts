@@ -1,5 +1,4 @@
 import { test, expect } from "bun:test";
-import { locationMapThemes } from ".";
 import {
   LOCATION_MAPS,
   LOCATION_MAP_THEMES,
@@ -18,12 +17,10 @@ test("getLocationMapThemes builds selectors for decorations, paths, and rooms",
   );
 });
 
-test("getLocationMaps resolves theme names to ids and builds the encounter sampler", () => {
+test("getLocationMaps references the theme by name and builds the encounter sampler", () => {
   const maps = getLocationMaps(LOCATION_MAPS);
   const cave = maps.find((m) => m.name === "beginner_cave");
-  expect(cave?.themeId).toBe(
-    locationMapThemes.findIndex((t) => t.name === "cave"),
-  );
+  expect(cave?.themeName).toBe("cave");
   expect(cave?.encounterIdsSampler.selections.length).toBe(4);
 });
 
@@ -1,4 +1,3 @@
-import { locationMapThemes } from ".";
 import {
   Layout,
   LocationMap,
@@ -153,7 +152,7 @@ export const getLocationMaps = (
   assets.map((asset, id) => ({
     ...asset,
     id,
-    themeId: locationMapThemes.findIndex((t) => t.name === asset.themeName),
+    themeId: 0,
     encounterIdsSampler: getEncounterIdsSampler(asset.encountersSampler),
   }));
 
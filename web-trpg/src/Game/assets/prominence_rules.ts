@@ -0,0 +1,22 @@
+import { ProminenceRule } from "../../stdb/types";
+
+/**
+ * Prominence is the sum of the weights of every matching rule, so paths sort
+ * first, then players, enemies, other creatures and finally items. Downed
+ * creatures lose the weight of being a creature.
+ */
+export const PROMINENCE_RULES = [
+  { condition: "Path", weight: 256 },
+  { condition: "PlayerController", weight: 128 },
+  { condition: "EnemyController", weight: 64 },
+  { condition: "Hp", weight: 32 },
+  { condition: "Downed", weight: -32 },
+  { condition: "Equipped", weight: 16 },
+  { condition: "Item", weight: 8 },
+] as const;
+
+export const getProminenceRules = (): ProminenceRule[] =>
+  PROMINENCE_RULES.map((rule) => ({
+    condition: { tag: rule.condition },
+    weight: rule.weight,
+  }));
@@ -34,8 +34,10 @@ export const WithStdb = ({ children }: { children: ReactNode }) => {
         (window as any).dev = (window as any).dev || {};
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
         (window as any).dev.connection = connection;
-        // eslint-disable-next-line @typescript-eslint/no-explicit-any
-        (window as any).dev.init = () => init(connection);
+        if (import.meta.env.DEV) {
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any
+          (window as any).dev.init = () => init(connection);
+        }
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
         (window as any).dev.getAll = () => {
           return Object.fromEntries(
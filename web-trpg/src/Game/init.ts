@@ -12,6 +12,7 @@ import {
 import {
   actions,
   appearanceFeatures,
+  ASSET_PACK_VERSION,
   baselines,
   entityBlobs,
   locationMaps,
@@ -22,11 +23,13 @@ import {
 } from "./assets";
 import { getEncounterBlobs, getEncounters } from "./assets/encounters";
 import { getEntityBlob } from "./assets/entity_blobs";
+import { getLevels } from "./assets/levels";
 import {
   getLocationMapConnections,
   getLocationMaps,
   getLocationMapThemes,
 } from "./assets/location_maps";
+import { getProminenceRules } from "./assets/prominence_rules";
 
 const assetToStatBlock = (asset: StatBlockAsset): StatBlock => {
   return {
@@ -34,9 +37,15 @@ const assetToStatBlock = (asset: StatBlockAsset): StatBlock => {
     mhp: asset.mhp ?? 0,
     defense: asset.defense ?? 0,
     mep: asset.mep ?? 0,
-    actionIds: (asset.actionNames ?? []).map((name) =>
-      actions.findIndex((a) => a.name === name),
-    ),
+    fireResistance: asset.fireResistance ?? 0,
+    coldResistance: asset.coldResistance ?? 0,
+    lightningResistance: asset.lightningResistance ?? 0,
+    poisonResistance: asset.poisonResistance ?? 0,
+    accuracy: asset.accuracy ?? 0,
+    evasion: asset.evasion ?? 0,
+    critChance: asset.critChance ?? 0,
+    critMultiplier: asset.critMultiplier ?? 0,
+    actionIds: [],
     appearanceFeatureIds: (asset.appearanceFeatureNames ?? []).map((name) =>
       appearanceFeatures.findIndex((af) => af.name === name),
     ),
@@ -49,6 +58,11 @@ const getActions = () =>
       id,
       name: a.name,
       actionType: { tag: a.type },
+      renderer: a.renderer && {
+        weightType: { tag: a.renderer.weight },
+        speedType: { tag: a.renderer.speed },
+        armamentType: { tag: a.renderer.armament },
+      },
     };
   });
 
@@ -64,7 +78,10 @@ const getActionSteps = () => {
         case "Attack":
           return {
             tag: "Attack",
-            value: s.value ?? 0,
+            value: {
+              amount: s.value.amount,
+              damageType: s.value.damageType,
+            },
           };
         case "Heal":
           return {
@@ -81,6 +98,8 @@ const getActionSteps = () => {
         actionId: actionId,
         sequenceIndex,
         actionEffect,
+        intensity: { tag: a.steps[sequenceIndex].intensity ?? "Normal" },
+        traitName: undefined,
       }),
     );
   });
@@ -107,6 +126,7 @@ const getBaselines = () =>
       id,
       name: b.name,
       statBlock: assetToStatBlock(b),
+      actionNames: b.actionNames ?? [],
     } as Baseline;
   });
 
@@ -116,28 +136,47 @@ const getTraits = () =>
       id,
       name: t.name,
       statBlock: assetToStatBlock(t),
+      actionNames: t.actionNames ?? [],
     } as Trait;
   });
 
-export const init = (connection: DbConnection) => {
-  connection.reducers.pushAssets({
-    assetPack: {
-      actions: getActions(),
-      actionSteps: getActionSteps(),
-      appearanceFeatures: getAppearanceFeatures(),
+/**
+ * Pushes the bundled assets. The server only accepts this from an admin, such
+ * as the identity that published the module, so a rejection is rethrown for
+ * the caller rather than only logged.
+ */
+export const init = async (connection: DbConnection) => {
+  try {
+    await connection.reducers.pushAssets({
+      assetPack: {
+        version: ASSET_PACK_VERSION,
 
-      baselines: getBaselines(),
-      traits: getTraits(),
+        actions: getActions(),
+        actionSteps: getActionSteps(),
+        appearanceFeatures: getAppearanceFeatures(),
+        locales: [],
+        localizedAppearanceFeatures: [],
 
-      encounterBlobs: getEncounterBlobs(),
-      encounters: getEncounters(),
+        baselines: getBaselines(),
+        traits: getTraits(),
+        levels: getLevels(),
 
-      locationMapThemes: getLocationMapThemes(locationMapThemes),
-      locationMaps: getLocationMaps(locationMaps),
-      locationMapConnections: getLocationMapConnections(locationMaps),
-      instantiateEntityBlobs: entityBlobs.map(getEntityBlob),
+        encounterBlobs: getEncounterBlobs(),
+        encounters: getEncounters(),
 
-      newPlayerBlob: getEntityBlob(newPlayerBlob),
-    },
-  });
+        locationMapThemes: getLocationMapThemes(locationMapThemes),
+        locationMaps: getLocationMaps(locationMaps),
+        locationMapConnections: getLocationMapConnections(locationMaps),
+        prominenceRules: getProminenceRules(),
+        instantiateEntityBlobs: entityBlobs.map(getEntityBlob),
+
+        newPlayerBlob: getEntityBlob(newPlayerBlob),
+      },
+    });
+  } catch (error) {
+    throw new Error(
+      `Pushing assets failed, and only admins may push: ${error}`,
+      { cause: error },
+    );
+  }
 };
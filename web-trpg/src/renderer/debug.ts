@@ -1,6 +1,12 @@
 import { actions } from "../Game/assets";
 import { ActionId } from "../Game/trpg";
-import { EntityEvent } from "../stdb/types";
+import {
+  ArmamentType,
+  AttackRenderer,
+  EntityEvent,
+  Intensity,
+  WeightType,
+} from "../stdb/types";
 import {
   createNarrationRenderValue,
   initialNarrationContext,
@@ -34,6 +40,44 @@ const DEFAULT_ACTION_TEMPLATE =
 const getActionTemplate = (actionId: ActionId): string =>
   actions[actionId]?.appearance.beginTemplate ?? DEFAULT_ACTION_TEMPLATE;
 
+const ARMAMENT_VERBS: Record<ArmamentType["tag"], string> = {
+  Blade: "slashed",
+  Sword: "slashed",
+  Club: "clubbed",
+  Staff: "struck",
+  Fist: "punched",
+  Claw: "clawed",
+  Teeth: "bit",
+  Stick: "poked",
+  Spout: "sprayed",
+};
+
+const WEIGHT_ADVERBS: Record<WeightType["tag"], string> = {
+  Heavy: " heavily",
+  Neutral: "",
+  Light: " lightly",
+};
+
+const INTENSITY_ADVERBS: Record<Intensity["tag"], string> = {
+  Normal: "",
+  Powerful: "fiercely ",
+  Extreme: "savagely ",
+};
+
+/** An attack sentence whose verb comes from the action's renderer data. */
+const getAttackTemplate = (
+  renderer: AttackRenderer | undefined,
+  intensity: Intensity | undefined,
+): string => {
+  if (!renderer) {
+    return "{0:sentence:subject} dealt {2} damage to {1:object}!";
+  }
+  const adverb = INTENSITY_ADVERBS[intensity?.tag ?? "Normal"];
+  const verb = ARMAMENT_VERBS[renderer.armamentType.tag];
+  const weight = WEIGHT_ADVERBS[renderer.weightType.tag];
+  return `{0:sentence:subject} ${adverb}${verb} {1:object}${weight} for {2} damage!`;
+};
+
 /**
  * The sentence for an event, or null when the event is not narrated (e.g.
  * resting, or an effect kind with no player-facing description yet).
@@ -51,10 +95,24 @@ const narrateEvent = (event: EntityEvent): Narration | null => {
       const effect = event.eventType.value;
       switch (effect.tag) {
         case "Attack":
-          return {
-            template: "{0:sentence:subject} dealt {2} damage to {1:object}!",
-            values: [owner, target, effect.value.toString()],
-          };
+          switch (event.attackOutcome?.tag) {
+            case "Miss":
+              return {
+                template: "{0:sentence:subject} missed {1:object}!",
+                values: [owner, target],
+              };
+            case "Critical":
+              return {
+                template:
+                  "{0:sentence:subject} landed a critical hit on {1:object} for {2} damage!",
+                values: [owner, target, effect.value.amount.toString()],
+              };
+            default:
+              return {
+                template: getAttackTemplate(event.renderer, event.intensity),
+                values: [owner, target, effect.value.amount.toString()],
+              };
+          }
         case "Heal":
           return {
             template: "{0:sentence:subject} healed {1:object} for {2}.",
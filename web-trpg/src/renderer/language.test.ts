@@ -14,11 +14,15 @@ const getName: NarrationName = ({ named }) =>
     : String(named ?? "");
 const getClassName = () => "entity";
 
-const actionEffect = (effect: { tag: string; value?: number }): EntityEvent =>
+const actionEffect = (
+  effect: { tag: string; value?: unknown },
+  fields: Record<string, unknown> = {},
+): EntityEvent =>
   ({
     ownerEntityId: 1n,
     targetEntityId: 2n,
     eventType: { tag: "ActionEffect", value: effect },
+    ...fields,
   }) as unknown as EntityEvent;
 
 const startAction = (actionId: number): EntityEvent =>
@@ -48,9 +52,47 @@ for (const [label, language] of [
   });
 
   test(`${label}: narrates an attack, capitalizing the subject and inlining damage`, () => {
-    expect(render(actionEffect({ tag: "Attack", value: 3 }))).toBe(
-      "The goblin dealt 3 damage to the hero!",
+    expect(
+      render(
+        actionEffect({
+          tag: "Attack",
+          value: { amount: 3, damageType: { tag: "Physical" } },
+        }),
+      ),
+    ).toBe("The goblin dealt 3 damage to the hero!");
+  });
+
+  test(`${label}: narrates missed and critical attacks`, () => {
+    const attack = {
+      tag: "Attack",
+      value: { amount: 6, damageType: { tag: "Physical" } },
+    };
+    expect(
+      render(actionEffect(attack, { attackOutcome: { tag: "Miss" } })),
+    ).toBe("The goblin missed the hero!");
+    expect(
+      render(actionEffect(attack, { attackOutcome: { tag: "Critical" } })),
+    ).toBe("The goblin landed a critical hit on the hero for 6 damage!");
+  });
+
+  test(`${label}: picks the attack verb from the renderer and intensity`, () => {
+    const attack = {
+      tag: "Attack",
+      value: { amount: 2, damageType: { tag: "Physical" } },
+    };
+    const renderer = {
+      weightType: { tag: "Heavy" },
+      speedType: { tag: "Slow" },
+      armamentType: { tag: "Sword" },
+    };
+    expect(render(actionEffect(attack, { renderer }))).toBe(
+      "The goblin slashed the hero heavily for 2 damage!",
     );
+    expect(
+      render(
+        actionEffect(attack, { renderer, intensity: { tag: "Extreme" } }),
+      ),
+    ).toBe("The goblin savagely slashed the hero heavily for 2 damage!");
   });
 
   test(`${label}: narrates a heal with its amount`, () => {
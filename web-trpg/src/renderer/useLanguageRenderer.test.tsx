@@ -14,7 +14,13 @@ const attackEvent = (
   ({
     ownerEntityId: owner,
     targetEntityId: target,
-    eventType: { tag: "ActionEffect", value: { tag: "Attack", value } },
+    eventType: {
+      tag: "ActionEffect",
+      value: {
+        tag: "Attack",
+        value: { amount: value, damageType: { tag: "Physical" } },
+      },
+    },
   }) as unknown as EntityEvent;
 
 test("useLanguageRenderer renders an event to React nodes for the player's viewpoint", () => {
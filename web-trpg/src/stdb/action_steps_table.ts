@@ -11,6 +11,7 @@ import {
 } from "spacetimedb";
 import {
   ActionEffect,
+  Intensity,
 } from "./types";
 
 
@@ -21,4 +22,8 @@ export default __t.row({
   get actionEffect() {
     return ActionEffect.name("action_effect");
   },
+  get intensity() {
+    return Intensity;
+  },
+  traitName: __t.option(__t.string()).name("trait_name"),
 });
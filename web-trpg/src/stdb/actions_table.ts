@@ -11,6 +11,7 @@ import {
 } from "spacetimedb";
 import {
   ActionType,
+  AttackRenderer,
 } from "./types";
 
 
@@ -20,4 +21,7 @@ export default __t.row({
   get actionType() {
     return ActionType.name("action_type");
   },
+  get renderer() {
+    return __t.option(AttackRenderer);
+  },
 });
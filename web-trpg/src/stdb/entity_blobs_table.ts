@@ -13,10 +13,12 @@ import {
   ActionHotkeysComponent,
   ActionStateComponent,
   ActionsComponent,
+  EncounterComponent,
   AllegianceComponent,
   AppearanceFeaturesComponent,
   AttackComponent,
   BaselineComponent,
+  BlobNames,
   NameComponent,
   LocationComponent,
   PathComponent,
@@ -25,15 +27,25 @@ import {
   FlagComponent,
   HpComponent,
   EpComponent,
+  ExperienceComponent,
+  ExperienceRewardComponent,
+  LastAttackerComponent,
   PlayerControllerComponent,
   EnemyControllerComponent,
   EntityProminenceComponent,
   TimerComponent,
   LocationMapComponent,
+  LocationMapSourceComponent,
+  LocaleComponent,
 } from "./types";
 
 
 export default __t.row({
+  blobId: __t.u64().primaryKey().name("blob_id"),
+  deactivatedEntityId: __t.option(__t.u64()).name("deactivated_entity_id"),
+  get names() {
+    return __t.option(BlobNames);
+  },
   get name() {
     return __t.option(NameComponent);
   },
@@ -61,12 +73,21 @@ export default __t.row({
   get traitsStatBlockCache() {
     return __t.option(StatBlockCacheComponent).name("traits_stat_block_cache");
   },
+  get totalStatBlockCache() {
+    return __t.option(StatBlockCacheComponent).name("total_stat_block_cache");
+  },
   get traitsStatBlockDirtyFlag() {
     return __t.option(FlagComponent).name("traits_stat_block_dirty_flag");
   },
   get totalStatBlockDirtyFlag() {
     return __t.option(FlagComponent).name("total_stat_block_dirty_flag");
   },
+  get item() {
+    return __t.option(FlagComponent);
+  },
+  get equipped() {
+    return __t.option(FlagComponent);
+  },
   get attack() {
     return __t.option(AttackComponent);
   },
@@ -76,6 +97,15 @@ export default __t.row({
   get ep() {
     return __t.option(EpComponent);
   },
+  get experience() {
+    return __t.option(ExperienceComponent);
+  },
+  get experienceReward() {
+    return __t.option(ExperienceRewardComponent).name("experience_reward");
+  },
+  get lastAttacker() {
+    return __t.option(LastAttackerComponent).name("last_attacker");
+  },
   get playerController() {
     return __t.option(PlayerControllerComponent).name("player_controller");
   },
@@ -103,10 +133,25 @@ export default __t.row({
   get playerDeactivationTimer() {
     return __t.option(TimerComponent).name("player_deactivation_timer");
   },
+  get encounterRespawnTimer() {
+    return __t.option(TimerComponent).name("encounter_respawn_timer");
+  },
   get locationMap() {
     return __t.option(LocationMapComponent).name("location_map");
   },
+  get locationMapSource() {
+    return __t.option(LocationMapSourceComponent).name("location_map_source");
+  },
+  get pendingEncounter() {
+    return __t.option(EncounterComponent).name("pending_encounter");
+  },
+  get activeEncounter() {
+    return __t.option(EncounterComponent).name("active_encounter");
+  },
   get appearanceFeatures() {
     return __t.option(AppearanceFeaturesComponent).name("appearance_features");
   },
+  get locale() {
+    return __t.option(LocaleComponent);
+  },
 });
@@ -35,7 +35,21 @@ import {
 
 // Import all reducer arg schemas
 import ActReducer from "./act_reducer";
+import GmAddTraitReducer from "./gm_add_trait_reducer";
+import GmKillReducer from "./gm_kill_reducer";
+import GmRegenerateMapReducer from "./gm_regenerate_map_reducer";
+import GmRemoveTraitReducer from "./gm_remove_trait_reducer";
+import GmReviveReducer from "./gm_revive_reducer";
+import GmSetEpReducer from "./gm_set_ep_reducer";
+import GmSetHpReducer from "./gm_set_hp_reducer";
+import GmSpawnEncounterBlobReducer from "./gm_spawn_encounter_blob_reducer";
+import GmSpawnWithTraitsReducer from "./gm_spawn_with_traits_reducer";
+import GmTeleportReducer from "./gm_teleport_reducer";
+import GrantAdminReducer from "./grant_admin_reducer";
 import PushAssetsReducer from "./push_assets_reducer";
+import RevokeAdminReducer from "./revoke_admin_reducer";
+import SetLocaleReducer from "./set_locale_reducer";
+import UpdateAssetsReducer from "./update_assets_reducer";
 
 // Import all procedure arg schemas
 
@@ -45,10 +59,13 @@ import ActionStateComponentsRow from "./action_state_components_table";
 import ActionStepsRow from "./action_steps_table";
 import ActionsRow from "./actions_table";
 import ActionsComponentsRow from "./actions_components_table";
+import ActiveEncounterComponentsRow from "./active_encounter_components_table";
 import AllegianceComponentsRow from "./allegiance_components_table";
 import AppearanceFeaturesComponentsRow from "./appearance_features_components_table";
+import AssetPackVersionsRow from "./asset_pack_versions_table";
 import AttackComponentsRow from "./attack_components_table";
 import BaselineComponentsRow from "./baseline_components_table";
+import EncounterRespawnTimerComponentsRow from "./encounter_respawn_timer_components_table";
 import EnemyControllerComponentsRow from "./enemy_controller_components_table";
 import EntitiesRow from "./entities_table";
 import EntityBlobsRow from "./entity_blobs_table";
@@ -56,16 +73,26 @@ import EntityDeletionTimerComponentsRow from "./entity_deletion_timer_components
 import EntityProminenceComponentsRow from "./entity_prominence_components_table";
 import EpComponentsRow from "./ep_components_table";
 import EquipmentStatBlockCacheComponentsRow from "./equipment_stat_block_cache_components_table";
+import EquippedComponentsRow from "./equipped_components_table";
+import ExperienceComponentsRow from "./experience_components_table";
+import ExperienceRewardComponentsRow from "./experience_reward_components_table";
 import HpComponentsRow from "./hp_components_table";
+import ItemComponentsRow from "./item_components_table";
+import LastAttackerComponentsRow from "./last_attacker_components_table";
+import LocaleComponentsRow from "./locale_components_table";
 import LocationComponentsRow from "./location_components_table";
 import LocationMapComponentsRow from "./location_map_components_table";
+import LocationMapSourceComponentsRow from "./location_map_source_components_table";
+import LookRow from "./look_table";
 import NameComponentsRow from "./name_components_table";
 import ObservableEventsRow from "./observable_events_table";
 import PathComponentsRow from "./path_components_table";
+import PendingEncounterComponentsRow from "./pending_encounter_components_table";
 import PlayerControllerComponentsRow from "./player_controller_components_table";
 import PlayerDeactivationTimerComponentsRow from "./player_deactivation_timer_components_table";
 import QueuedActionStateComponentsRow from "./queued_action_state_components_table";
 import StatusStatBlockCacheComponentsRow from "./status_stat_block_cache_components_table";
+import TotalStatBlockCacheComponentsRow from "./total_stat_block_cache_components_table";
 import TotalStatBlockDirtyFlagComponentsRow from "./total_stat_block_dirty_flag_components_table";
 import TraitsComponentsRow from "./traits_components_table";
 import TraitsStatBlockCacheComponentsRow from "./traits_stat_block_cache_components_table";
@@ -138,6 +165,17 @@ const tablesSchema = __schema({
       { name: 'actions_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, ActionsComponentsRow),
+  active_encounter_components: __table({
+    name: 'active_encounter_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'active_encounter_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'active_encounter_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, ActiveEncounterComponentsRow),
   allegiance_components: __table({
     name: 'allegiance_components',
     indexes: [
@@ -163,6 +201,17 @@ const tablesSchema = __schema({
       { name: 'appearance_features_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, AppearanceFeaturesComponentsRow),
+  asset_pack_versions: __table({
+    name: 'asset_pack_versions',
+    indexes: [
+      { accessor: 'id', name: 'asset_pack_versions_id_idx_btree', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'asset_pack_versions_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AssetPackVersionsRow),
   attack_components: __table({
     name: 'attack_components',
     indexes: [
@@ -185,6 +234,17 @@ const tablesSchema = __schema({
       { name: 'baseline_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, BaselineComponentsRow),
+  encounter_respawn_timer_components: __table({
+    name: 'encounter_respawn_timer_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'encounter_respawn_timer_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'encounter_respawn_timer_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, EncounterRespawnTimerComponentsRow),
   enemy_controller_components: __table({
     name: 'enemy_controller_components',
     indexes: [
@@ -210,8 +270,12 @@ const tablesSchema = __schema({
   entity_blobs: __table({
     name: 'entity_blobs',
     indexes: [
+      { accessor: 'blob_id', name: 'entity_blobs_blob_id_idx_btree', algorithm: 'btree', columns: [
+        'blobId',
+      ] },
     ],
     constraints: [
+      { name: 'entity_blobs_blob_id_key', constraint: 'unique', columns: ['blobId'] },
     ],
   }, EntityBlobsRow),
   entity_deletion_timer_components: __table({
@@ -258,6 +322,39 @@ const tablesSchema = __schema({
       { name: 'equipment_stat_block_cache_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, EquipmentStatBlockCacheComponentsRow),
+  equipped_components: __table({
+    name: 'equipped_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'equipped_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'equipped_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, EquippedComponentsRow),
+  experience_components: __table({
+    name: 'experience_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'experience_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'experience_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, ExperienceComponentsRow),
+  experience_reward_components: __table({
+    name: 'experience_reward_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'experience_reward_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'experience_reward_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, ExperienceRewardComponentsRow),
   hp_components: __table({
     name: 'hp_components',
     indexes: [
@@ -269,6 +366,39 @@ const tablesSchema = __schema({
       { name: 'hp_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, HpComponentsRow),
+  item_components: __table({
+    name: 'item_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'item_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'item_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, ItemComponentsRow),
+  last_attacker_components: __table({
+    name: 'last_attacker_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'last_attacker_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'last_attacker_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, LastAttackerComponentsRow),
+  locale_components: __table({
+    name: 'locale_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'locale_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'locale_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, LocaleComponentsRow),
   location_components: __table({
     name: 'location_components',
     indexes: [
@@ -289,11 +419,32 @@ const tablesSchema = __schema({
       { accessor: 'entity_id', name: 'location_map_components_entity_id_idx_btree', algorithm: 'btree', columns: [
         'entityId',
       ] },
+      { accessor: 'location_map_entity_id', name: 'location_map_components_location_map_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'locationMapEntityId',
+      ] },
     ],
     constraints: [
       { name: 'location_map_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, LocationMapComponentsRow),
+  location_map_source_components: __table({
+    name: 'location_map_source_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'location_map_source_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'location_map_source_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, LocationMapSourceComponentsRow),
+  look: __table({
+    name: 'look',
+    indexes: [
+    ],
+    constraints: [
+    ],
+  }, LookRow),
   name_components: __table({
     name: 'name_components',
     indexes: [
@@ -335,6 +486,17 @@ const tablesSchema = __schema({
       { name: 'path_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, PathComponentsRow),
+  pending_encounter_components: __table({
+    name: 'pending_encounter_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'pending_encounter_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'pending_encounter_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, PendingEncounterComponentsRow),
   player_controller_components: __table({
     name: 'player_controller_components',
     indexes: [
@@ -383,6 +545,17 @@ const tablesSchema = __schema({
       { name: 'status_stat_block_cache_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
     ],
   }, StatusStatBlockCacheComponentsRow),
+  total_stat_block_cache_components: __table({
+    name: 'total_stat_block_cache_components',
+    indexes: [
+      { accessor: 'entity_id', name: 'total_stat_block_cache_components_entity_id_idx_btree', algorithm: 'btree', columns: [
+        'entityId',
+      ] },
+    ],
+    constraints: [
+      { name: 'total_stat_block_cache_components_entity_id_key', constraint: 'unique', columns: ['entityId'] },
+    ],
+  }, TotalStatBlockCacheComponentsRow),
   total_stat_block_dirty_flag_components: __table({
     name: 'total_stat_block_dirty_flag_components',
     indexes: [
@@ -432,7 +605,21 @@ const tablesSchema = __schema({
 /** The schema information for all reducers in this module. This is defined the same way as the reducers would have been defined in the server, except the body of the reducer is omitted in code generation. */
 const reducersSchema = __reducers(
   __reducerSchema("act", ActReducer),
+  __reducerSchema("gm_add_trait", GmAddTraitReducer),
+  __reducerSchema("gm_kill", GmKillReducer),
+  __reducerSchema("gm_regenerate_map", GmRegenerateMapReducer),
+  __reducerSchema("gm_remove_trait", GmRemoveTraitReducer),
+  __reducerSchema("gm_revive", GmReviveReducer),
+  __reducerSchema("gm_set_ep", GmSetEpReducer),
+  __reducerSchema("gm_set_hp", GmSetHpReducer),
+  __reducerSchema("gm_spawn_encounter_blob", GmSpawnEncounterBlobReducer),
+  __reducerSchema("gm_spawn_with_traits", GmSpawnWithTraitsReducer),
+  __reducerSchema("gm_teleport", GmTeleportReducer),
+  __reducerSchema("grant_admin", GrantAdminReducer),
   __reducerSchema("push_assets", PushAssetsReducer),
+  __reducerSchema("revoke_admin", RevokeAdminReducer),
+  __reducerSchema("set_locale", SetLocaleReducer),
+  __reducerSchema("update_assets", UpdateAssetsReducer),
 );
 
 /** The schema information for all procedures in this module. This is defined the same way as the procedures would have been defined in the server. */
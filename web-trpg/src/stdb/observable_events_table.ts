@@ -10,7 +10,10 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 import {
+  Intensity,
+  AttackRenderer,
   EventType,
+  AttackOutcome,
 } from "./types";
 
 
@@ -22,4 +25,13 @@ export default __t.row({
     return EventType.name("event_type");
   },
   targetEntityId: __t.u64().name("target_entity_id"),
+  get attackOutcome() {
+    return __t.option(AttackOutcome).name("attack_outcome");
+  },
+  get intensity() {
+    return __t.option(Intensity);
+  },
+  get renderer() {
+    return __t.option(AttackRenderer);
+  },
 });
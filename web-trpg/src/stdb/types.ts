@@ -16,6 +16,9 @@ export const Action = __t.object("Action", {
   get actionType() {
     return ActionType;
   },
+  get renderer() {
+    return __t.option(AttackRenderer);
+  },
 });
 export type Action = __Infer<typeof Action>;
 
@@ -24,7 +27,9 @@ export const ActionEffect = __t.enum("ActionEffect", {
   get Buff() {
     return Buff;
   },
-  Attack: __t.i32(),
+  get Attack() {
+    return Damage;
+  },
   Heal: __t.i32(),
   Rest: __t.unit(),
   Move: __t.unit(),
@@ -32,6 +37,8 @@ export const ActionEffect = __t.enum("ActionEffect", {
   Drop: __t.unit(),
   Equip: __t.unit(),
   Unequip: __t.unit(),
+  AddTrait: __t.u32(),
+  RemoveTrait: __t.u32(),
 });
 export type ActionEffect = __Infer<typeof ActionEffect>;
 
@@ -64,6 +71,10 @@ export const ActionStep = __t.object("ActionStep", {
   get actionEffect() {
     return ActionEffect;
   },
+  get intensity() {
+    return Intensity;
+  },
+  traitName: __t.option(__t.string()),
 });
 export type ActionStep = __Infer<typeof ActionStep>;
 
@@ -83,6 +94,19 @@ export const ActionsComponent = __t.object("ActionsComponent", {
 });
 export type ActionsComponent = __Infer<typeof ActionsComponent>;
 
+export const Admin = __t.object("Admin", {
+  identity: __t.identity(),
+});
+export type Admin = __Infer<typeof Admin>;
+
+export const AdminAuditLog = __t.object("AdminAuditLog", {
+  id: __t.u64(),
+  time: __t.timestamp(),
+  identity: __t.identity(),
+  command: __t.string(),
+});
+export type AdminAuditLog = __Infer<typeof AdminAuditLog>;
+
 export const AllegianceComponent = __t.object("AllegianceComponent", {
   entityId: __t.u64(),
   allegianceEntityId: __t.u64(),
@@ -112,7 +136,22 @@ export const AppearanceFeaturesComponent = __t.object("AppearanceFeaturesCompone
 });
 export type AppearanceFeaturesComponent = __Infer<typeof AppearanceFeaturesComponent>;
 
+// The tagged union or sum type for the algebraic type `ArmamentType`.
+export const ArmamentType = __t.enum("ArmamentType", {
+  Blade: __t.unit(),
+  Sword: __t.unit(),
+  Club: __t.unit(),
+  Staff: __t.unit(),
+  Fist: __t.unit(),
+  Claw: __t.unit(),
+  Teeth: __t.unit(),
+  Stick: __t.unit(),
+  Spout: __t.unit(),
+});
+export type ArmamentType = __Infer<typeof ArmamentType>;
+
 export const AssetPack = __t.object("AssetPack", {
+  version: __t.u32(),
   get actions() {
     return __t.array(Action);
   },
@@ -122,12 +161,21 @@ export const AssetPack = __t.object("AssetPack", {
   get appearanceFeatures() {
     return __t.array(AppearanceFeature);
   },
+  get locales() {
+    return __t.array(Locale);
+  },
+  get localizedAppearanceFeatures() {
+    return __t.array(LocalizedAppearanceFeature);
+  },
   get baselines() {
     return __t.array(Baseline);
   },
   get traits() {
     return __t.array(Trait);
   },
+  get levels() {
+    return __t.array(Level);
+  },
   get encounterBlobs() {
     return __t.array(EncounterBlob);
   },
@@ -143,6 +191,9 @@ export const AssetPack = __t.object("AssetPack", {
   get locationMapConnections() {
     return __t.array(LocationMapConnection);
   },
+  get prominenceRules() {
+    return __t.array(ProminenceRule);
+  },
   get instantiateEntityBlobs() {
     return __t.array(EntityBlob);
   },
@@ -152,18 +203,46 @@ export const AssetPack = __t.object("AssetPack", {
 });
 export type AssetPack = __Infer<typeof AssetPack>;
 
+export const AssetPackVersion = __t.object("AssetPackVersion", {
+  id: __t.u8(),
+  version: __t.u32(),
+});
+export type AssetPackVersion = __Infer<typeof AssetPackVersion>;
+
 export const AttackComponent = __t.object("AttackComponent", {
   entityId: __t.u64(),
   attack: __t.i32(),
 });
 export type AttackComponent = __Infer<typeof AttackComponent>;
 
+// The tagged union or sum type for the algebraic type `AttackOutcome`.
+export const AttackOutcome = __t.enum("AttackOutcome", {
+  Hit: __t.unit(),
+  Critical: __t.unit(),
+  Miss: __t.unit(),
+});
+export type AttackOutcome = __Infer<typeof AttackOutcome>;
+
+export const AttackRenderer = __t.object("AttackRenderer", {
+  get weightType() {
+    return WeightType;
+  },
+  get speedType() {
+    return SpeedType;
+  },
+  get armamentType() {
+    return ArmamentType;
+  },
+});
+export type AttackRenderer = __Infer<typeof AttackRenderer>;
+
 export const Baseline = __t.object("Baseline", {
   id: __t.u32(),
   name: __t.string(),
   get statBlock() {
     return StatBlock;
   },
+  actionNames: __t.array(__t.string()),
 });
 export type Baseline = __Infer<typeof Baseline>;
 
@@ -173,27 +252,92 @@ export const BaselineComponent = __t.object("BaselineComponent", {
 });
 export type BaselineComponent = __Infer<typeof BaselineComponent>;
 
+export const BlobNames = __t.object("BlobNames", {
+  baseline: __t.option(__t.string()),
+  traits: __t.array(__t.string()),
+  actions: __t.array(__t.string()),
+});
+export type BlobNames = __Infer<typeof BlobNames>;
+
 // The tagged union or sum type for the algebraic type `Buff`.
 export const Buff = __t.enum("Buff", {
   Guard: __t.i32(),
 });
 export type Buff = __Infer<typeof Buff>;
 
+// The tagged union or sum type for the algebraic type `ChangeKind`.
+export const ChangeKind = __t.enum("ChangeKind", {
+  Added: __t.unit(),
+  Changed: __t.unit(),
+  Removed: __t.unit(),
+});
+export type ChangeKind = __Infer<typeof ChangeKind>;
+
+export const ComponentChange = __t.object("ComponentChange", {
+  changeId: __t.u64(),
+  component: __t.string(),
+  entityId: __t.u64(),
+  get kind() {
+    return ChangeKind;
+  },
+});
+export type ComponentChange = __Infer<typeof ComponentChange>;
+
+export const ComponentChangeCursor = __t.object("ComponentChangeCursor", {
+  system: __t.string(),
+  changeId: __t.u64(),
+});
+export type ComponentChangeCursor = __Infer<typeof ComponentChangeCursor>;
+
+export const Damage = __t.object("Damage", {
+  amount: __t.i32(),
+  get damageType() {
+    return DamageType;
+  },
+});
+export type Damage = __Infer<typeof Damage>;
+
+// The tagged union or sum type for the algebraic type `DamageType`.
+export const DamageType = __t.enum("DamageType", {
+  Physical: __t.unit(),
+  Fire: __t.unit(),
+  Cold: __t.unit(),
+  Lightning: __t.unit(),
+  Poison: __t.unit(),
+});
+export type DamageType = __Infer<typeof DamageType>;
+
+export const DeactivatedPlayer = __t.object("DeactivatedPlayer", {
+  identity: __t.identity(),
+  blobId: __t.u64(),
+});
+export type DeactivatedPlayer = __Infer<typeof DeactivatedPlayer>;
+
 export const Encounter = __t.object("Encounter", {
   id: __t.u32(),
   categoricBlobId: __t.u32(),
   blobIds: __t.array(__t.u32()),
+  categoricBlobName: __t.option(__t.string()),
+  blobNames: __t.array(__t.string()),
+  respawnDelay: __t.option(__t.timeDuration()),
 });
 export type Encounter = __Infer<typeof Encounter>;
 
 export const EncounterBlob = __t.object("EncounterBlob", {
   id: __t.u32(),
+  name: __t.string(),
   get blob() {
     return EntityBlob;
   },
 });
 export type EncounterBlob = __Infer<typeof EncounterBlob>;
 
+export const EncounterComponent = __t.object("EncounterComponent", {
+  entityId: __t.u64(),
+  encounterId: __t.u32(),
+});
+export type EncounterComponent = __Infer<typeof EncounterComponent>;
+
 export const EncounterIdSample = __t.object("EncounterIdSample", {
   weight: __t.u8(),
   id: __t.u32(),
@@ -218,6 +362,11 @@ export const Entity = __t.object("Entity", {
 export type Entity = __Infer<typeof Entity>;
 
 export const EntityBlob = __t.object("EntityBlob", {
+  blobId: __t.u64(),
+  deactivatedEntityId: __t.option(__t.u64()),
+  get names() {
+    return __t.option(BlobNames);
+  },
   get name() {
     return __t.option(NameComponent);
   },
@@ -245,12 +394,21 @@ export const EntityBlob = __t.object("EntityBlob", {
   get traitsStatBlockCache() {
     return __t.option(StatBlockCacheComponent);
   },
+  get totalStatBlockCache() {
+    return __t.option(StatBlockCacheComponent);
+  },
   get traitsStatBlockDirtyFlag() {
     return __t.option(FlagComponent);
   },
   get totalStatBlockDirtyFlag() {
     return __t.option(FlagComponent);
   },
+  get item() {
+    return __t.option(FlagComponent);
+  },
+  get equipped() {
+    return __t.option(FlagComponent);
+  },
   get attack() {
     return __t.option(AttackComponent);
   },
@@ -260,6 +418,15 @@ export const EntityBlob = __t.object("EntityBlob", {
   get ep() {
     return __t.option(EpComponent);
   },
+  get experience() {
+    return __t.option(ExperienceComponent);
+  },
+  get experienceReward() {
+    return __t.option(ExperienceRewardComponent);
+  },
+  get lastAttacker() {
+    return __t.option(LastAttackerComponent);
+  },
   get playerController() {
     return __t.option(PlayerControllerComponent);
   },
@@ -287,12 +454,27 @@ export const EntityBlob = __t.object("EntityBlob", {
   get playerDeactivationTimer() {
     return __t.option(TimerComponent);
   },
+  get encounterRespawnTimer() {
+    return __t.option(TimerComponent);
+  },
   get locationMap() {
     return __t.option(LocationMapComponent);
   },
+  get locationMapSource() {
+    return __t.option(LocationMapSourceComponent);
+  },
+  get pendingEncounter() {
+    return __t.option(EncounterComponent);
+  },
+  get activeEncounter() {
+    return __t.option(EncounterComponent);
+  },
   get appearanceFeatures() {
     return __t.option(AppearanceFeaturesComponent);
   },
+  get locale() {
+    return __t.option(LocaleComponent);
+  },
 });
 export type EntityBlob = __Infer<typeof EntityBlob>;
 
@@ -319,6 +501,15 @@ export const EntityEvent = __t.object("EntityEvent", {
     return EventType;
   },
   targetEntityId: __t.u64(),
+  get attackOutcome() {
+    return __t.option(AttackOutcome);
+  },
+  get intensity() {
+    return __t.option(Intensity);
+  },
+  get renderer() {
+    return __t.option(AttackRenderer);
+  },
 });
 export type EntityEvent = __Infer<typeof EntityEvent>;
 
@@ -344,6 +535,19 @@ export const EventType = __t.enum("EventType", {
 });
 export type EventType = __Infer<typeof EventType>;
 
+export const ExperienceComponent = __t.object("ExperienceComponent", {
+  entityId: __t.u64(),
+  xp: __t.u32(),
+  level: __t.u32(),
+});
+export type ExperienceComponent = __Infer<typeof ExperienceComponent>;
+
+export const ExperienceRewardComponent = __t.object("ExperienceRewardComponent", {
+  entityId: __t.u64(),
+  xp: __t.u32(),
+});
+export type ExperienceRewardComponent = __Infer<typeof ExperienceRewardComponent>;
+
 export const FlagComponent = __t.object("FlagComponent", {
   entityId: __t.u64(),
 });
@@ -359,6 +563,20 @@ export const HpComponent = __t.object("HpComponent", {
 });
 export type HpComponent = __Infer<typeof HpComponent>;
 
+// The tagged union or sum type for the algebraic type `Intensity`.
+export const Intensity = __t.enum("Intensity", {
+  Normal: __t.unit(),
+  Powerful: __t.unit(),
+  Extreme: __t.unit(),
+});
+export type Intensity = __Infer<typeof Intensity>;
+
+export const LastAttackerComponent = __t.object("LastAttackerComponent", {
+  entityId: __t.u64(),
+  attackerEntityId: __t.u64(),
+});
+export type LastAttackerComponent = __Infer<typeof LastAttackerComponent>;
+
 // The tagged union or sum type for the algebraic type `Layout`.
 export const Layout = __t.enum("Layout", {
   Path: __t.unit(),
@@ -366,6 +584,42 @@ export const Layout = __t.enum("Layout", {
 });
 export type Layout = __Infer<typeof Layout>;
 
+export const Level = __t.object("Level", {
+  level: __t.u32(),
+  xp: __t.u32(),
+  traitIds: __t.array(__t.u32()),
+  traitNames: __t.array(__t.string()),
+});
+export type Level = __Infer<typeof Level>;
+
+export const Locale = __t.object("Locale", {
+  id: __t.u32(),
+  code: __t.string(),
+  fallbackNoun: __t.string(),
+  wordSeparator: __t.string(),
+  article: __t.option(__t.string()),
+  vowelArticle: __t.option(__t.string()),
+});
+export type Locale = __Infer<typeof Locale>;
+
+export const LocaleComponent = __t.object("LocaleComponent", {
+  entityId: __t.u64(),
+  locale: __t.string(),
+});
+export type LocaleComponent = __Infer<typeof LocaleComponent>;
+
+export const LocalizedAppearanceFeature = __t.object("LocalizedAppearanceFeature", {
+  id: __t.u64(),
+  locale: __t.string(),
+  index: __t.u32(),
+  text: __t.string(),
+  get appearanceFeatureType() {
+    return AppearanceFeatureType;
+  },
+  priority: __t.i32(),
+});
+export type LocalizedAppearanceFeature = __Infer<typeof LocalizedAppearanceFeature>;
+
 export const LocationComponent = __t.object("LocationComponent", {
   entityId: __t.u64(),
   locationEntityId: __t.u64(),
@@ -376,6 +630,7 @@ export const LocationMap = __t.object("LocationMap", {
   id: __t.u32(),
   name: __t.string(),
   themeId: __t.u32(),
+  themeName: __t.option(__t.string()),
   get layout() {
     return Layout;
   },
@@ -404,8 +659,15 @@ export const LocationMapConnection = __t.object("LocationMapConnection", {
 });
 export type LocationMapConnection = __Infer<typeof LocationMapConnection>;
 
+export const LocationMapSourceComponent = __t.object("LocationMapSourceComponent", {
+  entityId: __t.u64(),
+  locationMapId: __t.u32(),
+});
+export type LocationMapSourceComponent = __Infer<typeof LocationMapSourceComponent>;
+
 export const LocationMapTheme = __t.object("LocationMapTheme", {
   id: __t.u32(),
+  name: __t.string(),
   get decorationsSelector() {
     return EntityBlobsSampler;
   },
@@ -420,6 +682,38 @@ export const LocationMapTheme = __t.object("LocationMapTheme", {
 });
 export type LocationMapTheme = __Infer<typeof LocationMapTheme>;
 
+export const Look = __t.object("Look", {
+  roomEntityId: __t.u64(),
+  roomName: __t.option(__t.string()),
+  roomDescription: __t.option(__t.string()),
+  get occupants() {
+    return __t.array(LookOccupant);
+  },
+  get exits() {
+    return __t.array(LookExit);
+  },
+  selfActionIds: __t.array(__t.u32()),
+});
+export type Look = __Infer<typeof Look>;
+
+export const LookExit = __t.object("LookExit", {
+  entityId: __t.u64(),
+  name: __t.option(__t.string()),
+  destinationEntityId: __t.u64(),
+  destinationName: __t.option(__t.string()),
+  actionIds: __t.array(__t.u32()),
+});
+export type LookExit = __Infer<typeof LookExit>;
+
+export const LookOccupant = __t.object("LookOccupant", {
+  entityId: __t.u64(),
+  name: __t.option(__t.string()),
+  description: __t.option(__t.string()),
+  prominence: __t.i32(),
+  actionIds: __t.array(__t.u32()),
+});
+export type LookOccupant = __Infer<typeof LookOccupant>;
+
 export const NameComponent = __t.object("NameComponent", {
   entityId: __t.u64(),
   name: __t.string(),
@@ -438,6 +732,26 @@ export const PlayerControllerComponent = __t.object("PlayerControllerComponent",
 });
 export type PlayerControllerComponent = __Infer<typeof PlayerControllerComponent>;
 
+// The tagged union or sum type for the algebraic type `ProminenceCondition`.
+export const ProminenceCondition = __t.enum("ProminenceCondition", {
+  Path: __t.unit(),
+  PlayerController: __t.unit(),
+  EnemyController: __t.unit(),
+  Hp: __t.unit(),
+  Downed: __t.unit(),
+  Item: __t.unit(),
+  Equipped: __t.unit(),
+});
+export type ProminenceCondition = __Infer<typeof ProminenceCondition>;
+
+export const ProminenceRule = __t.object("ProminenceRule", {
+  get condition() {
+    return ProminenceCondition;
+  },
+  weight: __t.i32(),
+});
+export type ProminenceRule = __Infer<typeof ProminenceRule>;
+
 export const SpecialEntityBlob = __t.object("SpecialEntityBlob", {
   get key() {
     return SpecialEntityBlobKey;
@@ -454,11 +768,27 @@ export const SpecialEntityBlobKey = __t.enum("SpecialEntityBlobKey", {
 });
 export type SpecialEntityBlobKey = __Infer<typeof SpecialEntityBlobKey>;
 
+// The tagged union or sum type for the algebraic type `SpeedType`.
+export const SpeedType = __t.enum("SpeedType", {
+  Slow: __t.unit(),
+  Neutral: __t.unit(),
+  Fast: __t.unit(),
+});
+export type SpeedType = __Infer<typeof SpeedType>;
+
 export const StatBlock = __t.object("StatBlock", {
   attack: __t.i32(),
   mhp: __t.i32(),
   defense: __t.i32(),
   mep: __t.i32(),
+  fireResistance: __t.i32(),
+  coldResistance: __t.i32(),
+  lightningResistance: __t.i32(),
+  poisonResistance: __t.i32(),
+  accuracy: __t.i32(),
+  evasion: __t.i32(),
+  critChance: __t.i32(),
+  critMultiplier: __t.i32(),
   actionIds: __t.array(__t.u32()),
   appearanceFeatureIds: __t.array(__t.u32()),
 });
@@ -490,6 +820,7 @@ export const Trait = __t.object("Trait", {
   get statBlock() {
     return StatBlock;
   },
+  actionNames: __t.array(__t.string()),
 });
 export type Trait = __Infer<typeof Trait>;
 
@@ -499,3 +830,11 @@ export const TraitsComponent = __t.object("TraitsComponent", {
 });
 export type TraitsComponent = __Infer<typeof TraitsComponent>;
 
+// The tagged union or sum type for the algebraic type `WeightType`.
+export const WeightType = __t.enum("WeightType", {
+  Heavy: __t.unit(),
+  Neutral: __t.unit(),
+  Light: __t.unit(),
+});
+export type WeightType = __Infer<typeof WeightType>;
+
@@ -7,8 +7,36 @@ import { type Infer as __Infer } from "spacetimedb";
 
 // Import all reducer arg schemas
 import ActReducer from "../act_reducer";
+import GmAddTraitReducer from "../gm_add_trait_reducer";
+import GmKillReducer from "../gm_kill_reducer";
+import GmRegenerateMapReducer from "../gm_regenerate_map_reducer";
+import GmRemoveTraitReducer from "../gm_remove_trait_reducer";
+import GmReviveReducer from "../gm_revive_reducer";
+import GmSetEpReducer from "../gm_set_ep_reducer";
+import GmSetHpReducer from "../gm_set_hp_reducer";
+import GmSpawnEncounterBlobReducer from "../gm_spawn_encounter_blob_reducer";
+import GmSpawnWithTraitsReducer from "../gm_spawn_with_traits_reducer";
+import GmTeleportReducer from "../gm_teleport_reducer";
+import GrantAdminReducer from "../grant_admin_reducer";
 import PushAssetsReducer from "../push_assets_reducer";
+import RevokeAdminReducer from "../revoke_admin_reducer";
+import SetLocaleReducer from "../set_locale_reducer";
+import UpdateAssetsReducer from "../update_assets_reducer";
 
 export type ActParams = __Infer<typeof ActReducer>;
+export type GmAddTraitParams = __Infer<typeof GmAddTraitReducer>;
+export type GmKillParams = __Infer<typeof GmKillReducer>;
+export type GmRegenerateMapParams = __Infer<typeof GmRegenerateMapReducer>;
+export type GmRemoveTraitParams = __Infer<typeof GmRemoveTraitReducer>;
+export type GmReviveParams = __Infer<typeof GmReviveReducer>;
+export type GmSetEpParams = __Infer<typeof GmSetEpReducer>;
+export type GmSetHpParams = __Infer<typeof GmSetHpReducer>;
+export type GmSpawnEncounterBlobParams = __Infer<typeof GmSpawnEncounterBlobReducer>;
+export type GmSpawnWithTraitsParams = __Infer<typeof GmSpawnWithTraitsReducer>;
+export type GmTeleportParams = __Infer<typeof GmTeleportReducer>;
+export type GrantAdminParams = __Infer<typeof GrantAdminReducer>;
 export type PushAssetsParams = __Infer<typeof PushAssetsReducer>;
+export type RevokeAdminParams = __Infer<typeof RevokeAdminReducer>;
+export type SetLocaleParams = __Infer<typeof SetLocaleReducer>;
+export type UpdateAssetsParams = __Infer<typeof UpdateAssetsReducer>;
 